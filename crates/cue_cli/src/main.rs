@@ -4,7 +4,7 @@
 
 use clap::{Parser, Subcommand};
 use cue_common::EXIT_ERROR;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(
@@ -38,30 +38,7 @@ enum Commands {
     },
 
     /// Launch interactive TUI file finder
-    Open {
-        /// Optional initial search query
-        query: Option<String>,
-
-        /// Search mode: keyword, semantic, or hybrid (default)
-        #[arg(long, default_value = "hybrid")]
-        mode: String,
-
-        /// Use semantic search (deprecated, use --mode=semantic)
-        #[arg(long)]
-        semantic: bool,
-
-        /// Filter by tags (comma-separated, e.g., "auth,api")
-        #[arg(long, value_delimiter = ',')]
-        tags: Option<Vec<String>>,
-
-        /// Filter by priority (e.g., "high", "medium", "low")
-        #[arg(long)]
-        priority: Option<String>,
-
-        /// Filter by assignee (e.g., "@tctri")
-        #[arg(long)]
-        assignee: Option<String>,
-    },
+    Open(OpenArgs),
 
     /// Watch for file changes and auto-regenerate scene
     Watch,
@@ -88,30 +65,75 @@ enum Commands {
     },
 
     /// List all cards (alias for 'card list')
-    List {
-        /// Filter by status (active, done, archived, all)
-        #[arg(long, default_value = "active")]
-        status: String,
+    List(ListArgs),
 
-        /// Filter by tags (comma-separated, OR logic)
-        #[arg(long, value_delimiter = ',')]
-        tags: Option<Vec<String>>,
+    /// Rename a doc or card and rewrite links and references to it
+    Mv {
+        /// Current path (or card ID)
+        from: String,
 
-        /// Filter by priority (critical, high, medium, low)
+        /// New path (or card ID)
+        to: String,
+
+        /// Show what would change without touching any file
         #[arg(long)]
-        priority: Option<String>,
+        dry_run: bool,
+    },
 
-        /// Filter by assignee (e.g., @username)
+    /// Import cards from a GitHub, Jira or CSV export
+    Import {
+        /// Export file to read
+        file: PathBuf,
+
+        /// Export format: github (gh issue list --json), jira or csv
         #[arg(long)]
-        assignee: Option<String>,
+        format: String,
+
+        /// Map a card field to a CSV column, e.g. --map title=Name (csv only)
+        #[arg(long = "map", value_name = "FIELD=COLUMN")]
+        mappings: Vec<String>,
 
-        /// Filter by created date (YYYY, YYYY-MM, YYYY-MM-DD, >2w, <7d)
+        /// Show what would be imported without creating cards
         #[arg(long)]
-        created: Option<String>,
+        dry_run: bool,
+    },
+
+    /// Export cards as CSV, JSON Lines or iCalendar
+    Export {
+        /// Output format: csv, jsonl or ics
+        #[arg(long)]
+        format: String,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        #[command(flatten)]
+        filters: FilterArgs,
+    },
+
+    /// Show per-milestone completion and overdue cards
+    Milestone {
+        /// Only show this milestone
+        name: Option<String>,
+    },
+
+    /// Summarize work across cards
+    Report {
+        #[command(subcommand)]
+        action: ReportAction,
+    },
 
-        /// Filter by updated date (YYYY, YYYY-MM, YYYY-MM-DD, >2w, <7d)
+    /// Markdown summary of what changed since a point in time, for standups
+    Digest {
+        /// Start of the window: a duration ago (e.g. 1d, 1w), a date
+        /// (YYYY-MM-DD) or an RFC 3339 timestamp
+        #[arg(long, default_value = "1d")]
+        since: String,
+
+        /// Output as JSON
         #[arg(long)]
-        updated: Option<String>,
+        json: bool,
     },
 
     /// Hard reset of cache
@@ -149,6 +171,145 @@ enum Commands {
     },
 }
 
+/// Options for `cue open`
+#[derive(clap::Args)]
+struct OpenArgs {
+    /// Optional initial search query
+    query: Option<String>,
+
+    /// Search mode: keyword, semantic, or hybrid (default)
+    #[arg(long, default_value = "hybrid")]
+    mode: String,
+
+    /// Use semantic search (deprecated, use --mode=semantic)
+    #[arg(long)]
+    semantic: bool,
+
+    /// Filter by tags (comma-separated, e.g., "auth,api")
+    #[arg(long, value_delimiter = ',')]
+    tags: Option<Vec<String>>,
+
+    /// Filter by priority (e.g., "high", "medium", "low")
+    #[arg(long)]
+    priority: Option<String>,
+
+    /// Filter by assignee (e.g., "@tctri")
+    #[arg(long)]
+    assignee: Option<String>,
+
+    /// Query expression (e.g., 'tag:auth -status:done "login flow"')
+    #[arg(short = 'q', long = "query")]
+    filter_query: Option<String>,
+
+    /// Also search archived cards
+    #[arg(long)]
+    archived: bool,
+}
+
+/// Card filters shared by `cue list` and `cue export`
+#[derive(clap::Args, Default)]
+struct FilterArgs {
+    /// Filter by status (active, done, archived, all)
+    #[arg(long, default_value = "active")]
+    status: String,
+
+    /// Filter by tags (comma-separated, OR logic)
+    #[arg(long, value_delimiter = ',')]
+    tags: Option<Vec<String>>,
+
+    /// Filter by priority (levels from the [priorities] config)
+    #[arg(long)]
+    priority: Option<String>,
+
+    /// Filter by assignee (e.g., @username)
+    #[arg(long)]
+    assignee: Option<String>,
+
+    /// Filter by created date (YYYY, YYYY-MM, YYYY-MM-DD, >2w, <7d)
+    #[arg(long)]
+    created: Option<String>,
+
+    /// Filter by updated date (YYYY, YYYY-MM, YYYY-MM-DD, >2w, <7d)
+    #[arg(long)]
+    updated: Option<String>,
+
+    /// Only list children of this epic
+    #[arg(long)]
+    parent: Option<String>,
+
+    /// Filter by due date (due<1w, due:overdue, <2024-06-01)
+    #[arg(long)]
+    due: Option<String>,
+
+    /// Filter by milestone
+    #[arg(long)]
+    milestone: Option<String>,
+
+    /// Query expression (e.g., 'status:todo (tag:auth OR tag:api) updated>2w')
+    #[arg(short = 'q', long)]
+    query: Option<String>,
+
+    /// Only cards nobody holds a live claim on
+    #[arg(long)]
+    unclaimed: bool,
+}
+
+impl FilterArgs {
+    /// Build the core filters; a `status:` term in the query replaces `--status`
+    fn task_filters(&self) -> anyhow::Result<cue_core::task_filters::TaskFilters> {
+        use cue_core::task_filters::{parse_date_filter, parse_due_filter, TaskFilters};
+
+        let mut filters = TaskFilters::default();
+
+        // Query expression (its own status terms replace the default status)
+        if let Some(q) = &self.query {
+            filters.query = Some(cue_core::query::Query::parse(q)?);
+        }
+        let query_sets_status = filters
+            .query
+            .as_ref()
+            .is_some_and(|q| q.mentions_field("status"));
+
+        // Status filter ("all" includes the archive)
+        if self.status == "all" {
+            filters.include_archived = true;
+        } else if !query_sets_status {
+            filters.status = Some(self.status.clone());
+        }
+
+        filters.tags = self.tags.clone();
+        filters.priority = self.priority.clone();
+        filters.assignee = self.assignee.clone();
+        filters.parent = self.parent.clone();
+        filters.milestone = self.milestone.clone();
+        filters.unclaimed = self.unclaimed;
+
+        // Date filters
+        if let Some(due_str) = &self.due {
+            filters.due = Some(parse_due_filter(due_str)?);
+        }
+        if let Some(created_str) = &self.created {
+            filters.created = Some(parse_date_filter(created_str)?);
+        }
+        if let Some(updated_str) = &self.updated {
+            filters.updated = Some(parse_date_filter(updated_str)?);
+        }
+
+        Ok(filters)
+    }
+}
+
+/// Arguments for `cue list`
+#[derive(clap::Args, Default)]
+struct ListArgs {
+    #[command(flatten)]
+    filters: FilterArgs,
+
+    /// Also show assignee and time logged
+    #[arg(short, long)]
+    long: bool,
+}
+
 #[derive(Subcommand)]
 enum CardAction {
     /// Create a new card
@@ -165,17 +326,33 @@ enum CardAction {
         #[arg(short, long, value_delimiter = ',')]
         tags: Option<Vec<String>>,
 
-        /// Priority: low, medium, high, critical
-        #[arg(short, long, default_value = "medium")]
-        priority: String,
+        /// Priority from the [priorities] config (default: medium)
+        #[arg(short, long)]
+        priority: Option<String>,
 
         /// Assignee
         #[arg(short, long)]
         assignee: Option<String>,
 
+        /// Template from .cuedeck/templates/ (e.g. bug, spike)
+        #[arg(long)]
+        template: Option<String>,
+
         /// Task IDs this depends on (comma-separated)
         #[arg(short, long, value_delimiter = ',')]
         depends_on: Option<Vec<String>>,
+
+        /// Parent (epic) task ID
+        #[arg(long)]
+        parent: Option<String>,
+
+        /// Due date (YYYY-MM-DD)
+        #[arg(long)]
+        due: Option<String>,
+
+        /// Milestone name
+        #[arg(short, long)]
+        milestone: Option<String>,
     },
 
     /// Show task dependencies
@@ -209,6 +386,20 @@ enum CardAction {
     List {
         #[arg(long, default_value = "active")]
         status: String,
+
+        /// Show cards nested under their epics
+        #[arg(long)]
+        tree: bool,
+    },
+
+    /// Show a card's metadata and the commits that mention it
+    Show {
+        /// Task ID (defaults to the card for the current git branch)
+        id: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Open card in $EDITOR
@@ -216,6 +407,217 @@ enum CardAction {
 
     /// Move card to archived status
     Archive { id: String },
+
+    /// Change a card's status, or list the statuses it can move to
+    Move {
+        /// Task ID
+        id: String,
+
+        /// New status (omit to list the valid next statuses)
+        status: Option<String>,
+    },
+
+    /// Start a work session on a card
+    Start {
+        /// Task ID (defaults to the card for the current git branch)
+        id: Option<String>,
+
+        /// Also create and check out the card's git branch and mark it active
+        #[arg(long)]
+        branch: bool,
+    },
+
+    /// Stop your running work session on a card
+    Stop {
+        /// Task ID (defaults to the card for the current git branch)
+        id: Option<String>,
+    },
+
+    /// Show the card for the checked-out git branch
+    Current,
+
+    /// Claim a card so other agents leave it alone (renews your own claim)
+    Claim {
+        /// Task ID (defaults to the card for the current git branch)
+        id: Option<String>,
+
+        /// Claimant (defaults to [author] name, then $USER)
+        #[arg(long = "as", value_name = "NAME")]
+        claimant: Option<String>,
+
+        /// Lease length (e.g. 90s, 30m, 2h, 1d)
+        #[arg(long, default_value = "30m")]
+        ttl: String,
+    },
+
+    /// Release a claim on a card
+    Release {
+        /// Task ID (defaults to the card for the current git branch)
+        id: Option<String>,
+
+        /// Claimant (defaults to [author] name, then $USER)
+        #[arg(long = "as", value_name = "NAME")]
+        claimant: Option<String>,
+
+        /// Release even if someone else holds the claim
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Bring a card back from the archive or the trash
+    Restore {
+        /// Task ID
+        id: String,
+    },
+
+    /// Permanently delete cards archived longer than a given age
+    Purge {
+        /// Minimum time since archiving (e.g. 90d, 12w, 6m)
+        #[arg(long, value_name = "AGE")]
+        older_than: String,
+
+        /// List the cards that would be purged without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Index new git commits and apply `Closes:` trailers ([git] close_on_trailer)
+    ScanCommits,
+
+    /// Create the next instance of recurring cards that are due
+    SpawnDue {
+        /// List the instances that would be created without creating them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Delete a card, moving it to .cuedeck/trash
+    Delete {
+        /// Task ID
+        id: String,
+
+        /// Remove the card from other cards' depends_on and parent
+        #[arg(long, conflicts_with = "force")]
+        cascade: bool,
+
+        /// Delete even if other cards still reference it
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Update every card matching a query
+    Update {
+        /// Query selecting the cards (e.g. 'status:todo tag:auth')
+        #[arg(long = "where", value_name = "QUERY")]
+        filter: String,
+
+        /// Field to set, as key=value (repeatable; `key=` clears the field,
+        /// `tags+=x` / `tags-=x` add or remove list items)
+        #[arg(long = "set", value_name = "KEY=VALUE", required = true)]
+        set: Vec<String>,
+
+        /// Show the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show the activity history of a card
+    History {
+        /// Task ID (defaults to the card for the current git branch)
+        id: Option<String>,
+
+        /// Output events as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportAction {
+    /// Time logged with `cue card start`/`stop`, per assignee or tag
+    Time {
+        /// Only count time since (e.g. 7d, 2w, 3m)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Group by: assignee or tag
+        #[arg(long, default_value = "assignee")]
+        by: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Open and closed cards per day
+    Burndown {
+        #[command(flatten)]
+        range: TrendArgs,
+    },
+
+    /// Cards closed per week
+    Throughput {
+        #[command(flatten)]
+        range: TrendArgs,
+    },
+
+    /// Time from created to done (p50/p85/p95), per assignee or tag
+    CycleTime {
+        #[command(flatten)]
+        range: TrendArgs,
+
+        /// Group by: assignee or tag
+        #[arg(long, default_value = "assignee")]
+        by: String,
+    },
+
+    /// Open cards, blocked cards and estimates per assignee, against [capacity]
+    Workload {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Date range and output options shared by the trend reports
+#[derive(clap::Args)]
+struct TrendArgs {
+    /// Start of the range: a date (YYYY-MM-DD) or a duration ago (e.g. 30d, 8w)
+    #[arg(long, default_value = "30d")]
+    since: String,
+
+    /// End of the range (YYYY-MM-DD or a duration ago; default: today)
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Output as JSON
+    #[arg(long, conflicts_with = "chart")]
+    json: bool,
+
+    /// Draw an ASCII bar chart instead of a table
+    #[arg(long)]
+    chart: bool,
+}
+
+impl TrendArgs {
+    fn report(&self, workspace_root: &Path, by: &str) -> anyhow::Result<cue_core::metrics::VelocityReport> {
+        let today = chrono::Utc::now().date_naive();
+        let parse = |value: &str| {
+            cue_core::metrics::parse_day(value, today)
+                .ok_or_else(|| anyhow::anyhow!("Invalid date '{}'. Expected YYYY-MM-DD or e.g. 30d, 8w", value))
+        };
+        let from = parse(&self.since)?;
+        let to = match &self.until {
+            Some(until) => parse(until)?,
+            None => today,
+        };
+        if from > to {
+            anyhow::bail!("--since ({}) is after --until ({})", from, to);
+        }
+
+        let grouping = cue_core::timelog::TimeGrouping::parse(by)?;
+        Ok(cue_core::metrics::velocity_report(workspace_root, from, to, grouping)?)
+    }
 }
 
 #[derive(Subcommand)]
@@ -241,21 +643,28 @@ async fn main() {
             dry_run,
             token_limit,
         } => cmd_scene(dry_run, token_limit).await,
-        Commands::Open {
-            query,
-            mode,
-            semantic,
-            tags,
-            priority,
-            assignee,
-        } => cmd_open(query, mode, semantic, tags, priority, assignee).await,
+        Commands::Open(args) => cmd_open(args).await,
 
         Commands::Watch => cmd_watch().await,
 
         Commands::Doctor { repair, json, normalize_tags } => cmd_doctor(repair, json, normalize_tags).await,
         Commands::Card { action } => cmd_card(action).await,
-        Commands::List { status, tags, priority, assignee, created, updated } => 
-            cmd_list(status, tags, priority, assignee, created, updated).await,
+        Commands::List(args) => cmd_list(args).await,
+        Commands::Mv { from, to, dry_run } => cmd_mv(&from, &to, dry_run).await,
+        Commands::Import {
+            file,
+            format,
+            mappings,
+            dry_run,
+        } => cmd_import(&file, &format, &mappings, dry_run).await,
+        Commands::Export {
+            format,
+            output,
+            filters,
+        } => cmd_export(&format, output.as_deref(), &filters).await,
+        Commands::Milestone { name } => cmd_milestone(name).await,
+        Commands::Report { action } => cmd_report(action).await,
+        Commands::Digest { since, json } => cmd_digest(&since, json).await,
         Commands::Clean { logs } => cmd_clean(logs).await,
         Commands::Logs { action } => cmd_logs(action).await,
         Commands::Upgrade => cmd_upgrade().await,
@@ -407,19 +816,37 @@ async fn cmd_scene(dry_run: bool, _token_limit: Option<usize>) -> anyhow::Result
     Ok(())
 }
 
-async fn cmd_open(
-    query: Option<String>,
-    mode: String,
-    semantic: bool,
-    tags: Option<Vec<String>>,
-    priority: Option<String>,
-    assignee: Option<String>,
-) -> anyhow::Result<()> {
+async fn cmd_open(args: OpenArgs) -> anyhow::Result<()> {
+    let OpenArgs {
+        query,
+        mode,
+        semantic,
+        tags,
+        priority,
+        assignee,
+        filter_query,
+        archived,
+    } = args;
+
     use cue_core::context::{search_workspace_with_mode, SearchFilters, SearchMode};
+    use cue_core::query::Query;
     use std::io::{self, Write};
 
     let cwd = std::env::current_dir()?;
-    let query_str = query.unwrap_or_default();
+    let mut query_str = query.unwrap_or_default();
+
+    // Free text in the query expression is ranked with the positional query;
+    // everything else filters the results
+    let parsed_query = match filter_query {
+        Some(q) => {
+            let (text, rest) = Query::parse(&q)?.split_text();
+            if !text.is_empty() {
+                query_str = format!("{} {}", query_str, text).trim().to_string();
+            }
+            (!rest.is_all()).then_some(rest)
+        }
+        None => None,
+    };
 
     // Determine search mode: --semantic flag overrides --mode for backward compat
     let search_mode = if semantic {
@@ -429,11 +856,13 @@ async fn cmd_open(
     };
 
     // Construct filters if any provided
-    let filters = if tags.is_some() || priority.is_some() || assignee.is_some() {
+    let filters = if tags.is_some() || priority.is_some() || assignee.is_some() || parsed_query.is_some() || archived {
         Some(SearchFilters {
             tags,
             priority,
             assignee,
+            query: parsed_query,
+            include_archived: archived,
         })
     } else {
         None
@@ -450,6 +879,9 @@ async fn cmd_open(
         if let Some(ref a) = f.assignee {
             eprintln!("👤 Filtering by assignee: {}", a);
         }
+        if f.query.is_some() {
+            eprintln!("🔎 Filtering by query expression");
+        }
     }
 
     match search_mode {
@@ -674,26 +1106,26 @@ async fn cmd_card(action: CardAction) -> anyhow::Result<()> {
             tags,
             priority,
             assignee,
+            template,
             depends_on,
+            parent,
+            due,
+            milestone,
         } => {
             let cwd = std::env::current_dir()?;
 
-            // Validate priority
-            if !["low", "medium", "high", "critical"].contains(&priority.as_str()) {
-                anyhow::bail!(
-                    "Invalid priority '{}'. Must be: low, medium, high, or critical",
-                    priority
-                );
-            }
-
-            let path = cue_core::tasks::create_task_with_metadata(
-                &cwd,
-                &title,
-                tags.clone(),
-                Some(&priority),
-                assignee.as_deref(),
-                depends_on.clone(),
-            )?;
+            let options = cue_core::tasks::NewTaskOptions {
+                tags,
+                priority,
+                assignee,
+                depends_on,
+                parent,
+                due,
+                milestone,
+                template: template.clone(),
+                ..Default::default()
+            };
+            let path = cue_core::tasks::create_task_with_options(&cwd, &title, &options)?;
 
             let task_id = path
                 .file_stem()
@@ -702,23 +1134,41 @@ async fn cmd_card(action: CardAction) -> anyhow::Result<()> {
 
             eprintln!("✓ Created task: {} at {}", task_id, path.display());
 
-            // Show metadata summary
-            if let Some(t) = tags {
-                eprintln!("  Tags: {}", t.join(", "));
-            }
-            eprintln!("  Priority: {}", priority);
-            if let Some(a) = assignee {
-                eprintln!("  Assignee: {}", a);
-            }
-            if let Some(deps) = depends_on {
-                if !deps.is_empty() {
+            // Show metadata summary (including template defaults)
+            let meta = cue_core::parse_file(&path)?.frontmatter;
+            if let Some(meta) = meta {
+                if let Some(name) = &template {
+                    eprintln!("  Template: {}", name);
+                }
+                if let Some(t) = meta.tags.filter(|t| !t.is_empty()) {
+                    eprintln!("  Tags: {}", t.join(", "));
+                }
+                eprintln!("  Priority: {}", meta.priority);
+                if let Some(a) = meta.assignee {
+                    eprintln!("  Assignee: {}", a);
+                }
+                if let Some(deps) = meta.depends_on.filter(|d| !d.is_empty()) {
                     eprintln!("  Depends on: {}", deps.join(", "));
                 }
+                if let Some(p) = meta.parent {
+                    eprintln!("  Parent: {}", p);
+                }
+                if let Some(d) = meta.due {
+                    eprintln!("  Due: {}", d);
+                }
+                if let Some(m) = meta.milestone {
+                    eprintln!("  Milestone: {}", m);
+                }
+            }
+
+            if let Some(assignee) = options.assignee.as_deref().filter(|a| !a.is_empty()) {
+                warn_capacity(&cwd, [assignee]);
             }
         }
 
         CardAction::Deps { id, reverse } => {
             let cwd = std::env::current_dir()?;
+            let id = resolve_card_id(&cwd, &id)?;
 
             if reverse {
                 // Show dependents (tasks that depend on this task)
@@ -730,7 +1180,7 @@ async fn cmd_card(action: CardAction) -> anyhow::Result<()> {
                     eprintln!("Tasks depending on '{}' ({} total):", id, dependents.len());
                     for dep in dependents {
                         // Load task details
-                        let task_path = cwd.join(format!(".cuedeck/cards/{}.md", dep.from_id));
+                        let task_path = cue_core::archive::card_path(&cwd, &dep.from_id);
                         if let Ok(doc) = cue_core::parse_file(&task_path) {
                             let title = doc
                                 .frontmatter
@@ -757,7 +1207,7 @@ async fn cmd_card(action: CardAction) -> anyhow::Result<()> {
                     );
                     for dep in dependencies {
                         // Load task details
-                        let task_path = cwd.join(format!(".cuedeck/cards/{}.md", dep.to_id));
+                        let task_path = cue_core::archive::card_path(&cwd, &dep.to_id);
                         if let Ok(doc) = cue_core::parse_file(&task_path) {
                             let title = doc
                                 .frontmatter
@@ -778,6 +1228,8 @@ async fn cmd_card(action: CardAction) -> anyhow::Result<()> {
             let cwd = std::env::current_dir()?;
 
             if let Some(task_id) = id {
+                let task_id = resolve_card_id(&cwd, &task_id)?;
+
                 // Validate specific task
                 eprintln!("Validating task '{}'...", task_id);
 
@@ -833,82 +1285,575 @@ async fn cmd_card(action: CardAction) -> anyhow::Result<()> {
             }
         }
 
-        CardAction::List { status } => {
-            cmd_list(status, None, None, None, None, None).await?;
-        }
+        CardAction::List { status, tree } => {
+            if tree {
+                let cwd = std::env::current_dir()?;
+                let mut filters = cue_core::task_filters::TaskFilters::default();
+                if status == "all" {
+                    filters.include_archived = true;
+                } else {
+                    filters.status = Some(status.clone());
+                }
+                let tasks = cue_core::tasks::list_tasks_filtered(&cwd, &filters)?;
+                let forest = cue_core::task_hierarchy::build_task_tree(&cwd, tasks)?;
 
-        CardAction::Edit { id } => {
-            let path = format!(".cuedeck/cards/{}.md", id);
-            if !Path::new(&path).exists() {
-                anyhow::bail!("Card not found: {}", id);
+                eprintln!("Cards (status={}):", status);
+                print_task_tree(&forest, "");
+            } else {
+                cmd_list(ListArgs {
+                    filters: FilterArgs {
+                        status,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await?;
             }
-
-            let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
-            std::process::Command::new(&editor).arg(&path).status()?;
         }
 
-        CardAction::Archive { id } => {
+        CardAction::Show { id, json } => {
             let cwd = std::env::current_dir()?;
-            let mut updates = serde_json::Map::new();
-            updates.insert(
-                "status".to_string(),
-                serde_json::Value::String("archived".to_string()),
-            );
+            let id = card_id_or_current(&cwd, id)?;
+            let doc = cue_core::parse_file(&cue_core::archive::card_path(&cwd, &id))?;
+            let Some(meta) = doc.frontmatter else {
+                anyhow::bail!("Card {} has no readable frontmatter", id);
+            };
 
-            match cue_core::tasks::update_task(&cwd, &id, updates) {
-                Ok(_) => eprintln!("✓ Archived card: {}", id),
-                Err(e) => anyhow::bail!("Failed to archive card {}: {}", id, e),
+            let (commits, scan) = match cue_core::commits::task_commits(&cwd, &id) {
+                Ok(found) => found,
+                Err(e) => {
+                    eprintln!("⚠ Commits unavailable: {}", e);
+                    Default::default()
+                }
+            };
+            for warning in &scan.warnings {
+                eprintln!("⚠ {}", warning);
             }
-        }
-    }
 
-    Ok(())
-}
+            if json {
+                let out = serde_json::json!({ "id": id, "frontmatter": meta, "commits": commits });
+                println!("{}", serde_json::to_string_pretty(&out)?);
+                return Ok(());
+            }
 
-async fn cmd_list(
-    status: String,
-    tags: Option<Vec<String>>,
-    priority: Option<String>,
-    assignee: Option<String>,
-    created: Option<String>,
-    updated: Option<String>,
-) -> anyhow::Result<()> {
-    use cue_core::task_filters::{parse_date_filter, TaskFilters};
+            eprintln!("{}: {}", id, meta.title);
+            eprintln!("  Status:   {}", meta.status);
+            eprintln!("  Priority: {}", meta.priority);
+            if let Some(assignee) = &meta.assignee {
+                eprintln!("  Assignee: {}", assignee);
+            }
+            if let Some(tags) = meta.tags.as_ref().filter(|t| !t.is_empty()) {
+                eprintln!("  Tags:     {}", tags.join(", "));
+            }
+            if let Some(deps) = meta.depends_on.as_ref().filter(|d| !d.is_empty()) {
+                eprintln!("  Depends:  {}", deps.join(", "));
+            }
+            if let Some(parent) = &meta.parent {
+                eprintln!("  Parent:   {}", parent);
+            }
+            if let Some(due) = &meta.due {
+                eprintln!("  Due:      {}", due);
+            }
+            if let Some(milestone) = &meta.milestone {
+                eprintln!("  Milestone: {}", milestone);
+            }
 
-    let cwd = std::env::current_dir()?;
+            eprintln!();
+            if commits.is_empty() {
+                eprintln!("No commits mention {}", id);
+            } else {
+                eprintln!("Commits ({}):", commits.len());
+                for commit in &commits {
+                    eprintln!(
+                        "  {} {} {:<16} {}{}",
+                        &commit.sha[..commit.sha.len().min(7)],
+                        commit.date.get(..10).unwrap_or(&commit.date),
+                        truncate(&commit.author, 16),
+                        commit.summary,
+                        if commit.closes { " (closes)" } else { "" }
+                    );
+                }
+            }
+        }
+
+        CardAction::Edit { id } => {
+            let cwd = std::env::current_dir()?;
+            let id = resolve_card_id(&cwd, &id)?;
+            let path = cue_core::archive::card_path(&cwd, &id);
+
+            let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+            std::process::Command::new(&editor).arg(&path).status()?;
+        }
+
+        CardAction::Archive { id } => {
+            let cwd = std::env::current_dir()?;
+            let id = resolve_card_id(&cwd, &id)?;
+            let mut updates = serde_json::Map::new();
+            updates.insert(
+                "status".to_string(),
+                serde_json::Value::String("archived".to_string()),
+            );
+
+            match cue_core::tasks::update_task(&cwd, &id, updates) {
+                Ok(_) => eprintln!("✓ Archived card: {} (moved to .cuedeck/archive/)", id),
+                Err(e) => anyhow::bail!("Failed to archive card {}: {}", id, e),
+            }
+
+            let open = cue_core::task_hierarchy::open_children(&cwd, &id)?;
+            if !open.is_empty() {
+                eprintln!(
+                    "⚠ Epic {} still has {} open child card(s): {}",
+                    id,
+                    open.len(),
+                    open.join(", ")
+                );
+            }
+        }
+
+        CardAction::Move { id, status } => {
+            let cwd = std::env::current_dir()?;
+            let id = resolve_card_id(&cwd, &id)?;
+            let current = cue_core::parse_file(&cue_core::archive::card_path(&cwd, &id))?
+                .frontmatter
+                .map(|m| m.status)
+                .unwrap_or_else(|| "todo".to_string());
+
+            let workflow = cue_config::Config::load(&cwd)?.workflow;
+            let next = workflow.next_statuses(&current);
+            let next_list = if next.is_empty() {
+                "none".to_string()
+            } else {
+                next.join(", ")
+            };
+
+            let Some(status) = status else {
+                eprintln!("Card {} is '{}'", id, current);
+                eprintln!("Valid next statuses: {}", next_list);
+                return Ok(());
+            };
+
+            let mut updates = serde_json::Map::new();
+            updates.insert("status".to_string(), serde_json::Value::String(status.clone()));
+            match cue_core::tasks::update_task(&cwd, &id, updates) {
+                Ok(_) => eprintln!("✓ Moved card {}: {} → {}", id, current, status),
+                Err(cue_common::CueError::ValidationError(msg)) => {
+                    anyhow::bail!("{}\nValid next statuses from '{}': {}", msg, current, next_list)
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        CardAction::Start { id, branch } => {
+            let cwd = std::env::current_dir()?;
+            let id = card_id_or_current(&cwd, id)?;
+            if branch {
+                let started = cue_core::branches::start_branch(&cwd, &id)?;
+                let verb = if started.created { "Created" } else { "Switched to" };
+                eprintln!("✓ {} branch {}", verb, started.branch);
+            }
+            cue_core::timelog::start_session(&cwd, &id, None)?;
+            eprintln!("✓ Started work on {}", id);
+        }
+
+        CardAction::Stop { id } => {
+            let cwd = std::env::current_dir()?;
+            let id = card_id_or_current(&cwd, id)?;
+            let session = cue_core::timelog::stop_session(&cwd, &id, None)?;
+            let worked = session.duration(chrono::Utc::now(), None);
+            eprintln!(
+                "✓ Stopped work on {} ({})",
+                id,
+                cue_core::timelog::format_duration(worked)
+            );
+        }
+
+        CardAction::Current => {
+            let cwd = std::env::current_dir()?;
+            let id = card_id_or_current(&cwd, None)?;
+            let meta = cue_core::parse_file(&cue_core::archive::card_path(&cwd, &id))?.frontmatter;
+            match meta {
+                Some(meta) => println!("{}: {} [{}]", id, meta.title, meta.status),
+                None => println!("{}", id),
+            }
+        }
+
+        CardAction::Claim { id, claimant, ttl } => {
+            let cwd = std::env::current_dir()?;
+            let id = card_id_or_current(&cwd, id)?;
+            let Some(lease) = cue_core::claims::parse_ttl(&ttl) else {
+                anyhow::bail!("Invalid TTL '{}'. Expected e.g. 90s, 30m, 2h, 1d", ttl);
+            };
+            let claimant = default_claimant(&cwd, claimant)?;
+
+            match cue_core::claims::claim_task(&cwd, &id, &claimant, lease) {
+                Ok(claim) if claim.renewed => {
+                    eprintln!("✓ Renewed claim on {} for {} (until {})", id, claimant, claim.expires)
+                }
+                Ok(claim) => eprintln!("✓ Claimed {} for {} (until {})", id, claimant, claim.expires),
+                Err(cue_common::CueError::Locked { holder, until }) => {
+                    anyhow::bail!("Card {} is claimed by {} until {}", id, holder, until)
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        CardAction::Release { id, claimant, force } => {
+            let cwd = std::env::current_dir()?;
+            let id = card_id_or_current(&cwd, id)?;
+            let claimant = default_claimant(&cwd, claimant)?;
+
+            match cue_core::claims::release_task(&cwd, &id, &claimant, force) {
+                Ok(()) => eprintln!("✓ Released claim on {}", id),
+                Err(cue_common::CueError::Locked { holder, until }) => anyhow::bail!(
+                    "Card {} is claimed by {} until {}\nUse --force to release it anyway",
+                    id,
+                    holder,
+                    until
+                ),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        CardAction::Restore { id } => {
+            let cwd = std::env::current_dir()?;
+            let report = match cue_core::archive::restore_task(&cwd, &id) {
+                Ok(report) => report,
+                Err(cue_common::CueError::FileNotFound { .. }) => {
+                    anyhow::bail!("No archived or deleted card found: {}", id)
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            let source = match report.source {
+                cue_core::archive::RestoreSource::Archive => "archive",
+                cue_core::archive::RestoreSource::Trash => "trash",
+            };
+            eprintln!("✓ Restored card {} from {} (status: {})", report.id, source, report.status);
+        }
+
+        CardAction::Purge { older_than, dry_run } => {
+            let cwd = std::env::current_dir()?;
+            let Some(age) = cue_core::task_filters::parse_relative_duration(&older_than) else {
+                anyhow::bail!("Invalid age '{}'. Expected e.g. 30d, 12w, 6m, 1y", older_than);
+            };
+
+            let purged = cue_core::archive::purge_archived(&cwd, age, dry_run)?;
+            if purged.is_empty() {
+                eprintln!("No cards archived more than {} ago", older_than);
+                return Ok(());
+            }
+
+            for id in &purged {
+                eprintln!("  {}", id);
+            }
+            if dry_run {
+                eprintln!("\nDry run: {} card(s) would be purged", purged.len());
+            } else {
+                eprintln!("\n✓ Purged {} archived card(s)", purged.len());
+            }
+        }
+
+        CardAction::ScanCommits => {
+            let cwd = std::env::current_dir()?;
+            let report = cue_core::commits::scan_commits(&cwd, true)?;
+
+            for warning in &report.warnings {
+                eprintln!("⚠ {}", warning);
+            }
+            for closed in &report.closed {
+                eprintln!("✓ Moved {} to done (Closes trailer)", closed);
+            }
+            eprintln!(
+                "✓ Scanned {} commit(s), {} new card link(s)",
+                report.scanned, report.linked
+            );
+        }
+
+        CardAction::SpawnDue { dry_run } => {
+            let cwd = std::env::current_dir()?;
+            let report = cue_core::recurrence::spawn_due(&cwd, dry_run)?;
+
+            for invalid in &report.invalid {
+                eprintln!("⚠ Skipping {}: {}", invalid.template, invalid.error);
+            }
+            if report.spawned.is_empty() {
+                eprintln!("No recurring cards are due");
+                return Ok(());
+            }
+
+            for card in &report.spawned {
+                let id = if card.id.is_empty() { "(new)" } else { card.id.as_str() };
+                let due = card.due.as_deref().map(|d| format!(", due {}", d)).unwrap_or_default();
+                eprintln!("  {}: {} (from {}{})", id, card.title, card.template, due);
+            }
+            if dry_run {
+                eprintln!("\nDry run: {} card(s) would be created", report.spawned.len());
+            } else {
+                eprintln!("\n✓ Spawned {} recurring card(s)", report.spawned.len());
+            }
+        }
+
+        CardAction::Delete { id, cascade, force } => {
+            let cwd = std::env::current_dir()?;
+            let options = cue_core::trash::DeleteOptions { cascade, force };
+
+            let report = match cue_core::trash::delete_task(&cwd, &id, &options) {
+                Ok(report) => report,
+                Err(cue_common::CueError::FileNotFound { .. }) => anyhow::bail!("Card not found: {}", id),
+                Err(cue_common::CueError::ValidationError(msg)) if msg.contains("still referenced") => {
+                    anyhow::bail!("{}\nUse --cascade to remove these references or --force to leave them", msg)
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            eprintln!("✓ Deleted card {} (moved to {})", report.id, report.trash_path.display());
+            let referencing: Vec<&String> = report.dependents.iter().chain(&report.children).collect();
+            if report.cascaded {
+                for card in &referencing {
+                    eprintln!("  Removed reference from {}", card);
+                }
+            } else if !referencing.is_empty() {
+                eprintln!(
+                    "⚠ Left dangling references in: {}",
+                    referencing.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+                );
+            }
+        }
 
-    // Build filters
-    let mut filters = TaskFilters::default();
+        CardAction::History { id, json } => {
+            let cwd = std::env::current_dir()?;
+            // History outlives the card, so fall back to the literal ID
+            let id = match id {
+                Some(id) => match cue_core::card_ids::resolve_task_id(&cwd, &id) {
+                    Err(cue_common::CueError::FileNotFound { .. }) => id,
+                    resolved => resolved?,
+                },
+                None => card_id_or_current(&cwd, None)?,
+            };
+            let history = cue_core::activity::task_history(&cwd, &id)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&history)?);
+                return Ok(());
+            }
+
+            if history.is_empty() {
+                eprintln!("No recorded activity for '{}'", id);
+                return Ok(());
+            }
+
+            eprintln!("History for {} ({} events):", id, history.len());
+            for event in history {
+                eprintln!(
+                    "{}  {:<9} {}",
+                    event.timestamp,
+                    event.action,
+                    event.author.as_deref().unwrap_or("unknown")
+                );
+                for change in event.changes {
+                    eprintln!("    {}", format_change(&change));
+                }
+            }
+        }
+        CardAction::Update {
+            filter,
+            set,
+            dry_run,
+        } => {
+            let cwd = std::env::current_dir()?;
+
+            let mut updates = serde_json::Map::new();
+            for assignment in &set {
+                let (key, value) = parse_assignment(assignment)?;
+                updates.insert(key, value);
+            }
+
+            let filters = cue_core::TaskFilters {
+                query: Some(cue_core::query::Query::parse(&filter)?),
+                ..Default::default()
+            };
+            let result = cue_core::bulk::bulk_update_tasks(&cwd, &filters, &updates, dry_run)?;
 
-    // Status filter
-    if status != "all" {
-        filters.status = Some(status.clone());
+            if result.cards.is_empty() {
+                eprintln!("No cards match '{}'", filter);
+                return Ok(());
+            }
+
+            for card in &result.cards {
+                eprintln!("{}", card.id);
+                for change in &card.changes {
+                    eprintln!("    {}", format_change(change));
+                }
+            }
+
+            if dry_run {
+                eprintln!("\nDry run: {} card(s) would be updated", result.cards.len());
+            } else {
+                eprintln!("\n✓ Updated {} card(s)", result.cards.len());
+                if updates.contains_key("assignee") || updates.contains_key("estimate") {
+                    let assignees: std::collections::BTreeSet<String> = result
+                        .cards
+                        .iter()
+                        .filter_map(|card| cue_core::parse_file(&cue_core::archive::card_path(&cwd, &card.id)).ok())
+                        .filter_map(|doc| doc.frontmatter?.assignee)
+                        .collect();
+                    warn_capacity(&cwd, assignees.iter().map(String::as_str));
+                }
+            }
+        }
     }
 
-    // Tags filter
-    filters.tags = tags;
+    Ok(())
+}
 
-    // Priority filter
-    filters.priority = priority.clone();
+/// Warn about assignees pushed over their [capacity] limits
+fn warn_capacity<'a>(workspace_root: &Path, assignees: impl IntoIterator<Item = &'a str>) {
+    for assignee in assignees {
+        match cue_core::workload::capacity_warnings(workspace_root, assignee) {
+            Ok(warnings) => warnings.iter().for_each(|w| eprintln!("⚠ {}", w)),
+            Err(e) => eprintln!("⚠ Could not check capacity for {}: {}", assignee, e),
+        }
+    }
+}
 
-    // Assignee filter
-    filters.assignee = assignee.clone();
+/// Claimant for `card claim`/`release`: `--as`, then [author] name, then $USER
+fn default_claimant(workspace_root: &Path, claimant: Option<String>) -> anyhow::Result<String> {
+    claimant
+        .filter(|c| !c.is_empty())
+        .or_else(|| cue_core::activity::current_author(workspace_root))
+        .or_else(|| std::env::var("USER").ok().filter(|u| !u.is_empty()))
+        .ok_or_else(|| anyhow::anyhow!("No claimant: pass --as <NAME> or set [author] name in config.toml"))
+}
 
-    // Created date filter
-    if let Some(created_str) = created {
-        filters.created = Some(parse_date_filter(&created_str)?);
+/// Resolve a full card ID or unambiguous prefix
+fn resolve_card_id(workspace_root: &Path, input: &str) -> anyhow::Result<String> {
+    match cue_core::card_ids::resolve_task_id(workspace_root, input) {
+        Ok(id) => Ok(id),
+        Err(cue_common::CueError::FileNotFound { .. }) => anyhow::bail!("Card not found: {}", input),
+        Err(e) => Err(e.into()),
     }
+}
 
-    // Updated date filter
-    if let Some(updated_str) = updated {
-        filters.updated = Some(parse_date_filter(&updated_str)?);
+/// Resolve `id`, or infer the card from the checked-out git branch
+fn card_id_or_current(workspace_root: &Path, id: Option<String>) -> anyhow::Result<String> {
+    if let Some(id) = id {
+        return resolve_card_id(workspace_root, &id);
+    }
+    match cue_core::branches::current_card(workspace_root)? {
+        Some(id) => Ok(id),
+        None => anyhow::bail!("No card ID given and the current git branch does not match a card"),
     }
+}
 
-    // Use new filtered function
+/// Print cards as an ASCII tree, showing child progress on epics
+fn print_task_tree(nodes: &[cue_core::task_hierarchy::TaskNode], prefix: &str) {
+    for (i, node) in nodes.iter().enumerate() {
+        let last = i == nodes.len() - 1;
+        let (branch, indent) = if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+
+        let (title, status) = node
+            .document
+            .frontmatter
+            .as_ref()
+            .map(|m| (m.title.as_str(), m.status.as_str()))
+            .unwrap_or(("Untitled", "unknown"));
+        let progress = node
+            .progress
+            .map(|p| format!(" ({}/{} done)", p.done, p.total))
+            .unwrap_or_default();
+
+        eprintln!("{}{}{}: {} [{}]{}", prefix, branch, node.id, title, status, progress);
+        print_task_tree(&node.children, &format!("{}{}", prefix, indent));
+    }
+}
+
+/// Parse a `--set` assignment into an `update_task` field
+///
+/// `key=value` sets a field (an empty value clears it). For list fields,
+/// values are comma-separated and `key+=v` / `key-=v` add or remove items.
+fn parse_assignment(assignment: &str) -> anyhow::Result<(String, serde_json::Value)> {
+    let Some((key, value)) = assignment.split_once('=') else {
+        anyhow::bail!("Invalid --set '{}': expected key=value", assignment);
+    };
+
+    let (key, op) = match key.trim() {
+        k if k.ends_with('+') => (k.trim_end_matches('+'), Some("add")),
+        k if k.ends_with('-') => (k.trim_end_matches('-'), Some("remove")),
+        k => (k, None),
+    };
+    let key = key.trim();
+    if key.is_empty() {
+        anyhow::bail!("Invalid --set '{}': missing key", assignment);
+    }
+
+    let value = value.trim();
+    let is_list = cue_core::tasks::LIST_FIELDS.contains(&key);
+    if op.is_some() && !is_list {
+        anyhow::bail!(
+            "Invalid --set '{}': += and -= only apply to {}",
+            assignment,
+            cue_core::tasks::LIST_FIELDS.join(", ")
+        );
+    }
+
+    let value = if value.is_empty() {
+        serde_json::Value::Null
+    } else if is_list {
+        let items: Vec<serde_json::Value> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(serde_json::Value::from)
+            .collect();
+        match op {
+            Some(op) => serde_json::json!({ op: items }),
+            None => serde_json::Value::Array(items),
+        }
+    } else {
+        serde_json::Value::String(value.to_string())
+    };
+    Ok((key.to_string(), value))
+}
+
+/// Render a field change as `field: old → new`
+fn format_change(change: &cue_core::activity::FieldChange) -> String {
+    let show = |v: &Option<serde_json::Value>| match v {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => "∅".to_string(),
+    };
+    format!("{}: {} → {}", change.field, show(&change.old), show(&change.new))
+}
+
+async fn cmd_list(args: ListArgs) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let filters = args.filters.task_filters()?;
     let tasks = cue_core::tasks::list_tasks_filtered(&cwd, &filters)?;
 
+    let ListArgs {
+        filters:
+            FilterArgs {
+                status,
+                priority,
+                assignee,
+                parent,
+                milestone,
+                due,
+                query,
+                ..
+            },
+        long,
+    } = args;
+    let query_sets_status = filters
+        .query
+        .as_ref()
+        .is_some_and(|q| q.mentions_field("status"));
+
     // Display filter info
-    let mut filter_parts = vec![format!("status={}", status)];
+    let mut filter_parts = vec![];
+    if !query_sets_status {
+        filter_parts.push(format!("status={}", status));
+    }
     if let Some(ref t) = filters.tags {
         filter_parts.push(format!("tags={}", t.join(",")));
     }
@@ -918,13 +1863,38 @@ async fn cmd_list(
     if let Some(ref a) = assignee {
         filter_parts.push(format!("assignee={}", a));
     }
+    if let Some(ref p) = parent {
+        filter_parts.push(format!("parent={}", p));
+    }
+    if let Some(ref m) = milestone {
+        filter_parts.push(format!("milestone={}", m));
+    }
+    if let Some(ref d) = due {
+        filter_parts.push(format!("due={}", d));
+    }
+    if let Some(ref q) = query {
+        filter_parts.push(format!("query='{}'", q));
+    }
 
     eprintln!("Cards ({}):", filter_parts.join(", "));
-    eprintln!(
-        "{:<10} {:<30} {:<15} {:<10}",
-        "ID", "Title", "Status", "Priority"
-    );
-    eprintln!("{}", "-".repeat(70));
+    let time_logged = if long {
+        cue_core::timelog::time_by_task(&cwd)?
+    } else {
+        Default::default()
+    };
+    if long {
+        eprintln!(
+            "{:<10} {:<30} {:<15} {:<10} {:<16} {:>8}",
+            "ID", "Title", "Status", "Priority", "Assignee", "Time"
+        );
+        eprintln!("{}", "-".repeat(94));
+    } else {
+        eprintln!(
+            "{:<10} {:<30} {:<15} {:<10}",
+            "ID", "Title", "Status", "Priority"
+        );
+        eprintln!("{}", "-".repeat(70));
+    }
 
     for doc in tasks {
         // ID from filename
@@ -943,23 +1913,368 @@ async fn cmd_list(
             created: None,
             updated: None,
             depends_on: None,
+            parent: None,
+            due: None,
+            milestone: None,
+            estimate: None,
+            claimed_by: None,
+            claim_expires: None,
+            recurrence: None,
+            recurrence_of: None,
+            previous_instance: None,
+            external_id: None,
+            branch: None,
         });
 
+        if long {
+            let worked = time_logged
+                .get(id)
+                .map(|d| cue_core::timelog::format_duration(*d))
+                .unwrap_or_else(|| "-".to_string());
+            eprintln!(
+                "{:<10} {:<30} {:<15} {:<10} {:<16} {:>8}",
+                id,
+                truncate(&meta.title, 28),
+                meta.status,
+                meta.priority,
+                truncate(meta.assignee.as_deref().unwrap_or("-"), 14),
+                worked
+            );
+        } else {
+            eprintln!(
+                "{:<10} {:<30} {:<15} {:<10}",
+                id,
+                truncate(&meta.title, 28),
+                meta.status,
+                meta.priority
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_mv(from: &str, to: &str, dry_run: bool) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let report = cue_core::rename::move_document(&cwd, from, to, dry_run)?;
+
+    eprintln!("{} -> {}", report.from.display(), report.to.display());
+    for file in &report.files {
+        eprintln!("  {}", file.path.display());
+        for change in &file.changes {
+            eprintln!("      {}", change);
+        }
+    }
+    if report.cache_moved {
+        eprintln!("  (cache entry moved)");
+    }
+
+    if dry_run {
+        eprintln!("\nDry run: {} file(s) would be updated", report.files.len());
+    } else {
+        eprintln!("\n✓ Moved, {} file(s) updated", report.files.len());
+    }
+    Ok(())
+}
+
+async fn cmd_import(file: &Path, format: &str, mappings: &[String], dry_run: bool) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let format = cue_core::import::ImportFormat::parse(format)?;
+
+    let mut options = cue_core::import::ImportOptions {
+        dry_run,
+        ..Default::default()
+    };
+    for mapping in mappings {
+        let Some((field, column)) = mapping.split_once('=') else {
+            anyhow::bail!("Invalid mapping '{}'. Expected FIELD=COLUMN", mapping);
+        };
+        options.mapping.insert(field.trim().to_string(), column.trim().to_string());
+    }
+
+    let report = cue_core::import::import_file(&cwd, file, format, &options)?;
+
+    for card in &report.created {
+        let id = if card.id.is_empty() { "(new)" } else { card.id.as_str() };
+        eprintln!("  {}: {} ({})", id, card.title, card.external_id);
+    }
+    for card in &report.skipped {
+        eprintln!("  = {}: {} ({}, already imported)", card.id, card.title, card.external_id);
+    }
+    for warning in &report.warnings {
+        eprintln!("⚠ {}", warning);
+    }
+
+    let summary = format!(
+        "{} card(s), {} dependency link(s), {} already imported",
+        report.created.len(),
+        report.links,
+        report.skipped.len()
+    );
+    if dry_run {
+        eprintln!("\nDry run: would import {}", summary);
+    } else {
+        eprintln!("\n✓ Imported {}", summary);
+    }
+    Ok(())
+}
+
+async fn cmd_export(format: &str, output: Option<&Path>, filters: &FilterArgs) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let format = cue_core::export::ExportFormat::parse(format)?;
+    let tasks = cue_core::tasks::list_tasks_filtered(&cwd, &filters.task_filters()?)?;
+    let rendered = cue_core::export::export_cards(&tasks, format)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, rendered)?;
+            eprintln!("✓ Exported {} card(s) to {}", tasks.len(), path.display());
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+async fn cmd_milestone(name: Option<String>) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let mut report = cue_core::milestones::milestone_report(&cwd)?;
+
+    if let Some(name) = &name {
+        report.retain(|m| &m.name == name);
+        if report.is_empty() {
+            anyhow::bail!("Milestone not found: {}", name);
+        }
+    }
+
+    if report.is_empty() {
+        eprintln!("No milestones found. Set 'milestone:' in card frontmatter to plan work.");
+        return Ok(());
+    }
+
+    eprintln!(
+        "{:<20} {:<12} {:<10} {:<10}",
+        "Milestone", "Done", "Progress", "Overdue"
+    );
+    eprintln!("{}", "-".repeat(56));
+
+    for milestone in &report {
         eprintln!(
-            "{:<10} {:<30} {:<15} {:<10}",
-            id,
-            truncate(&meta.title, 28),
-            meta.status,
-            meta.priority
+            "{:<20} {:<12} {:<10} {:<10}",
+            truncate(&milestone.name, 18),
+            format!("{}/{}", milestone.done, milestone.total),
+            format!("{}%", milestone.percent_complete()),
+            milestone.overdue.len()
         );
     }
 
+    for milestone in report.iter().filter(|m| !m.overdue.is_empty()) {
+        eprintln!();
+        eprintln!("⚠ Overdue in {}:", milestone.name);
+        for card in &milestone.overdue {
+            eprintln!(
+                "  {}: {} (due {}, {} days overdue)",
+                card.id, card.title, card.due, card.days_overdue
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_digest(since: &str, json: bool) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let Some(cutoff) = cue_core::digest::parse_since(since, chrono::Utc::now()) else {
+        anyhow::bail!("Invalid --since '{}'. Expected e.g. 1d, 1w, 2024-06-01", since);
+    };
+
+    let digest = cue_core::digest::digest(&cwd, cutoff)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&digest)?);
+    } else {
+        print!("{}", digest.to_markdown());
+    }
     Ok(())
 }
 
+async fn cmd_report(action: ReportAction) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+
+    match action {
+        ReportAction::Time { since, by, json } => {
+            let grouping = cue_core::timelog::TimeGrouping::parse(&by)?;
+            let cutoff = match &since {
+                Some(s) => {
+                    let Some(age) = cue_core::task_filters::parse_relative_duration(s) else {
+                        anyhow::bail!("Invalid duration '{}'. Expected e.g. 1d, 2w, 3m", s);
+                    };
+                    Some(chrono::Utc::now() - age)
+                }
+                None => None,
+            };
+
+            let report = cue_core::timelog::time_report(&cwd, cutoff, grouping)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            if report.is_empty() {
+                eprintln!("No time logged. Use 'cue card start <id>' and 'cue card stop <id>' to track work.");
+                return Ok(());
+            }
+
+            eprintln!("{:<24} {:>10} {:>9} {:>6}", by, "Time", "Sessions", "Cards");
+            eprintln!("{}", "-".repeat(52));
+            for bucket in &report {
+                eprintln!(
+                    "{:<24} {:>10} {:>9} {:>6}",
+                    truncate(&bucket.key, 22),
+                    cue_core::timelog::format_duration(chrono::Duration::seconds(bucket.seconds)),
+                    bucket.sessions,
+                    bucket.cards.len()
+                );
+            }
+        }
+
+        ReportAction::Burndown { range } => {
+            let report = range.report(&cwd, "assignee")?;
+            if range.json {
+                println!("{}", serde_json::to_string_pretty(&report.burndown)?);
+            } else if range.chart {
+                let rows: Vec<_> = report
+                    .burndown
+                    .iter()
+                    .map(|p| (p.date.clone(), p.open as f64, p.open.to_string()))
+                    .collect();
+                eprint!("{}", cue_core::metrics::ascii_chart(&rows, 50));
+            } else {
+                eprintln!("{:<12} {:>6} {:>7}", "Date", "Open", "Closed");
+                eprintln!("{}", "-".repeat(27));
+                for point in &report.burndown {
+                    eprintln!("{:<12} {:>6} {:>7}", point.date, point.open, point.closed);
+                }
+            }
+        }
+
+        ReportAction::Throughput { range } => {
+            let report = range.report(&cwd, "assignee")?;
+            if range.json {
+                println!("{}", serde_json::to_string_pretty(&report.throughput)?);
+            } else if range.chart {
+                let rows: Vec<_> = report
+                    .throughput
+                    .iter()
+                    .map(|w| (w.week.clone(), w.closed as f64, w.closed.to_string()))
+                    .collect();
+                eprint!("{}", cue_core::metrics::ascii_chart(&rows, 50));
+            } else {
+                eprintln!("{:<12} {:>7}", "Week of", "Closed");
+                eprintln!("{}", "-".repeat(20));
+                for week in &report.throughput {
+                    eprintln!("{:<12} {:>7}", week.week, week.closed);
+                }
+            }
+        }
+
+        ReportAction::CycleTime { range, by } => {
+            let report = range.report(&cwd, &by)?;
+            if range.json {
+                let json = serde_json::json!({
+                    "by": by,
+                    "buckets": report.cycle_time,
+                    "overall": report.overall,
+                });
+                println!("{}", serde_json::to_string_pretty(&json)?);
+                return Ok(());
+            }
+            let Some(overall) = &report.overall else {
+                eprintln!("No cards closed between {} and {}.", report.from, report.to);
+                return Ok(());
+            };
+
+            let days = cue_core::metrics::format_days;
+            if range.chart {
+                let rows: Vec<_> = report
+                    .cycle_time
+                    .iter()
+                    .map(|b| (truncate(&b.key, 22), b.p50_seconds as f64, format!("p50 {}", days(b.p50_seconds))))
+                    .collect();
+                eprint!("{}", cue_core::metrics::ascii_chart(&rows, 50));
+                return Ok(());
+            }
+            eprintln!("{:<24} {:>6} {:>8} {:>8} {:>8}", by, "Cards", "p50", "p85", "p95");
+            eprintln!("{}", "-".repeat(58));
+            for bucket in report.cycle_time.iter().chain([overall]) {
+                eprintln!(
+                    "{:<24} {:>6} {:>8} {:>8} {:>8}",
+                    truncate(&bucket.key, 22),
+                    bucket.cards,
+                    days(bucket.p50_seconds),
+                    days(bucket.p85_seconds),
+                    days(bucket.p95_seconds)
+                );
+            }
+        }
+
+        ReportAction::Workload { json } => {
+            let report = cue_core::workload::workload(&cwd)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            if report.is_empty() {
+                eprintln!("No open cards.");
+                return Ok(());
+            }
+            let config = cue_config::Config::load(&cwd)?;
+            print_workload(&report, &config.priorities.levels);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_workload(report: &[cue_core::workload::AssigneeLoad], priorities: &[String]) {
+    eprintln!(
+        "{:<20} {:>5} {:>8} {:>9}  {:<28} Oldest active",
+        "Assignee", "Open", "Blocked", "Estimate", "By priority"
+    );
+    eprintln!("{}", "-".repeat(90));
+    for load in report {
+        let open = match load.max_open {
+            Some(max) => format!("{}/{}", load.open, max),
+            None => load.open.to_string(),
+        };
+        let estimate = match load.max_estimate {
+            Some(max) => format!("{}/{}", load.estimate, max),
+            None => load.estimate.to_string(),
+        };
+        // Priorities in scale order; unknown levels last
+        let mut levels: Vec<(&String, &usize)> = load.by_priority.iter().collect();
+        levels.sort_by_key(|(level, _)| priorities.iter().position(|p| p == *level).unwrap_or(usize::MAX));
+        let by_priority: Vec<String> = levels.iter().map(|(level, n)| format!("{} {}", n, level)).collect();
+        let oldest = load
+            .oldest_active
+            .as_ref()
+            .map(|o| format!("{} ({})", o.id, o.created.get(..10).unwrap_or(&o.created)))
+            .unwrap_or_default();
+
+        eprintln!(
+            "{:<20} {:>5} {:>8} {:>9}  {:<28} {}{}",
+            truncate(&load.assignee, 18),
+            open,
+            load.blocked,
+            estimate,
+            truncate(&by_priority.join(", "), 28),
+            oldest,
+            if load.over_capacity { "  ⚠ over capacity" } else { "" }
+        );
+    }
+}
+
 fn truncate(s: &str, max_width: usize) -> String {
-    if s.len() > max_width {
-        format!("{}..", &s[..max_width - 2])
+    // Count characters, not bytes, so non-ASCII text is never split mid-character
+    if s.chars().count() > max_width {
+        format!("{}..", s.chars().take(max_width - 2).collect::<String>())
     } else {
         s.to_string()
     }
@@ -264,7 +264,9 @@ fn test_metadata_preservation() {
 
     assert!(content.contains("title: Test Metadata"));
     assert!(content.contains("priority: high"));
-    assert!(content.contains("assignee: @developer"));
+    assert!(content.contains("assignee: \"@developer\""));
+    let doc = cue_core::parse_file(&task_path).expect("card should parse");
+    assert_eq!(doc.frontmatter.unwrap().assignee.as_deref(), Some("@developer"));
     assert!(content.contains("- a"));
     assert!(content.contains("- b"));
     assert!(content.contains("- c"));
@@ -296,3 +298,732 @@ fn test_validate_specific_task() {
         .success()
         .stderr(predicate::str::contains("dependencies are valid"));
 }
+
+#[test]
+fn test_epic_hierarchy_workflow() {
+    let (_temp, workspace) = setup_workspace();
+
+    let output_epic = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Auth Epic"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let epic = extract_task_id(&output_epic);
+
+    let output_child = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Login Form", "--parent", &epic])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("Parent: {}", epic)))
+        .get_output()
+        .stderr
+        .clone();
+    let child = extract_task_id(&output_child);
+
+    // Child must record its parent
+    let content = fs::read_to_string(workspace.join(format!(".cuedeck/cards/{}.md", child))).unwrap();
+    assert!(content.contains(&format!("parent: {}", epic)));
+
+    // Unknown parent is rejected
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Orphan", "--parent", "zzzzzz"])
+        .assert()
+        .failure();
+
+    // Tree view nests the child under the epic with progress
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "list", "--status", "all", "--tree"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("{}: Auth Epic [todo] (0/1 done)", epic)))
+        .stderr(predicate::str::contains(format!("└── {}: Login Form", child)));
+
+    // Parent filter on the flat list
+    cue_command()
+        .current_dir(&workspace)
+        .args(["list", "--status", "all", "--parent", &epic])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(child.as_str()))
+        .stderr(predicate::str::contains("Auth Epic").not());
+
+    // Archiving an epic with open children warns
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "archive", &epic])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("still has 1 open child card(s)"));
+}
+
+#[test]
+fn test_card_history_records_mutations() {
+    let (_temp, workspace) = setup_workspace();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Audited Task", "--priority", "high"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "archive", &task])
+        .assert()
+        .success();
+
+    assert!(workspace.join(".cuedeck/activity.jsonl").exists());
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "history", &task])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("2 events"))
+        .stderr(predicate::str::contains("created"))
+        .stderr(predicate::str::contains("archived"))
+        .stderr(predicate::str::contains("status: todo → archived"));
+}
+
+#[test]
+fn test_create_from_template() {
+    let (_temp, workspace) = setup_workspace();
+
+    let templates = workspace.join(".cuedeck/templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(
+        templates.join("bug.md"),
+        "---\npriority: high\ntags: [bug]\ntype: bug\n---\n# {{title}}\n\nFiled {{date}} as {{id}}\n\n## Steps to Reproduce\n",
+    )
+    .unwrap();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Crash on save", "--template", "bug", "--tags", "editor"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Priority: high"))
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    let content = fs::read_to_string(workspace.join(format!(".cuedeck/cards/{}.md", task))).unwrap();
+    assert!(content.contains("priority: high"));
+    assert!(content.contains("type: bug"));
+    assert!(content.contains("  - editor"), "explicit tags override template tags");
+    assert!(content.contains("# Crash on save"));
+    assert!(content.contains(&format!("as {}", task)));
+    assert!(content.contains("## Steps to Reproduce"));
+    assert!(!content.contains("[Add description]"));
+
+    // Unknown templates are rejected
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Nope", "--template", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Template not found"));
+
+    // Doctor flags malformed templates
+    fs::write(templates.join("spike.md"), "---\ntags: research\n---\n# {{name}}\n").unwrap();
+    cue_command()
+        .current_dir(&workspace)
+        .args(["doctor", "--json"])
+        .assert()
+        .stdout(predicate::str::contains("Card Templates"))
+        .stdout(predicate::str::contains("Unknown placeholder"));
+}
+
+#[test]
+fn test_sequential_ids_and_prefix_lookup() {
+    let (_temp, workspace) = setup_workspace();
+
+    let config = workspace.join(".cuedeck/config.toml");
+    let mut content = fs::read_to_string(&config).unwrap_or_default();
+    content.push_str("\n[ids]\nscheme = \"sequential\"\nprefix = \"CUE\"\n");
+    fs::write(&config, content).unwrap();
+
+    // Pre-existing card pushes the sequence forward
+    fs::create_dir_all(workspace.join(".cuedeck/cards")).unwrap();
+    fs::write(
+        workspace.join(".cuedeck/cards/CUE-41.md"),
+        "---\ntitle: Existing\nstatus: todo\n---\n# Existing\n",
+    )
+    .unwrap();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Next Card"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    assert_eq!(extract_task_id(&output), "CUE-42");
+
+    // Unambiguous prefix resolves; ambiguous prefix is rejected
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "archive", "cue-42"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Archived card: CUE-42"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "deps", "CUE-4"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Ambiguous card ID"));
+}
+
+#[test]
+fn test_archive_storage_restore_and_purge() {
+    let (_temp, workspace) = setup_workspace();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Shelved Task"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "archive", &task])
+        .assert()
+        .success();
+    assert!(workspace.join(format!(".cuedeck/archive/{}.md", task)).exists());
+    assert!(!workspace.join(format!(".cuedeck/cards/{}.md", task)).exists());
+
+    // Only listed when archived cards are asked for
+    cue_command()
+        .current_dir(&workspace)
+        .args(["list", "--status", "todo"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Shelved Task").not());
+    cue_command()
+        .current_dir(&workspace)
+        .args(["list", "--status", "archived"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Shelved Task"));
+
+    // Archived cards can still be depended on and are shown by title
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Follow-up", "--depends-on", &task])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let follow_up = extract_task_id(&output);
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "deps", &follow_up])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("→ {}: Shelved Task", task)));
+
+    // Recently archived, so nothing is old enough to purge
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "purge", "--older-than", "90d"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No cards archived more than 90d ago"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "restore", &task])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("from archive (status: todo)"));
+    assert!(workspace.join(format!(".cuedeck/cards/{}.md", task)).exists());
+}
+
+#[test]
+fn test_card_move_follows_workflow() {
+    let (_temp, workspace) = setup_workspace();
+    fs::write(
+        workspace.join(".cuedeck/config.toml"),
+        "[workflow]\nstatuses = [\"todo\", \"active\", \"review\", \"done\"]\n\n[workflow.transitions]\ntodo = [\"active\"]\nactive = [\"review\"]\n",
+    )
+    .unwrap();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Workflow Task"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "move", &task])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Valid next statuses: active"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "move", &task, "done"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Valid next statuses from 'todo': active"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "move", &task, "active"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("todo → active"));
+}
+
+#[test]
+fn test_card_claim_and_release() {
+    let (_temp, workspace) = setup_workspace();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Contested Task"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "claim", &task, "--as", "agent-a", "--ttl", "1h"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Claimed"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "claim", &task, "--as", "agent-b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("claimed by agent-a"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["list", "--status", "todo", "--unclaimed"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Contested Task").not());
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "release", &task, "--as", "agent-b", "--force"])
+        .assert()
+        .success();
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["list", "--status", "todo", "--unclaimed"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Contested Task"));
+}
+
+#[test]
+fn test_card_start_stop_and_time_report() {
+    let (_temp, workspace) = setup_workspace();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Timed Task", "--assignee", "dana", "--tags", "überprüfung-der-anmeldung"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "start", &task])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Started work"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "start", &task])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already running"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "stop", &task])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Stopped work"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["report", "time", "--since", "2w"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("dana"));
+
+    // Long non-ASCII keys are shortened by characters
+    cue_command()
+        .current_dir(&workspace)
+        .args(["report", "time", "--by", "tag"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("überprüfung-der-anme.."));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["list", "--status", "todo", "--long"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Assignee").and(predicate::str::contains("0m")));
+}
+
+#[test]
+fn test_card_spawn_due() {
+    let (_temp, workspace) = setup_workspace();
+    fs::create_dir_all(workspace.join(".cuedeck/cards")).unwrap();
+    fs::write(
+        workspace.join(".cuedeck/cards/chore1.md"),
+        "---\ntitle: Dependency audit\nstatus: todo\nrecurrence: weekly\n---\n",
+    )
+    .unwrap();
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "spawn-due"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Spawned 1 recurring card"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "spawn-due"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No recurring cards are due"));
+}
+
+#[test]
+fn test_import_github_issues() {
+    let (_temp, workspace) = setup_workspace();
+    let export = workspace.join("issues.json");
+    fs::write(
+        &export,
+        r#"[
+            {"number": 7, "title": "Set up CI", "body": "", "state": "OPEN",
+             "labels": [{"name": "infra"}], "assignees": [{"login": "dana"}]},
+            {"number": 8, "title": "Deploy preview", "body": "Blocked by #7", "state": "OPEN",
+             "labels": [], "assignees": []}
+        ]"#,
+    )
+    .unwrap();
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["import", "issues.json", "--format", "github", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("would import 2 card(s), 1 dependency link(s)"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["import", "issues.json", "--format", "github"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Imported 2 card(s)"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["import", "issues.json", "--format", "github"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("0 card(s), 0 dependency link(s), 2 already imported"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["list", "--status", "todo", "--tags", "infra"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Set up CI"));
+}
+
+#[test]
+fn test_export_respects_list_filters() {
+    let (_temp, workspace) = setup_workspace();
+
+    for (title, tags) in [("Export me", "report"), ("Leave me", "other")] {
+        cue_command()
+            .current_dir(&workspace)
+            .args(["card", "create", title, "--tags", tags])
+            .assert()
+            .success();
+    }
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["export", "--format", "csv", "--status", "todo", "--tags", "report"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let csv = String::from_utf8(output).unwrap();
+    assert!(csv.starts_with("id,title,status"));
+    assert!(csv.contains("Export me"));
+    assert!(!csv.contains("Leave me"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["export", "--format", "ics", "--status", "todo", "-o", "cards.ics"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Exported 2 card(s)"));
+    let ics = fs::read_to_string(workspace.join("cards.ics")).unwrap();
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+}
+
+#[test]
+fn test_card_show_lists_commits() {
+    let (_temp, workspace) = setup_workspace();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Fix login"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    for args in [
+        vec!["init", "-q"],
+        vec!["config", "user.name", "Dana"],
+        vec!["config", "user.email", "dana@example.com"],
+        vec!["config", "commit.gpgsign", "false"],
+    ] {
+        std::process::Command::new("git").current_dir(&workspace).args(&args).output().unwrap();
+    }
+    let message = format!("fix login ({})", task);
+    std::process::Command::new("git")
+        .current_dir(&workspace)
+        .args(["commit", "--allow-empty", "-q", "-m", &message])
+        .output()
+        .unwrap();
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "show", &task])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Commits (1):").and(predicate::str::contains(message)));
+}
+
+#[test]
+fn test_card_start_branch_sets_current_card() {
+    let (_temp, workspace) = setup_workspace();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Fix login"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    for args in [
+        vec!["init", "-q"],
+        vec!["config", "user.name", "Dana"],
+        vec!["config", "user.email", "dana@example.com"],
+        vec!["config", "commit.gpgsign", "false"],
+        vec!["commit", "--allow-empty", "-q", "-m", "init"],
+    ] {
+        std::process::Command::new("git").current_dir(&workspace).args(&args).output().unwrap();
+    }
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "current"])
+        .assert()
+        .failure();
+
+    let branch = format!("{}-fix-login", task);
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "start", &task, "--branch"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("Created branch {}", branch)));
+
+    let head = std::process::Command::new("git")
+        .current_dir(&workspace)
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&head.stdout).trim(), branch);
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "current"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("{}: Fix login [active]", task)));
+
+    // Commands default to the current card
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "stop"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!("Stopped work on {}", task)));
+}
+
+#[test]
+fn test_report_burndown_throughput_and_cycle_time() {
+    let (_temp, workspace) = setup_workspace();
+    let cards = workspace.join(".cuedeck/cards");
+    fs::create_dir_all(&cards).unwrap();
+    fs::write(
+        cards.join("aaa111.md"),
+        "---\ntitle: A\nstatus: done\nassignee: dana\ncreated: 2024-06-03T09:00:00Z\nupdated: 2024-06-05T09:00:00Z\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        cards.join("bbb222.md"),
+        "---\ntitle: B\nstatus: todo\ncreated: 2024-06-04T09:00:00Z\n---\n",
+    )
+    .unwrap();
+    let range = ["--since", "2024-06-03", "--until", "2024-06-09"];
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["report", "burndown"])
+        .args(range)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("2024-06-04").and(predicate::str::contains("2024-06-09        1       1")));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["report", "throughput", "--chart"])
+        .args(range)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("2024-06-03  █"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["report", "cycle-time"])
+        .args(range)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("dana").and(predicate::str::contains("2.0d")));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["report", "burndown", "--since", "2024-06-09", "--until", "2024-06-03"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is after --until"));
+}
+
+#[test]
+fn test_digest_lists_changes_as_markdown() {
+    let (_temp, workspace) = setup_workspace();
+
+    let output = cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Write digest"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+    let task = extract_task_id(&output);
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["digest", "--since", "1d"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with("# Digest since ")
+                .and(predicate::str::contains("## Created (1)"))
+                .and(predicate::str::contains(format!("- `{}` Write digest (todo)", task))),
+        );
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["digest", "--since", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid --since"));
+}
+
+#[test]
+fn test_report_workload_and_capacity_warning() {
+    let (_temp, workspace) = setup_workspace();
+    fs::write(workspace.join(".cuedeck/config.toml"), "[capacity]\nmax_open = 1\n").unwrap();
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "First", "--assignee", "dana"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("over the limit").not());
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "create", "Second", "--assignee", "dana"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("⚠ dana now has 2 open card(s), over the limit of 1"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["card", "update", "--where", "title:First", "--set", "estimate=3"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("⚠ dana now has 2 open card(s)"));
+
+    cue_command()
+        .current_dir(&workspace)
+        .args(["report", "workload"])
+        .assert()
+        .success()
+        .stderr(
+            predicate::str::contains("dana")
+                .and(predicate::str::contains("2/1"))
+                .and(predicate::str::contains("over capacity")),
+        );
+}
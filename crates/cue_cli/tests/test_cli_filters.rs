@@ -123,3 +123,116 @@ fn test_cli_combined_filters() {
         .stderr(predicate::str::contains(&id_noise1).not())
         .stderr(predicate::str::contains(&id_noise2).not());
 }
+
+#[test]
+fn test_cli_filter_due_and_milestone_report() {
+    let temp = init_workspace();
+
+    let id_late = create_task(&temp, "Late Task", &["--due", "2020-01-01", "--milestone", "v1"]);
+    let id_future = create_task(&temp, "Future Task", &["--due", "2999-01-01", "--milestone", "v1"]);
+    let id_none = create_task(&temp, "Unscheduled Task", &[]);
+
+    // Overdue filter only matches past-due open cards
+    cue_command()
+        .current_dir(temp.path())
+        .args(["list", "--status", "all", "--due", "due:overdue"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(&id_late))
+        .stderr(predicate::str::contains(&id_future).not())
+        .stderr(predicate::str::contains(&id_none).not());
+
+    // Milestone filter
+    cue_command()
+        .current_dir(temp.path())
+        .args(["list", "--status", "all", "--milestone", "v1"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(&id_future))
+        .stderr(predicate::str::contains(&id_none).not());
+
+    // Milestone report lists progress and overdue cards
+    cue_command()
+        .current_dir(temp.path())
+        .arg("milestone")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("0/2"))
+        .stderr(predicate::str::contains(format!("{}: Late Task (due 2020-01-01", id_late)));
+
+    // Invalid due dates are rejected at creation
+    cue_command()
+        .current_dir(temp.path())
+        .args(["card", "create", "Bad Date", "--due", "next tuesday"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_query_expression() {
+    let temp = init_workspace();
+
+    let id_auth = create_task(&temp, "Auth Task", &["--tags", "auth", "--assignee", "alice"]);
+    let id_api = create_task(&temp, "API Task", &["--tags", "api", "--assignee", "bob"]);
+    let id_ui = create_task(&temp, "UI Task", &["--tags", "ui"]);
+
+    // Query status terms replace the default --status=active
+    cue_command()
+        .current_dir(temp.path())
+        .args(["list", "-q", "status:todo (tag:auth OR tag:api) -assignee:@bob"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(&id_auth))
+        .stderr(predicate::str::contains(&id_api).not())
+        .stderr(predicate::str::contains(&id_ui).not());
+
+    // Bad tokens are reported with their position
+    cue_command()
+        .current_dir(temp.path())
+        .args(["list", "-q", "tag:auth colour:red"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("column 10"))
+        .stderr(predicate::str::contains("Unknown field 'colour'"));
+}
+
+#[test]
+fn test_cli_bulk_update() {
+    let temp = init_workspace();
+
+    let id_a = create_task(&temp, "Bug A", &["--tags", "bug"]);
+    let id_b = create_task(&temp, "Bug B", &["--tags", "bug"]);
+    let id_other = create_task(&temp, "Feature", &["--tags", "feature"]);
+
+    // Dry run prints the diff but writes nothing
+    cue_command()
+        .current_dir(temp.path())
+        .args(["card", "update", "--where", "tag:bug", "--set", "priority=critical", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("priority: medium → critical"))
+        .stderr(predicate::str::contains("2 card(s) would be updated"));
+
+    cue_command()
+        .current_dir(temp.path())
+        .args(["list", "--status", "all", "--priority", "critical"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(&id_a).not());
+
+    cue_command()
+        .current_dir(temp.path())
+        .args(["card", "update", "--where", "tag:bug", "--set", "priority=critical", "--set", "assignee=dana"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Updated 2 card(s)"));
+
+    cue_command()
+        .current_dir(temp.path())
+        .args(["list", "--status", "all", "--priority", "critical", "--assignee", "dana"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(&id_a))
+        .stderr(predicate::str::contains(&id_b))
+        .stderr(predicate::str::contains(&id_other).not());
+}
@@ -104,3 +104,33 @@ fn test_invalid_command() {
     let mut cmd = Command::cargo_bin("cue").unwrap();
     cmd.arg("invalid-command").assert().failure();
 }
+
+#[test]
+fn test_cue_mv_dry_run_and_apply() {
+    let temp = TempDir::new().unwrap();
+    let docs = temp.path().join(".cuedeck/docs");
+    std::fs::create_dir_all(&docs).unwrap();
+    std::fs::write(docs.join("old.md"), "# Old\n").unwrap();
+    std::fs::write(docs.join("index.md"), "# Index\n\nSee [[old]].\n").unwrap();
+
+    Command::cargo_bin("cue")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["mv", ".cuedeck/docs/old.md", ".cuedeck/docs/new.md", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("[[old]] -> [[new]]"))
+        .stderr(predicate::str::contains("Dry run"));
+    assert!(docs.join("old.md").exists());
+
+    Command::cargo_bin("cue")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["mv", ".cuedeck/docs/old.md", ".cuedeck/docs/new.md"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(".cuedeck/docs/index.md"));
+    assert!(docs.join("new.md").exists());
+    let index = std::fs::read_to_string(docs.join("index.md")).unwrap();
+    assert!(index.contains("[[new]]"));
+}
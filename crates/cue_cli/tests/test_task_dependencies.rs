@@ -291,3 +291,36 @@ fn test_task_create_nonexistent_dependency() {
         .failure()
         .stderr(predicate::str::contains("Dependency not found"));
 }
+
+#[test]
+fn test_card_delete_requires_cascade_or_force() {
+    let temp = init_workspace();
+    let cards_dir = temp.path().join(".cuedeck/cards");
+    fs::create_dir_all(&cards_dir).unwrap();
+    fs::write(cards_dir.join("base11.md"), "---\ntitle: Base\n---\n").unwrap();
+    fs::write(
+        cards_dir.join("next11.md"),
+        "---\ntitle: Next\ndepends_on:\n  - base11\n---\n",
+    )
+    .unwrap();
+
+    cue_command()
+        .current_dir(temp.path())
+        .args(["card", "delete", "base11"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("depended on by next11"))
+        .stderr(predicate::str::contains("--cascade"));
+    assert!(cards_dir.join("base11.md").exists());
+
+    cue_command()
+        .current_dir(temp.path())
+        .args(["card", "delete", "base11", "--cascade"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Removed reference from next11"));
+
+    assert!(temp.path().join(".cuedeck/trash/base11.md").exists());
+    let dependent = fs::read_to_string(cards_dir.join("next11.md")).unwrap();
+    assert!(!dependent.contains("base11"));
+}
@@ -39,8 +39,8 @@ pub enum CueError {
     #[error("Cache is stale")]
     StaleCache,
 
-    #[error("Resource locked by PID {pid}")]
-    Locked { pid: u32 },
+    #[error("Resource locked by {holder} until {until}")]
+    Locked { holder: String, until: String },
 
     #[error("Rate limit exceeded: {current}/{limit} in {window}s")]
     RateLimit {
@@ -52,6 +52,15 @@ pub enum CueError {
     #[error("Invalid input: {0}")]
     ValidationError(String),
 
+    /// The file changed since the caller read it (hash precondition failed)
+    #[error("Conflict: {path} was modified since it was read (current hash {current_hash})")]
+    Conflict {
+        path: String,
+        current_hash: String,
+        /// Frontmatter as it is now, if it parses
+        frontmatter: Option<Box<CardMetadata>>,
+    },
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -73,7 +82,7 @@ pub struct CardMetadata {
     #[serde(default)]
     pub assignee: Option<String>,
 
-    /// Priority (low, medium, high, critical)
+    /// Priority (a level from the `[priorities]` config; low, medium, high, critical by default)
     #[serde(default = "default_priority")]
     pub priority: String,
 
@@ -93,6 +102,50 @@ pub struct CardMetadata {
     /// Task IDs this task depends on
     #[serde(default)]
     pub depends_on: Option<Vec<String>>,
+
+    /// Parent (epic) card ID this task belongs to
+    #[serde(default)]
+    pub parent: Option<String>,
+
+    /// Due date (YYYY-MM-DD)
+    #[serde(default)]
+    pub due: Option<String>,
+
+    /// Milestone this task is planned for
+    #[serde(default)]
+    pub milestone: Option<String>,
+
+    /// Size of the work, in whatever unit the team plans in (points, hours)
+    #[serde(default)]
+    pub estimate: Option<f64>,
+
+    /// Agent or user currently holding a claim on the card
+    #[serde(default)]
+    pub claimed_by: Option<String>,
+
+    /// When the claim lapses (RFC 3339)
+    #[serde(default)]
+    pub claim_expires: Option<String>,
+
+    /// Repeat rule making this card a recurring template (daily, weekly, monthly or cron)
+    #[serde(default)]
+    pub recurrence: Option<String>,
+
+    /// Recurring template card this card was spawned from
+    #[serde(default)]
+    pub recurrence_of: Option<String>,
+
+    /// Previous instance of the same recurring card
+    #[serde(default)]
+    pub previous_instance: Option<String>,
+
+    /// Source tracker ID of an imported card (e.g. `github#12`, `jira:PROJ-7`)
+    #[serde(default)]
+    pub external_id: Option<String>,
+
+    /// Git branch the card is being worked on
+    #[serde(default)]
+    pub branch: Option<String>,
 }
 
 fn default_status() -> String {
@@ -4,6 +4,7 @@
 
 use cue_common::{CueError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// Main configuration structure
@@ -45,6 +46,26 @@ pub struct Config {
     #[serde(default)]
     pub search: SearchConfig,
 
+    /// Card ID settings
+    #[serde(default)]
+    pub ids: IdConfig,
+
+    /// Status workflow settings
+    #[serde(default)]
+    pub workflow: WorkflowConfig,
+
+    /// Priority scale settings
+    #[serde(default)]
+    pub priorities: PriorityConfig,
+
+    /// Git integration settings
+    #[serde(default)]
+    pub git: GitConfig,
+
+    /// Per-assignee workload limits
+    #[serde(default)]
+    pub capacity: CapacityConfig,
+
     // Keep old budgets field for backward compatibility
     #[serde(default, skip_serializing)]
     pub budgets: TokenBudgets,
@@ -267,6 +288,302 @@ impl Default for SearchConfig {
     }
 }
 
+/// Card ID configuration ([ids])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdConfig {
+    /// "random" (6 lowercase alphanumerics) or "sequential" (e.g. CUE-142)
+    #[serde(default = "default_id_scheme")]
+    pub scheme: String,
+
+    /// Prefix for sequential IDs
+    #[serde(default = "default_id_prefix")]
+    pub prefix: String,
+}
+
+fn default_id_scheme() -> String {
+    "random".to_string()
+}
+fn default_id_prefix() -> String {
+    "CUE".to_string()
+}
+
+impl Default for IdConfig {
+    fn default() -> Self {
+        Self {
+            scheme: default_id_scheme(),
+            prefix: default_id_prefix(),
+        }
+    }
+}
+
+/// Status workflow configuration ([workflow])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowConfig {
+    /// Allowed statuses, in display order
+    #[serde(default = "default_statuses")]
+    pub statuses: Vec<String>,
+
+    /// Statuses that mean work is under way (checked for staleness)
+    #[serde(default = "default_in_progress")]
+    pub in_progress: Vec<String>,
+
+    /// Statuses that close a card
+    #[serde(default = "default_terminal")]
+    pub terminal: Vec<String>,
+
+    /// Allowed next statuses per status; a status without an entry may
+    /// move to any status, except a terminal one once any are configured
+    #[serde(default)]
+    pub transitions: BTreeMap<String, Vec<String>>,
+}
+
+fn default_statuses() -> Vec<String> {
+    ["todo", "active", "in-progress", "done", "archived"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+fn default_in_progress() -> Vec<String> {
+    vec!["active".to_string(), "in-progress".to_string()]
+}
+fn default_terminal() -> Vec<String> {
+    vec!["done".to_string(), "archived".to_string()]
+}
+
+impl Default for WorkflowConfig {
+    fn default() -> Self {
+        Self {
+            statuses: default_statuses(),
+            in_progress: default_in_progress(),
+            terminal: default_terminal(),
+            transitions: BTreeMap::new(),
+        }
+    }
+}
+
+impl WorkflowConfig {
+    /// Whether `status` is part of the vocabulary
+    pub fn is_known(&self, status: &str) -> bool {
+        self.statuses.iter().any(|s| s == status)
+    }
+
+    /// Whether `status` closes a card
+    pub fn is_terminal(&self, status: &str) -> bool {
+        self.terminal.iter().any(|s| s == status)
+    }
+
+    /// Whether `status` means work is under way
+    pub fn is_in_progress(&self, status: &str) -> bool {
+        self.in_progress.iter().any(|s| s == status)
+    }
+
+    /// Statuses a card in `from` may move to, in vocabulary order
+    pub fn next_statuses(&self, from: &str) -> Vec<String> {
+        match self.transitions.get(from) {
+            Some(allowed) => self
+                .statuses
+                .iter()
+                .filter(|s| allowed.contains(s))
+                .cloned()
+                .collect(),
+            None if self.is_final(from) => Vec::new(),
+            None => self.statuses.iter().filter(|s| *s != from).cloned().collect(),
+        }
+    }
+
+    /// A terminal status with no way out, because transitions are configured
+    /// and none are listed for it
+    fn is_final(&self, status: &str) -> bool {
+        !self.transitions.is_empty()
+            && !self.transitions.contains_key(status)
+            && self.is_terminal(status)
+    }
+
+    /// Check a status change; staying in the same status is always allowed
+    pub fn check_transition(&self, from: &str, to: &str) -> Result<()> {
+        if from == to {
+            return Ok(());
+        }
+        if !self.is_known(to) {
+            return Err(CueError::ValidationError(format!(
+                "Unknown status '{}'. Expected one of: {}",
+                to,
+                self.statuses.join(", ")
+            )));
+        }
+        if !self.transitions.contains_key(from) && !self.is_final(from) {
+            return Ok(());
+        }
+
+        let next = self.next_statuses(from);
+        if next.iter().any(|s| s == to) {
+            return Ok(());
+        }
+        Err(CueError::ValidationError(if self.is_final(from) {
+            format!(
+                "Cannot move from '{}' to '{}': '{}' is terminal; list it under [workflow.transitions] to allow reopening",
+                from, to, from
+            )
+        } else if next.is_empty() {
+            format!("Cannot move from '{}' to '{}': '{}' has no next statuses", from, to, from)
+        } else {
+            format!(
+                "Cannot move from '{}' to '{}'. Allowed: {}",
+                from,
+                to,
+                next.join(", ")
+            )
+        }))
+    }
+}
+
+/// Priority scale configuration ([priorities])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityConfig {
+    /// Priority names, highest first
+    #[serde(default = "default_priority_levels")]
+    pub levels: Vec<String>,
+
+    /// Priority given to new cards
+    #[serde(default = "default_priority")]
+    pub default: String,
+
+    /// Alternative spellings mapped to a level (e.g. `urgent = "critical"`)
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+fn default_priority_levels() -> Vec<String> {
+    ["critical", "high", "medium", "low"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+fn default_priority() -> String {
+    "medium".to_string()
+}
+
+impl Default for PriorityConfig {
+    fn default() -> Self {
+        Self {
+            levels: default_priority_levels(),
+            default: default_priority(),
+            aliases: BTreeMap::new(),
+        }
+    }
+}
+
+impl PriorityConfig {
+    /// The level a priority or alias names, ignoring case
+    pub fn resolve(&self, priority: &str) -> Option<&str> {
+        let level = self
+            .aliases
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(priority))
+            .map(|(_, level)| level.as_str())
+            .unwrap_or(priority);
+        self.levels
+            .iter()
+            .find(|l| l.eq_ignore_ascii_case(level))
+            .map(|l| l.as_str())
+    }
+
+    /// Sort key: higher for more urgent levels, 0 for unknown priorities
+    pub fn score(&self, priority: &str) -> usize {
+        self.resolve(priority)
+            .and_then(|level| self.levels.iter().position(|l| l == level))
+            .map_or(0, |i| self.levels.len() - i)
+    }
+
+    /// Check that `default` names one of the levels
+    pub fn check(&self) -> Result<()> {
+        if self.levels.iter().any(|l| l.eq_ignore_ascii_case(&self.default)) {
+            return Ok(());
+        }
+        Err(CueError::ConfigError(format!(
+            "Invalid [priorities] default '{}'. Must be one of: {}",
+            self.default,
+            self.levels.join(", ")
+        )))
+    }
+
+    /// Resolve a priority, failing with the list of levels if unknown
+    pub fn validate(&self, priority: &str) -> Result<String> {
+        self.resolve(priority).map(String::from).ok_or_else(|| {
+            CueError::ValidationError(format!(
+                "Invalid priority '{}'. Must be one of: {}",
+                priority,
+                self.levels.join(", ")
+            ))
+        })
+    }
+}
+
+/// Git integration configuration ([git])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitConfig {
+    /// Move cards to `done` when a scanned commit has a `Closes: <id>` trailer
+    #[serde(default)]
+    pub close_on_trailer: bool,
+
+    /// Branch name for `cue card start --branch`; `{{id}}` and `{{slug}}`
+    /// (the title in lowercase-dashed form) are filled in
+    #[serde(default = "default_branch_template")]
+    pub branch_template: String,
+}
+
+fn default_branch_template() -> String {
+    "{{id}}-{{slug}}".to_string()
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            close_on_trailer: false,
+            branch_template: default_branch_template(),
+        }
+    }
+}
+
+/// Workload limits per assignee ([capacity])
+///
+/// Limits only produce warnings; no limit is set by default.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CapacityConfig {
+    /// Open cards an assignee may hold
+    #[serde(default)]
+    pub max_open: Option<usize>,
+
+    /// Summed `estimate` of an assignee's open cards
+    #[serde(default)]
+    pub max_estimate: Option<f64>,
+
+    /// Overrides by assignee name ([capacity.assignees.<name>])
+    #[serde(default)]
+    pub assignees: BTreeMap<String, CapacityLimit>,
+}
+
+/// Limits for one assignee; unset fields fall back to the [capacity] defaults
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct CapacityLimit {
+    #[serde(default)]
+    pub max_open: Option<usize>,
+
+    #[serde(default)]
+    pub max_estimate: Option<f64>,
+}
+
+impl CapacityConfig {
+    /// Effective limits for `assignee`
+    pub fn limit_for(&self, assignee: &str) -> CapacityLimit {
+        let own = self.assignees.get(assignee);
+        CapacityLimit {
+            max_open: own.and_then(|l| l.max_open).or(self.max_open),
+            max_estimate: own.and_then(|l| l.max_estimate).or(self.max_estimate),
+        }
+    }
+}
+
 /// Token budget configuration (legacy, for backward compatibility)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenBudgets {
@@ -302,7 +619,26 @@ impl Default for TokenBudgets {
 
 impl Config {
     /// Load configuration from workspace root
+    ///
+    /// An invalid `[priorities]` section falls back to the default scale with
+    /// a warning, so commands (and `cue doctor`) keep working; `check_file`
+    /// reports the error.
     pub fn load(workspace_root: &Path) -> Result<Self> {
+        let mut config = Self::read(workspace_root)?;
+        if let Err(e) = config.priorities.check() {
+            tracing::warn!("{}; using the default priority scale", e);
+            config.priorities = PriorityConfig::default();
+        }
+        Ok(config)
+    }
+
+    /// Parse the config file and check the sections `load` would otherwise
+    /// fall back from
+    pub fn check_file(workspace_root: &Path) -> Result<()> {
+        Self::read(workspace_root)?.priorities.check()
+    }
+
+    fn read(workspace_root: &Path) -> Result<Self> {
         let config_path = workspace_root.join(".cuedeck/config.toml");
 
         if !config_path.exists() {
@@ -317,6 +653,11 @@ impl Config {
                 watcher: WatcherConfig::default(),
                 cache: CacheConfig::default(),
                 search: SearchConfig::default(),
+                ids: IdConfig::default(),
+                workflow: WorkflowConfig::default(),
+                priorities: PriorityConfig::default(),
+                git: GitConfig::default(),
+                capacity: CapacityConfig::default(),
                 budgets: TokenBudgets::default(),
             });
         }
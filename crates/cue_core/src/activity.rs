@@ -0,0 +1,269 @@
+//! Append-only activity log for task cards
+//!
+//! Every card mutation (create, update, archive) is appended as one JSON line
+//! to `.cuedeck/activity.jsonl`. Entries are never rewritten, so the log is a
+//! reliable record of who changed what and when.
+
+use cue_common::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Location of the activity log, relative to the workspace root
+pub const ACTIVITY_LOG: &str = ".cuedeck/activity.jsonl";
+
+/// Fields that change on every write and would only add noise to diffs
+const IGNORED_FIELDS: &[&str] = &["updated"];
+
+/// Author override for the current process (e.g. the connected MCP client)
+static SESSION_AUTHOR: RwLock<Option<String>> = RwLock::new(None);
+
+/// A single field change within an event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<Value>,
+}
+
+/// One entry in the activity log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    pub task_id: String,
+    /// created, updated, archived, claimed, released, renamed or deleted
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub changes: Vec<FieldChange>,
+}
+
+/// Set the author recorded for mutations made by this process
+///
+/// Takes precedence over `[author]` in config.toml. The MCP server sets this
+/// from the client name sent during `initialize`.
+pub fn set_session_author(name: Option<String>) {
+    if let Ok(mut author) = SESSION_AUTHOR.write() {
+        *author = name.filter(|n| !n.is_empty());
+    }
+}
+
+/// Resolve the author for a new event
+pub fn current_author(workspace_root: &Path) -> Option<String> {
+    if let Some(name) = SESSION_AUTHOR.read().ok().and_then(|a| a.clone()) {
+        return Some(name);
+    }
+
+    cue_config::Config::load(workspace_root)
+        .ok()
+        .map(|c| c.author.name)
+        .filter(|n| !n.is_empty())
+}
+
+fn log_path(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(ACTIVITY_LOG)
+}
+
+/// Diff two frontmatter mappings into field changes
+///
+/// Keys keep the order they have in `new`, followed by removed keys.
+pub fn diff_frontmatter(old: &serde_yaml::Mapping, new: &serde_yaml::Mapping) -> Vec<FieldChange> {
+    let to_json = |v: &serde_yaml::Value| serde_json::to_value(v).ok();
+    let mut changes = Vec::new();
+
+    for (key, new_value) in new {
+        let Some(field) = key.as_str() else { continue };
+        if IGNORED_FIELDS.contains(&field) {
+            continue;
+        }
+        let old_value = old.get(key);
+        if old_value != Some(new_value) {
+            changes.push(FieldChange {
+                field: field.to_string(),
+                old: old_value.and_then(to_json),
+                new: to_json(new_value),
+            });
+        }
+    }
+
+    for (key, old_value) in old {
+        let Some(field) = key.as_str() else { continue };
+        if !new.contains_key(key) && !IGNORED_FIELDS.contains(&field) {
+            changes.push(FieldChange {
+                field: field.to_string(),
+                old: to_json(old_value),
+                new: None,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Append an event for `task_id` to the activity log
+pub fn record_event(
+    workspace_root: &Path,
+    task_id: &str,
+    action: &str,
+    changes: Vec<FieldChange>,
+) -> Result<()> {
+    let event = ActivityEvent {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        task_id: task_id.to_string(),
+        action: action.to_string(),
+        author: current_author(workspace_root),
+        changes,
+    };
+
+    let path = log_path(workspace_root);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // One write per line keeps concurrent appends from interleaving
+    let mut line = serde_json::to_string(&event)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    file.write_all(line.as_bytes())?;
+
+    Ok(())
+}
+
+/// Record an event, logging instead of failing if the log cannot be written
+///
+/// The card itself has already been written at this point, so a log failure
+/// must not turn a successful mutation into an error.
+pub(crate) fn record_event_lossy(
+    workspace_root: &Path,
+    task_id: &str,
+    action: &str,
+    changes: Vec<FieldChange>,
+) {
+    if let Err(e) = record_event(workspace_root, task_id, action, changes) {
+        tracing::warn!("Failed to write activity log for {}: {}", task_id, e);
+    }
+}
+
+/// Read every event in the log, oldest first
+///
+/// Malformed lines are skipped so a single bad entry cannot hide history.
+pub fn read_events(workspace_root: &Path) -> Result<Vec<ActivityEvent>> {
+    let path = log_path(workspace_root);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str::<ActivityEvent>(l).ok())
+        .collect())
+}
+
+/// Read all events for a task, oldest first
+///
+/// Follows `renamed` events back to the card's earlier IDs. Events for an ID
+/// after the card that had it was renamed belong to a different card.
+pub fn task_history(workspace_root: &Path, task_id: &str) -> Result<Vec<ActivityEvent>> {
+    let mut current = task_id.to_string();
+    let mut history = Vec::new();
+    for event in read_events(workspace_root)?.into_iter().rev() {
+        if event.action == "renamed" {
+            let id_change = event.changes.iter().find(|c| c.field == "id");
+            let old_id = id_change.and_then(|c| c.old.as_ref()).and_then(Value::as_str);
+            // The ID was given up here; anything older was another card
+            if old_id == Some(current.as_str()) && event.task_id != current {
+                break;
+            }
+            if event.task_id == current {
+                let old_id = old_id.map(String::from);
+                history.push(event);
+                if let Some(old_id) = old_id {
+                    current = old_id;
+                }
+                continue;
+            }
+        }
+        if event.task_id == current {
+            history.push(event);
+        }
+    }
+    history.reverse();
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_frontmatter_ignores_updated() {
+        let old: serde_yaml::Mapping =
+            serde_yaml::from_str("title: A\nstatus: todo\nupdated: x\nassignee: bob").unwrap();
+        let new: serde_yaml::Mapping =
+            serde_yaml::from_str("title: A\nstatus: done\nupdated: y").unwrap();
+
+        let changes = diff_frontmatter(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, "status");
+        assert_eq!(changes[0].old, Some(Value::from("todo")));
+        assert_eq!(changes[0].new, Some(Value::from("done")));
+        assert_eq!(changes[1].field, "assignee");
+        assert_eq!(changes[1].new, None);
+    }
+
+    #[test]
+    fn test_record_and_read_history() {
+        let temp = assert_fs::TempDir::new().unwrap();
+
+        record_event(temp.path(), "abc123", "created", Vec::new()).unwrap();
+        record_event(temp.path(), "zzz999", "created", Vec::new()).unwrap();
+        record_event(
+            temp.path(),
+            "abc123",
+            "updated",
+            vec![FieldChange {
+                field: "status".to_string(),
+                old: Some(Value::from("todo")),
+                new: Some(Value::from("active")),
+            }],
+        )
+        .unwrap();
+
+        let history = task_history(temp.path(), "abc123").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].action, "created");
+        assert_eq!(history[1].changes[0].field, "status");
+    }
+
+    #[test]
+    fn test_history_follows_renames() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let renamed = vec![FieldChange {
+            field: "id".to_string(),
+            old: Some(Value::from("abc123")),
+            new: Some(Value::from("login")),
+        }];
+
+        record_event(temp.path(), "abc123", "created", Vec::new()).unwrap();
+        record_event(temp.path(), "login", "renamed", renamed).unwrap();
+        record_event(temp.path(), "login", "updated", Vec::new()).unwrap();
+        // A new card takes the old ID
+        record_event(temp.path(), "abc123", "created", Vec::new()).unwrap();
+
+        let history = task_history(temp.path(), "login").unwrap();
+        let actions: Vec<&str> = history.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, vec!["created", "renamed", "updated"]);
+        assert_eq!(history[0].task_id, "abc123");
+
+        assert_eq!(task_history(temp.path(), "abc123").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,331 @@
+//! Archived card storage
+//!
+//! Archived cards live in `.cuedeck/archive/` so the everyday scans of
+//! `.cuedeck/cards` (listing, search, the engine) skip them. Setting
+//! `status: archived` through `update_task` moves a card there and any other
+//! status moves it back; listing and search only read the archive when asked.
+
+use crate::activity::{record_event_lossy, task_history};
+use crate::card_ids::{is_valid_task_id, resolve_task_id};
+use crate::parse_file;
+use crate::trash::trash_dir;
+use cue_common::{CueError, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Status restored when the activity log does not say what it was
+const DEFAULT_RESTORED_STATUS: &str = "todo";
+
+/// Directory archived cards are moved to
+pub fn archive_dir(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".cuedeck/archive")
+}
+
+/// Location of a card: the cards directory, or the archive if it is there
+///
+/// Returns the cards-directory path for cards that do not exist yet.
+pub fn card_path(workspace_root: &Path, id: &str) -> PathBuf {
+    let active = workspace_root.join(".cuedeck/cards").join(format!("{}.md", id));
+    if active.exists() {
+        return active;
+    }
+    let archived = archive_dir(workspace_root).join(format!("{}.md", id));
+    if archived.exists() {
+        archived
+    } else {
+        active
+    }
+}
+
+/// Whether a card exists, active or archived
+pub fn card_exists(workspace_root: &Path, id: &str) -> bool {
+    card_path(workspace_root, id).exists()
+}
+
+/// IDs of archived cards, sorted
+pub fn list_archived_ids(workspace_root: &Path) -> Result<Vec<String>> {
+    let dir = archive_dir(workspace_root);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut ids: Vec<String> = fs::read_dir(&dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "md"))
+        .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(String::from))
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+/// Move a card between the cards and archive directories to match its status
+///
+/// Returns the card's path after the move.
+pub(crate) fn settle_card(workspace_root: &Path, path: &Path) -> Result<PathBuf> {
+    let archived = parse_file(path)?
+        .frontmatter
+        .is_some_and(|m| m.status == "archived");
+    let target_dir = if archived {
+        archive_dir(workspace_root)
+    } else {
+        workspace_root.join(".cuedeck/cards")
+    };
+    if path.parent() == Some(target_dir.as_path()) {
+        return Ok(path.to_path_buf());
+    }
+
+    let Some(file_name) = path.file_name() else {
+        return Ok(path.to_path_buf());
+    };
+    let target = target_dir.join(file_name);
+    if target.exists() {
+        return Err(CueError::ValidationError(format!(
+            "Cannot move {} to {}: a card with that ID already exists there",
+            path.display(),
+            target_dir.display()
+        )));
+    }
+
+    fs::create_dir_all(&target_dir)?;
+    fs::rename(path, &target)?;
+    Ok(target)
+}
+
+/// Where `restore_task` brought a card back from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RestoreSource {
+    Archive,
+    Trash,
+}
+
+/// Outcome of `restore_task`
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreReport {
+    pub id: String,
+    pub source: RestoreSource,
+    /// Status of the card after the restore
+    pub status: String,
+}
+
+/// Bring a card back from the archive or the trash
+///
+/// Archived cards get the status they had before they were archived, taken
+/// from the activity log (`todo` if unknown). Trashed cards come back as they
+/// were when deleted; the most recent deletion wins if there are several.
+pub fn restore_task(workspace_root: &Path, id: &str) -> Result<RestoreReport> {
+    match resolve_task_id(workspace_root, id) {
+        Ok(id) => restore_archived(workspace_root, &id),
+        Err(CueError::FileNotFound { .. }) => restore_trashed(workspace_root, id),
+        Err(e) => Err(e),
+    }
+}
+
+fn restore_archived(workspace_root: &Path, id: &str) -> Result<RestoreReport> {
+    if card_path(workspace_root, id).parent() != Some(archive_dir(workspace_root).as_path()) {
+        return Err(CueError::ValidationError(format!(
+            "Card {} is not archived",
+            id
+        )));
+    }
+
+    let status = previous_status(workspace_root, id)?;
+    let mut updates = serde_json::Map::new();
+    updates.insert("status".to_string(), serde_json::Value::String(status.clone()));
+    crate::tasks::update_task(workspace_root, id, updates)?;
+
+    Ok(RestoreReport {
+        id: id.to_string(),
+        source: RestoreSource::Archive,
+        status,
+    })
+}
+
+/// Status a card had before its most recent archive
+fn previous_status(workspace_root: &Path, id: &str) -> Result<String> {
+    let history = task_history(workspace_root, id)?;
+    let status = history
+        .iter()
+        .rev()
+        .flat_map(|event| &event.changes)
+        .find(|c| c.field == "status" && c.new.as_ref().and_then(|v| v.as_str()) == Some("archived"))
+        .and_then(|c| c.old.as_ref()?.as_str().map(String::from))
+        .filter(|s| s != "archived")
+        .unwrap_or_else(|| DEFAULT_RESTORED_STATUS.to_string());
+    Ok(status)
+}
+
+fn restore_trashed(workspace_root: &Path, id: &str) -> Result<RestoreReport> {
+    let not_found = || CueError::FileNotFound {
+        path: workspace_root
+            .join(".cuedeck/cards")
+            .join(format!("{}.md", id))
+            .to_string_lossy()
+            .to_string(),
+    };
+    if !is_valid_task_id(id) {
+        return Err(not_found());
+    }
+
+    // `<id>.md` is the first deletion; timestamped names sort after it
+    let dir = trash_dir(workspace_root);
+    let source = fs::read_dir(&dir)
+        .map_err(|_| not_found())?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with(".md") && n.split('.').next() == Some(id))
+        })
+        .max_by_key(|p| (p.file_name() != Some(format!("{}.md", id).as_ref()), p.clone()))
+        .ok_or_else(not_found)?;
+
+    let target = workspace_root.join(".cuedeck/cards").join(format!("{}.md", id));
+    if target.exists() {
+        return Err(CueError::ValidationError(format!(
+            "Cannot restore {}: a card with that ID already exists",
+            id
+        )));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&source, &target)?;
+    let placed = settle_card(workspace_root, &target)?;
+
+    record_event_lossy(workspace_root, id, "restored", Vec::new());
+
+    let status = parse_file(&placed)?
+        .frontmatter
+        .map(|m| m.status)
+        .unwrap_or_else(|| DEFAULT_RESTORED_STATUS.to_string());
+    Ok(RestoreReport {
+        id: id.to_string(),
+        source: RestoreSource::Trash,
+        status,
+    })
+}
+
+/// Permanently delete archived cards archived more than `older_than` ago
+///
+/// The archive time is the card's `updated` timestamp (set when it was
+/// archived), falling back to the file's modification time. Returns the IDs
+/// that were (or, in a dry run, would be) purged.
+pub fn purge_archived(
+    workspace_root: &Path,
+    older_than: chrono::Duration,
+    dry_run: bool,
+) -> Result<Vec<String>> {
+    let cutoff = chrono::Utc::now() - older_than;
+    let dir = archive_dir(workspace_root);
+
+    let mut purged = Vec::new();
+    for id in list_archived_ids(workspace_root)? {
+        let path = dir.join(format!("{}.md", id));
+        let Some(archived_at) = archived_at(&path) else {
+            tracing::warn!("Skipping {}: cannot tell when it was archived", id);
+            continue;
+        };
+        if archived_at >= cutoff {
+            continue;
+        }
+
+        if !dry_run {
+            fs::remove_file(&path)?;
+            record_event_lossy(workspace_root, &id, "purged", Vec::new());
+        }
+        purged.push(id);
+    }
+    Ok(purged)
+}
+
+fn archived_at(path: &Path) -> Option<chrono::DateTime<chrono::Utc>> {
+    let updated = parse_file(path)
+        .ok()
+        .and_then(|doc| doc.frontmatter?.updated)
+        .and_then(|u| chrono::DateTime::parse_from_rfc3339(&u).ok())
+        .map(|t| t.with_timezone(&chrono::Utc));
+
+    updated.or_else(|| {
+        let mtime = fs::metadata(path).ok()?.modified().ok()?;
+        Some(chrono::DateTime::<chrono::Utc>::from(mtime))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn workspace() -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        cards
+            .child("base11.md")
+            .write_str("---\ntitle: Base\nstatus: active\n---\n")
+            .unwrap();
+        temp
+    }
+
+    fn set_status(temp: &assert_fs::TempDir, id: &str, status: &str) {
+        let mut updates = serde_json::Map::new();
+        updates.insert("status".to_string(), serde_json::json!(status));
+        crate::tasks::update_task(temp.path(), id, updates).unwrap();
+    }
+
+    #[test]
+    fn test_archive_and_restore_round_trip() {
+        let temp = workspace();
+        set_status(&temp, "base11", "archived");
+
+        assert!(!temp.path().join(".cuedeck/cards/base11.md").exists());
+        assert!(temp.path().join(".cuedeck/archive/base11.md").exists());
+        assert!(card_exists(temp.path(), "base11"));
+        assert!(crate::tasks::list_tasks(temp.path(), None, None).unwrap().is_empty());
+        assert_eq!(
+            crate::tasks::list_tasks(temp.path(), Some("archived"), None).unwrap().len(),
+            1
+        );
+
+        let report = restore_task(temp.path(), "base11").unwrap();
+        assert_eq!(report.source, RestoreSource::Archive);
+        assert_eq!(report.status, "active");
+        assert!(temp.path().join(".cuedeck/cards/base11.md").exists());
+        assert!(list_archived_ids(temp.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_from_trash() {
+        let temp = workspace();
+        crate::trash::delete_task(temp.path(), "base11", &Default::default()).unwrap();
+
+        let report = restore_task(temp.path(), "base11").unwrap();
+        assert_eq!(report.source, RestoreSource::Trash);
+        assert!(temp.path().join(".cuedeck/cards/base11.md").exists());
+
+        assert!(matches!(
+            restore_task(temp.path(), "nope11"),
+            Err(CueError::FileNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_purge_only_old_archives() {
+        let temp = workspace();
+        temp.child(".cuedeck/archive/old111.md")
+            .write_str("---\ntitle: Old\nstatus: archived\nupdated: 2020-01-01T00:00:00Z\n---\n")
+            .unwrap();
+        set_status(&temp, "base11", "archived");
+
+        let preview = purge_archived(temp.path(), chrono::Duration::days(90), true).unwrap();
+        assert_eq!(preview, vec!["old111".to_string()]);
+        assert!(temp.path().join(".cuedeck/archive/old111.md").exists());
+
+        purge_archived(temp.path(), chrono::Duration::days(90), false).unwrap();
+        assert_eq!(list_archived_ids(temp.path()).unwrap(), vec!["base11".to_string()]);
+    }
+}
@@ -0,0 +1,244 @@
+//! Git branch workflow for cards
+//!
+//! `start_branch` creates (or switches to) a branch named from the
+//! `[git] branch_template`, moves the card to `active` and records the branch
+//! in its `branch` field. `current_card` goes the other way, finding the card
+//! for the checked-out branch so commands can default to it.
+
+use crate::commits::git;
+use crate::task_filters::TaskFilters;
+use crate::tasks::{list_tasks_filtered, update_task};
+use cue_common::{CueError, Result};
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+/// Longest slug used in branch names
+const MAX_SLUG_LEN: usize = 40;
+
+/// Outcome of `start_branch`
+#[derive(Debug, Clone, Serialize)]
+pub struct BranchStart {
+    pub id: String,
+    pub branch: String,
+    /// False if the branch already existed and was only checked out
+    pub created: bool,
+}
+
+/// Lowercase a title into dash-separated words, e.g. `Fix Login!` -> `fix-login`
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.len() > MAX_SLUG_LEN {
+        slug.truncate(MAX_SLUG_LEN);
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Branch name for a card from a template with `{{id}}` and `{{slug}}`
+pub fn branch_name(template: &str, id: &str, title: &str) -> String {
+    let name = template
+        .replace("{{id}}", id)
+        .replace("{{slug}}", &slugify(title));
+    // An empty slug would leave a dangling separator
+    name.trim_end_matches(['-', '/', '_']).to_string()
+}
+
+/// Run git, turning a failure into an error carrying git's message
+fn git_checked(workspace_root: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workspace_root)
+        .args(args)
+        .output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(CueError::ValidationError(format!(
+        "git {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr).trim()
+    )))
+}
+
+fn current_branch(workspace_root: &Path) -> Result<String> {
+    git(workspace_root, &["rev-parse", "--abbrev-ref", "HEAD"])?
+        .map(|b| b.trim().to_string())
+        .filter(|b| b != "HEAD")
+        .ok_or_else(|| {
+            CueError::ValidationError(
+                "Not on a git branch (no repository, or detached HEAD)".to_string(),
+            )
+        })
+}
+
+/// Create and check out the card's branch, then mark the card active
+///
+/// An existing branch of the same name is checked out instead of created.
+/// The move to `active` is checked against the workflow first, so a card that
+/// cannot start leaves the repository untouched.
+pub fn start_branch(workspace_root: &Path, id: &str) -> Result<BranchStart> {
+    let id = crate::card_ids::resolve_task_id(workspace_root, id)?;
+    let meta = crate::parse_file(&crate::archive::card_path(workspace_root, &id))?
+        .frontmatter
+        .ok_or_else(|| {
+            CueError::ValidationError(format!("Card {} has no readable frontmatter", id))
+        })?;
+    if git(workspace_root, &["rev-parse", "--is-inside-work-tree"])?.is_none() {
+        return Err(CueError::ValidationError(format!(
+            "{} is not inside a git repository",
+            workspace_root.display()
+        )));
+    }
+
+    let config = cue_config::Config::load(workspace_root)?;
+    config.workflow.check_transition(&meta.status, "active")?;
+    let branch = branch_name(&config.git.branch_template, &id, &meta.title);
+    git_checked(workspace_root, &["check-ref-format", "--branch", &branch])?;
+
+    let exists = git(
+        workspace_root,
+        &["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", branch)],
+    )?
+    .is_some();
+    if exists {
+        git_checked(workspace_root, &["checkout", &branch])?;
+    } else {
+        git_checked(workspace_root, &["checkout", "-b", &branch])?;
+    }
+
+    let mut updates = serde_json::Map::new();
+    if meta.status != "active" {
+        updates.insert("status".to_string(), "active".into());
+    }
+    updates.insert("branch".to_string(), branch.clone().into());
+    update_task(workspace_root, &id, updates)?;
+
+    Ok(BranchStart {
+        id,
+        branch,
+        created: !exists,
+    })
+}
+
+/// The card for the checked-out branch, if any
+///
+/// A card whose `branch` field names the branch wins; otherwise the branch
+/// name (after any `feature/`-style prefix) must start with a card ID
+/// followed by a non-alphanumeric character or nothing.
+pub fn current_card(workspace_root: &Path) -> Result<Option<String>> {
+    let branch = current_branch(workspace_root)?;
+    let cards: Vec<(String, Option<String>)> = list_tasks_filtered(workspace_root, &TaskFilters::default())?
+        .into_iter()
+        .filter_map(|doc| {
+            let id = doc.path.file_stem()?.to_str()?.to_string();
+            Some((id, doc.frontmatter.and_then(|m| m.branch)))
+        })
+        .collect();
+
+    if let Some((id, _)) = cards.iter().find(|(_, b)| b.as_deref() == Some(branch.as_str())) {
+        return Ok(Some(id.clone()));
+    }
+
+    let leaf = branch.rsplit('/').next().unwrap_or(&branch);
+    Ok(cards
+        .into_iter()
+        .map(|(id, _)| id)
+        .filter(|id| {
+            leaf.strip_prefix(id.as_str())
+                .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_alphanumeric()))
+        })
+        .max_by_key(|id| id.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn test_branch_name() {
+        assert_eq!(branch_name("{{id}}-{{slug}}", "abc123", "Fix Login (SSO)!"), "abc123-fix-login-sso");
+        assert_eq!(branch_name("feature/{{id}}", "abc123", "x"), "feature/abc123");
+        assert_eq!(branch_name("{{id}}-{{slug}}", "abc123", "!!!"), "abc123");
+        assert!(slugify(&"word ".repeat(20)).len() <= MAX_SLUG_LEN);
+    }
+
+    fn run(dir: &Path, args: &[&str]) {
+        let out = Command::new("git").arg("-C").arg(dir).args(args).output().unwrap();
+        assert!(out.status.success(), "git {:?} failed", args);
+    }
+
+    fn repo() -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        run(temp.path(), &["init", "-q", "-b", "main"]);
+        run(temp.path(), &["config", "user.name", "Dana"]);
+        run(temp.path(), &["config", "user.email", "dana@example.com"]);
+        run(temp.path(), &["config", "commit.gpgsign", "false"]);
+        run(temp.path(), &["commit", "--allow-empty", "-q", "-m", "init"]);
+        temp
+    }
+
+    #[test]
+    fn test_start_branch_and_current_card() {
+        let temp = repo();
+        let run = |args: &[&str]| run(temp.path(), args);
+        temp.child(".cuedeck/cards/abc123.md")
+            .write_str("---\ntitle: Fix login\nstatus: todo\n---\n")
+            .unwrap();
+
+        assert_eq!(current_card(temp.path()).unwrap(), None);
+
+        let started = start_branch(temp.path(), "abc123").unwrap();
+        assert_eq!(started.branch, "abc123-fix-login");
+        assert!(started.created);
+        assert_eq!(current_card(temp.path()).unwrap().as_deref(), Some("abc123"));
+
+        let meta = crate::parse_file(temp.child(".cuedeck/cards/abc123.md").path())
+            .unwrap()
+            .frontmatter
+            .unwrap();
+        assert_eq!(meta.status, "active");
+        assert_eq!(meta.branch.as_deref(), Some("abc123-fix-login"));
+
+        // Branch names alone are enough, e.g. one created by hand
+        run(&["checkout", "-q", "-b", "feature/abc123_retry"]);
+        assert_eq!(current_card(temp.path()).unwrap().as_deref(), Some("abc123"));
+
+        run(&["checkout", "-q", "main"]);
+        assert!(!start_branch(temp.path(), "abc123").unwrap().created);
+    }
+
+    #[test]
+    fn test_start_branch_checks_workflow_before_git() {
+        let temp = repo();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[workflow]\nstatuses = [\"todo\", \"active\", \"done\"]\n\n[workflow.transitions]\ntodo = [\"active\"]\n")
+            .unwrap();
+        temp.child(".cuedeck/cards/abc123.md")
+            .write_str("---\ntitle: Fix login\nstatus: done\n---\n")
+            .unwrap();
+
+        let err = start_branch(temp.path(), "abc123").unwrap_err();
+        assert!(err.to_string().contains("'done' is terminal"), "got {}", err);
+
+        // Neither the branch nor the card changed
+        assert_eq!(current_branch(temp.path()).unwrap(), "main");
+        assert!(git(temp.path(), &["rev-parse", "--verify", "--quiet", "refs/heads/abc123-fix-login"])
+            .unwrap()
+            .is_none());
+        let meta = crate::parse_file(temp.child(".cuedeck/cards/abc123.md").path())
+            .unwrap()
+            .frontmatter
+            .unwrap();
+        assert_eq!(meta.status, "done");
+        assert!(meta.branch.is_none());
+    }
+}
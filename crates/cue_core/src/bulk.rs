@@ -0,0 +1,197 @@
+//! Bulk card updates
+//!
+//! Applies one `update_task` update map to every card selected by
+//! `TaskFilters`. All updates are computed before anything is written; if a
+//! write fails, cards already written are restored so the batch is applied
+//! all-or-nothing.
+
+use crate::activity::{record_event_lossy, FieldChange};
+use crate::archive::settle_card;
+use crate::task_filters::TaskFilters;
+use crate::tasks::{list_tasks_filtered, prepare_update, PendingUpdate};
+use cue_common::{CueError, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// Changes made (or, in a dry run, that would be made) to one card
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkUpdateEntry {
+    pub id: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Outcome of a bulk update
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkUpdateResult {
+    pub dry_run: bool,
+    /// Cards matched by the filters, with their field changes
+    pub cards: Vec<BulkUpdateEntry>,
+}
+
+/// Whether the filters select anything narrower than "every card"
+fn is_unfiltered(filters: &TaskFilters) -> bool {
+    filters.status.is_none()
+        && filters.assignee.is_none()
+        && filters.tags.is_none()
+        && filters.priority.is_none()
+        && filters.parent.is_none()
+        && filters.milestone.is_none()
+        && filters.due.is_none()
+        && filters.created.is_none()
+        && filters.updated.is_none()
+        && filters.query.as_ref().is_none_or(|q| q.is_all())
+}
+
+/// Write every pending update, restoring earlier writes if one fails
+pub(crate) fn write_all_or_nothing<W>(pending: &[PendingUpdate], mut write: W) -> Result<()>
+where
+    W: FnMut(&Path, &str) -> std::io::Result<()>,
+{
+    for (i, update) in pending.iter().enumerate() {
+        if let Err(e) = write(&update.path, &update.new_content) {
+            for done in pending[..i].iter().rev() {
+                if let Err(restore) = write(&done.path, &done.old_content) {
+                    tracing::error!("Failed to roll back {}: {}", done.id, restore);
+                }
+            }
+            return Err(CueError::IoError(std::io::Error::new(
+                e.kind(),
+                format!(
+                    "Write failed on {} ({}); {} card(s) rolled back",
+                    update.id, e, i
+                ),
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Apply `updates` to every card matching `filters`
+///
+/// Validation errors (bad parent, unreadable frontmatter) on any card abort
+/// the batch before anything is written. An empty filter is rejected so a
+/// typo cannot rewrite the whole deck.
+pub fn bulk_update_tasks(
+    workspace_root: &Path,
+    filters: &TaskFilters,
+    updates: &serde_json::Map<String, serde_json::Value>,
+    dry_run: bool,
+) -> Result<BulkUpdateResult> {
+    if is_unfiltered(filters) {
+        return Err(CueError::ValidationError(
+            "Bulk update needs a filter; refusing to update every card".to_string(),
+        ));
+    }
+    if updates.is_empty() {
+        return Err(CueError::ValidationError(
+            "Bulk update needs at least one field to set".to_string(),
+        ));
+    }
+
+    let pending = list_tasks_filtered(workspace_root, filters)?
+        .iter()
+        .filter_map(|doc| doc.path.file_stem().and_then(|s| s.to_str()).map(String::from))
+        .map(|id| prepare_update(workspace_root, &id, updates))
+        .collect::<Result<Vec<_>>>()?;
+
+    if !dry_run {
+        write_all_or_nothing(&pending, crate::write_atomic)?;
+        for update in &pending {
+            if let Err(e) = settle_card(workspace_root, &update.path) {
+                tracing::warn!("Failed to move {} to match its status: {}", update.id, e);
+            }
+            record_event_lossy(workspace_root, &update.id, update.action(), update.changes.clone());
+        }
+    }
+
+    Ok(BulkUpdateResult {
+        dry_run,
+        cards: pending
+            .into_iter()
+            .map(|p| BulkUpdateEntry {
+                id: p.id,
+                changes: p.changes,
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::Query;
+    use assert_fs::prelude::*;
+
+    fn workspace() -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        for (id, priority) in [("aaa111", "low"), ("bbb222", "low"), ("ccc333", "high")] {
+            cards
+                .child(format!("{}.md", id))
+                .write_str(&format!("---\ntitle: {}\nstatus: todo\npriority: {}\n---\n# Body\n", id, priority))
+                .unwrap();
+        }
+        temp
+    }
+
+    fn low_priority() -> TaskFilters {
+        TaskFilters {
+            query: Some(Query::parse("priority:low").unwrap()),
+            ..Default::default()
+        }
+    }
+
+    fn set_assignee() -> serde_json::Map<String, serde_json::Value> {
+        let mut updates = serde_json::Map::new();
+        updates.insert("assignee".to_string(), serde_json::Value::from("dana"));
+        updates
+    }
+
+    #[test]
+    fn test_bulk_update_dry_run_and_apply() {
+        let temp = workspace();
+
+        let preview = bulk_update_tasks(temp.path(), &low_priority(), &set_assignee(), true).unwrap();
+        assert_eq!(preview.cards.len(), 2);
+        assert_eq!(preview.cards[0].changes[0].field, "assignee");
+        let untouched = crate::parse_file(&temp.path().join(".cuedeck/cards/aaa111.md")).unwrap();
+        assert_eq!(untouched.frontmatter.unwrap().assignee, None);
+
+        bulk_update_tasks(temp.path(), &low_priority(), &set_assignee(), false).unwrap();
+        let filters = TaskFilters {
+            assignee: Some("dana".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(list_tasks_filtered(temp.path(), &filters).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_bulk_update_rejects_empty_filter() {
+        let temp = workspace();
+        let result = bulk_update_tasks(temp.path(), &TaskFilters::default(), &set_assignee(), false);
+        assert!(matches!(result, Err(CueError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_failed_write_rolls_back() {
+        let temp = workspace();
+        let pending: Vec<PendingUpdate> = ["aaa111", "bbb222"]
+            .iter()
+            .map(|id| prepare_update(temp.path(), id, &set_assignee()).unwrap())
+            .collect();
+
+        let mut calls = 0;
+        let result = write_all_or_nothing(&pending, |path, content| {
+            calls += 1;
+            if calls == 2 {
+                return Err(std::io::Error::other("disk full"));
+            }
+            std::fs::write(path, content)
+        });
+
+        assert!(result.unwrap_err().to_string().contains("rolled back"));
+        let restored = std::fs::read_to_string(&pending[0].path).unwrap();
+        assert_eq!(restored, pending[0].old_content);
+    }
+}
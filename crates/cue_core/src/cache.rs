@@ -159,11 +159,32 @@ impl DocumentCache {
         }
     }
 
+    /// Move an entry to a new path after the file was renamed
+    ///
+    /// Returns whether an entry existed. The content hash and mtime are kept,
+    /// so the renamed file is still a cache hit.
+    pub fn rename(&mut self, from: &Path, to: &Path) -> bool {
+        match self.entries.remove(from) {
+            Some(mut entry) => {
+                entry.document.path = to.to_path_buf();
+                self.entries.insert(to.to_path_buf(), entry);
+                tracing::debug!("Moved cache entry {:?} -> {:?}", from, to);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Get a cached document if it exists and is valid
     pub fn get(&self, path: &Path) -> Option<&Document> {
         self.entries.get(path).map(|entry| &entry.document)
     }
 
+    /// Get the cache entry for a path, including its content hash and mtime
+    pub fn entry(&self, path: &Path) -> Option<&CachedDocument> {
+        self.entries.get(path)
+    }
+
     /// Clear all cache entries
     pub fn clear(&mut self) {
         self.entries.clear();
@@ -255,4 +276,23 @@ mod tests {
         let _doc2 = cache.get_or_parse(file.path()).unwrap();
         assert_eq!(cache.stats().misses, 2);
     }
+
+    #[test]
+    fn test_cache_rename_keeps_entry() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let file = temp.child("old.md");
+        file.write_str("# Test").unwrap();
+
+        let mut cache = DocumentCache::new(temp.path()).unwrap();
+        cache.get_or_parse(file.path()).unwrap();
+
+        let new_path = temp.path().join("new.md");
+        std::fs::rename(file.path(), &new_path).unwrap();
+        assert!(cache.rename(file.path(), &new_path));
+        assert!(!cache.rename(file.path(), &new_path));
+
+        let doc = cache.get_or_parse(&new_path).unwrap();
+        assert_eq!(doc.path, new_path);
+        assert_eq!(cache.stats().hits, 1);
+    }
 }
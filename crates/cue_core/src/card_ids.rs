@@ -0,0 +1,199 @@
+//! Card ID allocation and lookup
+//!
+//! IDs are either 6 random lowercase alphanumerics (the default) or
+//! sequential and prefixed (`CUE-142`), chosen by `[ids]` in config.toml.
+//! Allocation only proposes candidates; the card file itself is created with
+//! `create_new` so two writers can never claim the same ID.
+
+use cue_common::{CueError, Result};
+use cue_config::IdConfig;
+use std::path::Path;
+
+/// How many candidates to try before giving up on allocation
+pub const MAX_ALLOCATION_ATTEMPTS: usize = 16;
+
+/// Whether a string is usable as a card ID (and therefore a filename)
+pub fn is_valid_task_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && !id.starts_with('-')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// IDs of all cards in the workspace, active and archived, sorted
+pub fn list_task_ids(workspace_root: &Path) -> Result<Vec<String>> {
+    let cards_dir = workspace_root.join(".cuedeck/cards");
+    let mut ids = crate::archive::list_archived_ids(workspace_root)?;
+
+    if cards_dir.exists() {
+        ids.extend(
+            std::fs::read_dir(&cards_dir)?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|e| e == "md"))
+                .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(String::from)),
+        );
+    }
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+fn random_id() -> String {
+    use rand::Rng;
+
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(6)
+        .map(char::from)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Next sequential number for `prefix`, one past the highest in use
+///
+/// Trashed cards count as in use so a deleted card can always be restored
+/// under its old ID.
+fn next_sequence(workspace_root: &Path, prefix: &str) -> Result<u64> {
+    let pattern = regex::Regex::new(&format!(r"(?i)^{}-(\d+)$", regex::escape(prefix)))
+        .map_err(|e| CueError::ConfigError(format!("Invalid ID prefix '{}': {}", prefix, e)))?;
+
+    let mut ids = list_task_ids(workspace_root)?;
+    ids.extend(crate::trash::list_trashed_ids(workspace_root)?);
+
+    let highest = ids
+        .iter()
+        .filter_map(|id| pattern.captures(id)?.get(1)?.as_str().parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+
+    Ok(highest + 1)
+}
+
+/// Propose an ID for a new card
+///
+/// Sequential candidates are recomputed from the cards on disk, so a number
+/// claimed by a concurrent writer is skipped on the next attempt.
+pub fn next_candidate_id(workspace_root: &Path, config: &IdConfig) -> Result<String> {
+    match config.scheme.as_str() {
+        "random" => Ok(random_id()),
+        "sequential" => {
+            if config.prefix.is_empty() || !is_valid_task_id(&config.prefix) {
+                return Err(CueError::ConfigError(format!(
+                    "Invalid ID prefix '{}'",
+                    config.prefix
+                )));
+            }
+            let number = next_sequence(workspace_root, &config.prefix)?;
+            Ok(format!("{}-{}", config.prefix, number))
+        }
+        other => Err(CueError::ConfigError(format!(
+            "Unknown ID scheme '{}'. Expected: random or sequential",
+            other
+        ))),
+    }
+}
+
+/// Resolve a full ID or unambiguous prefix to a card ID
+///
+/// An exact match always wins. Otherwise the input is matched
+/// case-insensitively against the start of every card ID.
+pub fn resolve_task_id(workspace_root: &Path, input: &str) -> Result<String> {
+    if !is_valid_task_id(input) {
+        return Err(CueError::ValidationError(format!(
+            "Invalid card ID: '{}'",
+            input
+        )));
+    }
+
+    let ids = list_task_ids(workspace_root)?;
+    if ids.iter().any(|id| id == input) {
+        return Ok(input.to_string());
+    }
+
+    let needle = input.to_lowercase();
+    let matches: Vec<&String> = ids
+        .iter()
+        .filter(|id| id.to_lowercase().starts_with(&needle))
+        .collect();
+
+    match matches.as_slice() {
+        [only] => Ok((*only).clone()),
+        [] => Err(CueError::FileNotFound {
+            path: workspace_root
+                .join(".cuedeck/cards")
+                .join(format!("{}.md", input))
+                .to_string_lossy()
+                .to_string(),
+        }),
+        many => Err(CueError::ValidationError(format!(
+            "Ambiguous card ID '{}': matches {}",
+            input,
+            many.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn workspace(ids: &[&str]) -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        for id in ids {
+            cards.child(format!("{}.md", id)).write_str("---\ntitle: T\n---\n").unwrap();
+        }
+        temp
+    }
+
+    #[test]
+    fn test_resolve_task_id_prefix() {
+        let temp = workspace(&["abc123", "abd456", "xyz789"]);
+
+        assert_eq!(resolve_task_id(temp.path(), "abc123").unwrap(), "abc123");
+        assert_eq!(resolve_task_id(temp.path(), "xy").unwrap(), "xyz789");
+        assert_eq!(resolve_task_id(temp.path(), "ABC").unwrap(), "abc123");
+
+        let err = resolve_task_id(temp.path(), "ab").unwrap_err().to_string();
+        assert!(err.contains("abc123, abd456"));
+
+        assert!(matches!(
+            resolve_task_id(temp.path(), "qqq"),
+            Err(CueError::FileNotFound { .. })
+        ));
+        assert!(resolve_task_id(temp.path(), "../etc").is_err());
+    }
+
+    #[test]
+    fn test_sequential_ids_continue_from_highest() {
+        let temp = workspace(&["CUE-7", "CUE-41", "abc123"]);
+        let config = IdConfig {
+            scheme: "sequential".to_string(),
+            prefix: "CUE".to_string(),
+        };
+
+        assert_eq!(next_candidate_id(temp.path(), &config).unwrap(), "CUE-42");
+
+        // A trashed card keeps its number reserved
+        temp.child(".cuedeck/trash/CUE-42.md").write_str("---\ntitle: T\n---\n").unwrap();
+        assert_eq!(next_candidate_id(temp.path(), &config).unwrap(), "CUE-43");
+    }
+
+    #[test]
+    fn test_unknown_scheme_is_config_error() {
+        let temp = workspace(&[]);
+        let config = IdConfig {
+            scheme: "uuid".to_string(),
+            prefix: "CUE".to_string(),
+        };
+        assert!(matches!(
+            next_candidate_id(temp.path(), &config),
+            Err(CueError::ConfigError(_))
+        ));
+    }
+}
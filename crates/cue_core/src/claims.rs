@@ -0,0 +1,219 @@
+//! Task claims for multi-agent coordination
+//!
+//! A claim is a lease on a card: `claimed_by` and `claim_expires` in its
+//! frontmatter. While the lease is live, other claimants get
+//! `CueError::Locked`; once it expires anyone may claim the card. Claims are
+//! written under the card's lock with a hash precondition, so two agents
+//! racing for the same card cannot both win.
+
+use crate::card_ids::resolve_task_id;
+use crate::tasks::update_task_checked;
+use chrono::{DateTime, Utc};
+use cue_common::{CardMetadata, CueError, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Lease length when the caller does not give one
+pub const DEFAULT_CLAIM_TTL_SECS: i64 = 30 * 60;
+
+/// Attempts before giving up on a card that keeps changing underneath us
+const MAX_CLAIM_ATTEMPTS: usize = 5;
+
+/// Outcome of `claim_task`
+#[derive(Debug, Clone, Serialize)]
+pub struct Claim {
+    pub id: String,
+    pub claimed_by: String,
+    /// RFC 3339 lease expiry
+    pub expires: String,
+    /// Whether the claimant already held the card and the lease was extended
+    pub renewed: bool,
+}
+
+/// The live claim on a card, if any: `(claimant, expiry)`
+///
+/// Claims with a missing or unparsable expiry count as expired.
+pub fn active_claim(meta: &CardMetadata, now: DateTime<Utc>) -> Option<(&str, DateTime<Utc>)> {
+    let holder = meta.claimed_by.as_deref()?;
+    let expires = DateTime::parse_from_rfc3339(meta.claim_expires.as_deref()?)
+        .ok()?
+        .with_timezone(&Utc);
+    (expires > now).then_some((holder, expires))
+}
+
+/// Claim a card for `claimant` for `ttl`
+///
+/// Renews the lease if `claimant` already holds it and takes over expired
+/// claims. Fails with `CueError::Locked` while someone else holds a live claim.
+pub fn claim_task(
+    workspace_root: &Path,
+    id: &str,
+    claimant: &str,
+    ttl: chrono::Duration,
+) -> Result<Claim> {
+    if claimant.trim().is_empty() {
+        return Err(CueError::ValidationError("Claimant must not be empty".to_string()));
+    }
+    if ttl <= chrono::Duration::zero() {
+        return Err(CueError::ValidationError("Claim TTL must be positive".to_string()));
+    }
+    let id = resolve_task_id(workspace_root, id)?;
+
+    with_retries(workspace_root, &id, |meta, now| {
+        let renewed = match active_claim(meta, now) {
+            Some((holder, _)) if holder == claimant => true,
+            Some((holder, until)) => return Err(locked(holder, until)),
+            None => false,
+        };
+
+        let expires = (now + ttl).to_rfc3339();
+        let mut updates = serde_json::Map::new();
+        updates.insert("claimed_by".to_string(), claimant.into());
+        updates.insert("claim_expires".to_string(), expires.clone().into());
+
+        Ok((
+            updates,
+            Claim {
+                id: id.clone(),
+                claimed_by: claimant.to_string(),
+                expires,
+                renewed,
+            },
+        ))
+    })
+}
+
+/// Release `claimant`'s claim on a card
+///
+/// Releasing an unclaimed or expired card is a no-op. A live claim held by
+/// someone else is only released with `force`.
+pub fn release_task(workspace_root: &Path, id: &str, claimant: &str, force: bool) -> Result<()> {
+    let id = resolve_task_id(workspace_root, id)?;
+
+    with_retries(workspace_root, &id, |meta, now| {
+        if let Some((holder, until)) = active_claim(meta, now) {
+            if holder != claimant && !force {
+                return Err(locked(holder, until));
+            }
+        }
+
+        let mut updates = serde_json::Map::new();
+        if meta.claimed_by.is_some() || meta.claim_expires.is_some() {
+            updates.insert("claimed_by".to_string(), serde_json::Value::Null);
+            updates.insert("claim_expires".to_string(), serde_json::Value::Null);
+        }
+        Ok((updates, ()))
+    })
+}
+
+/// Read the card, decide the update, and write it if the card is unchanged
+///
+/// `decide` returns the frontmatter updates (empty for none) and the result.
+/// A concurrent write makes the hash check fail; the card is then re-read and
+/// `decide` runs again against the new state.
+fn with_retries<T, F>(workspace_root: &Path, id: &str, mut decide: F) -> Result<T>
+where
+    F: FnMut(&CardMetadata, DateTime<Utc>) -> Result<(serde_json::Map<String, serde_json::Value>, T)>,
+{
+    let path = crate::archive::card_path(workspace_root, id);
+    for _ in 0..MAX_CLAIM_ATTEMPTS {
+        let content = fs::read_to_string(&path)?;
+        let meta = crate::parse_file(&path)?.frontmatter.ok_or_else(|| {
+            CueError::ValidationError(format!("Card {} has no readable frontmatter", id))
+        })?;
+
+        let (updates, result) = decide(&meta, Utc::now())?;
+        if updates.is_empty() {
+            return Ok(result);
+        }
+
+        let expected = crate::content_hash(&content);
+        match update_task_checked(workspace_root, id, updates, Some(&expected)) {
+            Ok(_) => return Ok(result),
+            Err(CueError::Conflict { .. }) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(CueError::ValidationError(format!(
+        "Card {} kept changing; gave up after {} attempts",
+        id, MAX_CLAIM_ATTEMPTS
+    )))
+}
+
+fn locked(holder: &str, until: DateTime<Utc>) -> CueError {
+    CueError::Locked {
+        holder: holder.to_string(),
+        until: until.to_rfc3339(),
+    }
+}
+
+/// Parse a lease length: `90s`, `30m`, `2h` or `1d`
+pub fn parse_ttl(input: &str) -> Option<chrono::Duration> {
+    let unit_at = input.find(|c: char| !c.is_ascii_digit())?;
+    let (num, unit) = input.split_at(unit_at);
+    let num: i64 = num.parse().ok()?;
+
+    match unit {
+        "s" => Some(chrono::Duration::seconds(num)),
+        "m" => Some(chrono::Duration::minutes(num)),
+        "h" => Some(chrono::Duration::hours(num)),
+        "d" => Some(chrono::Duration::days(num)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn workspace() -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child(".cuedeck/cards/base11.md")
+            .write_str("---\ntitle: Base\nstatus: todo\n---\n")
+            .unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_claim_blocks_other_claimants_until_released() {
+        let temp = workspace();
+        let ttl = chrono::Duration::minutes(30);
+
+        let claim = claim_task(temp.path(), "base11", "agent-a", ttl).unwrap();
+        assert!(!claim.renewed);
+        assert!(claim_task(temp.path(), "base11", "agent-a", ttl).unwrap().renewed);
+
+        let err = claim_task(temp.path(), "base11", "agent-b", ttl).unwrap_err();
+        assert!(matches!(err, CueError::Locked { ref holder, .. } if holder == "agent-a"));
+        assert!(matches!(
+            release_task(temp.path(), "base11", "agent-b", false),
+            Err(CueError::Locked { .. })
+        ));
+
+        release_task(temp.path(), "base11", "agent-a", false).unwrap();
+        claim_task(temp.path(), "base11", "agent-b", ttl).unwrap();
+    }
+
+    #[test]
+    fn test_expired_claims_are_reclaimed() {
+        let temp = workspace();
+        temp.child(".cuedeck/cards/base11.md")
+            .write_str("---\ntitle: Base\nclaimed_by: agent-a\nclaim_expires: 2020-01-01T00:00:00Z\n---\n")
+            .unwrap();
+
+        let claim = claim_task(temp.path(), "base11", "agent-b", chrono::Duration::minutes(5)).unwrap();
+        assert_eq!(claim.claimed_by, "agent-b");
+        assert!(!claim.renewed);
+    }
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("90s"), Some(chrono::Duration::seconds(90)));
+        assert_eq!(parse_ttl("2h"), Some(chrono::Duration::hours(2)));
+        assert_eq!(parse_ttl("30"), None);
+        assert_eq!(parse_ttl("1w"), None);
+    }
+}
@@ -0,0 +1,437 @@
+//! Link commits in local git history to cards
+//!
+//! Commit messages that mention a card ID (`fix login (abc123)`) are linked to
+//! that card. Scanning shells out to the local `git` binary and never touches
+//! the network. Links and the last scanned commit are kept in
+//! `.cuedeck/cache/commits.json`, so each scan only reads commits made since
+//! the previous one; if history was rewritten the index is rebuilt.
+//!
+//! With `[git] close_on_trailer = true`, a `Closes: <id>` trailer in a commit
+//! message moves the card to `done` when `scan_commits` is asked to apply
+//! trailers. Read paths such as `task_commits` only index commits; their
+//! trailers stay pending until the next applying scan. Commits whose trailers
+//! were applied are remembered across rebuilds, so a card reopened after its
+//! closing commit stays open.
+
+use crate::task_filters::TaskFilters;
+use crate::tasks::{list_tasks_filtered, update_task};
+use cue_common::{CueError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Location of the commit index, relative to the workspace root
+pub const COMMIT_INDEX: &str = ".cuedeck/cache/commits.json";
+
+/// Separators in the `git log` format: fields, then records
+const FIELD_SEP: char = '\u{1f}';
+const RECORD_SEP: char = '\u{1e}';
+
+/// A commit that mentions a card
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LinkedCommit {
+    pub sha: String,
+    pub author: String,
+    /// Author date (RFC 3339)
+    pub date: String,
+    /// First line of the message
+    pub summary: String,
+    /// Whether the commit has a `Closes` trailer for the card
+    #[serde(default)]
+    pub closes: bool,
+}
+
+/// Scanned commits, by card
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommitIndex {
+    /// Newest commit covered by `links`
+    #[serde(default)]
+    pub last_scanned: Option<String>,
+    /// Card ID -> commits mentioning it, oldest first
+    #[serde(default)]
+    pub links: BTreeMap<String, Vec<LinkedCommit>>,
+    /// Commits whose `Closes` trailers were handled (kept when `links` is rebuilt)
+    #[serde(default)]
+    pub applied: BTreeSet<String>,
+}
+
+/// Result of `scan_commits`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScanReport {
+    /// Commits read in this scan
+    pub scanned: usize,
+    /// New commit-card links
+    pub linked: usize,
+    /// Cards moved to `done` by `Closes` trailers
+    pub closed: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+fn index_path(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(COMMIT_INDEX)
+}
+
+/// Load the commit index (empty if it has not been built yet)
+pub fn load_index(workspace_root: &Path) -> Result<CommitIndex> {
+    let path = index_path(workspace_root);
+    if !path.exists() {
+        return Ok(CommitIndex::default());
+    }
+    // A corrupt index is only a cache; rebuild it
+    Ok(serde_json::from_str(&fs::read_to_string(&path)?).unwrap_or_default())
+}
+
+fn save_index(workspace_root: &Path, index: &CommitIndex) -> Result<()> {
+    let path = index_path(workspace_root);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::write_atomic(&path, &serde_json::to_string_pretty(index)?)?;
+    Ok(())
+}
+
+/// Run git in the workspace; `None` if it exits unsuccessfully
+pub(crate) fn git(workspace_root: &Path, args: &[&str]) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workspace_root)
+        .args(args)
+        .output()?;
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+/// Read commits made since the last scan and link them to cards
+///
+/// With `apply_trailers` and `[git] close_on_trailer`, pending `Closes`
+/// trailers (including ones indexed by earlier read-only scans) move their
+/// cards to `done`. Without it cards are never modified.
+pub fn scan_commits(workspace_root: &Path, apply_trailers: bool) -> Result<ScanReport> {
+    if git(workspace_root, &["rev-parse", "--is-inside-work-tree"])?.is_none() {
+        return Err(CueError::ValidationError(format!(
+            "{} is not inside a git repository",
+            workspace_root.display()
+        )));
+    }
+
+    let mut report = ScanReport::default();
+    let mut index = load_index(workspace_root)?;
+
+    // No commits yet
+    let Some(head) = git(workspace_root, &["rev-parse", "HEAD"])? else {
+        return Ok(report);
+    };
+    let head = head.trim().to_string();
+    let config = cue_config::Config::load(workspace_root)?;
+    let apply_trailers = apply_trailers && config.git.close_on_trailer;
+    if index.last_scanned.as_deref() == Some(head.as_str()) {
+        if apply_trailers && apply_pending(workspace_root, &mut index, &config, &mut report) {
+            save_index(workspace_root, &index)?;
+        }
+        return Ok(report);
+    }
+
+    // Continue from the last scan unless history was rewritten since
+    let range = match &index.last_scanned {
+        Some(last)
+            if git(workspace_root, &["merge-base", "--is-ancestor", last, &head])?.is_some() =>
+        {
+            format!("{}..{}", last, head)
+        }
+        _ => {
+            index.links.clear();
+            head.clone()
+        }
+    };
+
+    let format = format!("--format=%H{0}%an{0}%aI{0}%B{1}", FIELD_SEP, RECORD_SEP);
+    let log = git(workspace_root, &["log", "--reverse", &format, &range])?.unwrap_or_default();
+
+    let card_ids = known_card_ids(workspace_root)?;
+    let closes_re = regex::Regex::new(r"(?im)^(?:closes|fixes|resolves):[ \t]*(.+)$")
+        .map_err(|e| CueError::ParseError(e.to_string()))?;
+
+    for record in log.split(RECORD_SEP) {
+        let mut fields = record.trim_start_matches('\n').splitn(4, FIELD_SEP);
+        let (Some(sha), Some(author), Some(date), Some(message)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        report.scanned += 1;
+
+        let closes: HashSet<&str> = closes_re
+            .captures_iter(trailer_block(message))
+            .flat_map(|c| c.get(1).map_or("", |m| m.as_str()).split([',', ' ']))
+            .map(|id| id.trim().trim_start_matches('#'))
+            .filter(|id| card_ids.contains(*id))
+            .collect();
+
+        let mentioned: Vec<&str> = {
+            let mut seen = HashSet::new();
+            message
+                .split(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                .filter(|token| card_ids.contains(*token) && seen.insert(*token))
+                .collect()
+        };
+
+        for id in mentioned {
+            let commits = index.links.entry(id.to_string()).or_default();
+            if commits.iter().any(|c| c.sha == sha) {
+                continue;
+            }
+            commits.push(LinkedCommit {
+                sha: sha.to_string(),
+                author: author.to_string(),
+                date: date.to_string(),
+                summary: message.lines().next().unwrap_or_default().to_string(),
+                closes: closes.contains(id),
+            });
+            report.linked += 1;
+        }
+
+        // Trailers seen while closing is disabled are never acted on later
+        if !config.git.close_on_trailer && !closes.is_empty() {
+            index.applied.insert(sha.to_string());
+        }
+    }
+
+    if apply_trailers {
+        apply_pending(workspace_root, &mut index, &config, &mut report);
+    }
+    index.last_scanned = Some(head);
+    save_index(workspace_root, &index)?;
+    Ok(report)
+}
+
+/// The final paragraph of a commit message, if it is not the subject
+fn trailer_block(message: &str) -> &str {
+    let message = message.trim_end();
+    match message.split_once("\n\n") {
+        Some((_, body)) => body.rsplit("\n\n").next().unwrap_or(body),
+        None => "",
+    }
+}
+
+/// Close cards for indexed `Closes` trailers that have not been handled yet
+///
+/// Returns whether any trailer was handled.
+fn apply_pending(
+    workspace_root: &Path,
+    index: &mut CommitIndex,
+    config: &cue_config::Config,
+    report: &mut ScanReport,
+) -> bool {
+    let mut pending: Vec<(&str, &LinkedCommit)> = index
+        .links
+        .iter()
+        .flat_map(|(id, commits)| commits.iter().map(move |c| (id.as_str(), c)))
+        .filter(|(_, c)| c.closes && !index.applied.contains(&c.sha))
+        .collect();
+    // Oldest commits first, as they were made
+    pending.sort_by(|a, b| a.1.date.cmp(&b.1.date));
+
+    let mut handled = Vec::new();
+    for (id, commit) in pending {
+        close_card(workspace_root, id, &commit.sha, &config.workflow, report);
+        handled.push(commit.sha.clone());
+    }
+    let changed = !handled.is_empty();
+    index.applied.extend(handled);
+    changed
+}
+
+fn close_card(
+    workspace_root: &Path,
+    id: &str,
+    sha: &str,
+    workflow: &cue_config::WorkflowConfig,
+    report: &mut ScanReport,
+) {
+    let status = crate::parse_file(&crate::archive::card_path(workspace_root, id))
+        .ok()
+        .and_then(|doc| doc.frontmatter)
+        .map(|meta| meta.status);
+    if status.as_deref().is_some_and(|s| workflow.is_terminal(s)) {
+        return;
+    }
+
+    let mut update = serde_json::Map::new();
+    update.insert("status".to_string(), "done".into());
+    match update_task(workspace_root, id, update) {
+        Ok(_) => report.closed.push(id.to_string()),
+        Err(e) => report.warnings.push(format!(
+            "{}: could not close from commit {}: {}",
+            id,
+            &sha[..sha.len().min(7)],
+            e
+        )),
+    }
+}
+
+fn known_card_ids(workspace_root: &Path) -> Result<HashSet<String>> {
+    let filters = TaskFilters {
+        include_archived: true,
+        ..Default::default()
+    };
+    Ok(list_tasks_filtered(workspace_root, &filters)?
+        .iter()
+        .filter_map(|doc| doc.path.file_stem()?.to_str().map(String::from))
+        .collect())
+}
+
+/// Commits mentioning a card, newest first, after bringing the index up to date
+///
+/// Never modifies cards: `Closes` trailers are left for `scan_commits`.
+pub fn task_commits(workspace_root: &Path, id: &str) -> Result<(Vec<LinkedCommit>, ScanReport)> {
+    let id = crate::card_ids::resolve_task_id(workspace_root, id)?;
+    let report = scan_commits(workspace_root, false)?;
+    let mut commits = load_index(workspace_root)?
+        .links
+        .remove(&id)
+        .unwrap_or_default();
+    commits.reverse();
+    Ok((commits, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let status = Command::new("git").arg("-C").arg(dir).args(args).output().unwrap().status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn commit(dir: &Path, message: &str) {
+        run(dir, &["commit", "--allow-empty", "-q", "-m", message]);
+    }
+
+    fn repo() -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        run(temp.path(), &["init", "-q"]);
+        run(temp.path(), &["config", "user.name", "Dana"]);
+        run(temp.path(), &["config", "user.email", "dana@example.com"]);
+        run(temp.path(), &["config", "commit.gpgsign", "false"]);
+        let cards = temp.child(".cuedeck/cards");
+        cards.child("abc123.md").write_str("---\ntitle: Login\nstatus: active\n---\n").unwrap();
+        cards.child("def456.md").write_str("---\ntitle: Logout\nstatus: todo\n---\n").unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_scan_links_commits_incrementally() {
+        let temp = repo();
+        commit(temp.path(), "fix login (abc123)");
+        commit(temp.path(), "unrelated abc1234 change");
+
+        let first = scan_commits(temp.path(), true).unwrap();
+        assert_eq!(first.scanned, 2);
+        assert_eq!(first.linked, 1);
+
+        commit(temp.path(), "Polish abc123 and def456\n\nCloses: def456");
+        let second = scan_commits(temp.path(), true).unwrap();
+        assert_eq!(second.scanned, 1);
+        assert_eq!(second.linked, 2);
+        // Trailers are only acted on when enabled
+        assert!(second.closed.is_empty());
+
+        let (commits, _) = task_commits(temp.path(), "abc123").unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].summary, "Polish abc123 and def456");
+        assert_eq!(commits[1].author, "Dana");
+
+        let (commits, report) = task_commits(temp.path(), "def456").unwrap();
+        assert_eq!(report.scanned, 0);
+        assert!(commits[0].closes);
+    }
+
+    #[test]
+    fn test_closes_trailer_moves_card_to_done() {
+        let temp = repo();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[git]\nclose_on_trailer = true\n")
+            .unwrap();
+        commit(temp.path(), "Finish login\n\nCloses: abc123");
+
+        let report = scan_commits(temp.path(), true).unwrap();
+        assert_eq!(report.closed, vec!["abc123"]);
+        let meta = crate::parse_file(temp.child(".cuedeck/cards/abc123.md").path())
+            .unwrap()
+            .frontmatter
+            .unwrap();
+        assert_eq!(meta.status, "done");
+    }
+
+    #[test]
+    fn test_only_trailers_close_cards() {
+        let temp = repo();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[git]\nclose_on_trailer = true\n")
+            .unwrap();
+        commit(temp.path(), "Fixes: crash in abc123");
+        commit(temp.path(), "Tidy def456\n\nCloses def456 once reviewed\n\nSigned-off-by: Dana");
+
+        let report = scan_commits(temp.path(), true).unwrap();
+        assert_eq!(report.linked, 2);
+        assert!(report.closed.is_empty());
+        assert!(trailer_block("Fixes: abc123").is_empty());
+        assert_eq!(trailer_block("Subject\n\nBody\n\nCloses: abc123\n"), "Closes: abc123");
+    }
+
+    #[test]
+    fn test_read_only_scan_leaves_trailers_pending() {
+        let temp = repo();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[git]\nclose_on_trailer = true\n")
+            .unwrap();
+        commit(temp.path(), "Finish login\n\nCloses: abc123");
+
+        let (commits, report) = task_commits(temp.path(), "abc123").unwrap();
+        assert!(commits[0].closes);
+        assert!(report.closed.is_empty());
+        let meta = crate::parse_file(temp.child(".cuedeck/cards/abc123.md").path())
+            .unwrap()
+            .frontmatter
+            .unwrap();
+        assert_eq!(meta.status, "active");
+
+        // The next applying scan has no new commits but still closes the card
+        let report = scan_commits(temp.path(), true).unwrap();
+        assert_eq!(report.scanned, 0);
+        assert_eq!(report.closed, vec!["abc123"]);
+        assert!(scan_commits(temp.path(), true).unwrap().closed.is_empty());
+    }
+
+    #[test]
+    fn test_rebuilt_index_does_not_reapply_trailers() {
+        let temp = repo();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[git]\nclose_on_trailer = true\n")
+            .unwrap();
+        commit(temp.path(), "Finish login\n\nCloses: abc123");
+        assert_eq!(scan_commits(temp.path(), true).unwrap().closed, vec!["abc123"]);
+
+        // Reopen the card, then rewrite history so the index is rebuilt
+        let mut reopen = serde_json::Map::new();
+        reopen.insert("status".to_string(), "active".into());
+        update_task(temp.path(), "abc123", reopen).unwrap();
+        commit(temp.path(), "Tidy up");
+        assert_eq!(scan_commits(temp.path(), true).unwrap().scanned, 1);
+        run(temp.path(), &["commit", "--amend", "-q", "--allow-empty", "-m", "Tidy up again"]);
+
+        let report = scan_commits(temp.path(), true).unwrap();
+        assert_eq!(report.scanned, 2);
+        assert!(report.closed.is_empty());
+        let meta = crate::parse_file(temp.child(".cuedeck/cards/abc123.md").path())
+            .unwrap()
+            .frontmatter
+            .unwrap();
+        assert_eq!(meta.status, "active");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::doctor::{CheckStatus, HealthCheck, RepairResult};
+use crate::frontmatter::Frontmatter;
 use crate::Result;
 use std::path::{Path, PathBuf};
 use std::fs;
@@ -27,8 +28,10 @@ pub fn check_metadata_consistency(workspace_root: &Path) -> Result<Vec<HealthChe
     let mut tag_counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
     let now = Utc::now();
     
-    // Known valid priorities
-    let valid_priorities = ["low", "medium", "high", "critical"];
+    // Status and priority vocabularies from config
+    let config = cue_config::Config::load(workspace_root)?;
+    let workflow = config.workflow;
+    let priorities = config.priorities;
     
     // Compile regex once outside loop
     let frontmatter_regex = Regex::new(r"(?ms)^---\r?\n(.*?)\r?\n---").unwrap();
@@ -56,8 +59,13 @@ pub fn check_metadata_consistency(workspace_root: &Path) -> Result<Vec<HealthChe
                         if let serde_yaml::Value::Mapping(map) = yaml {
                             // Check priority
                             if let Some(serde_yaml::Value::String(priority)) = map.get(serde_yaml::Value::String("priority".to_string())) {
-                                if !valid_priorities.contains(&priority.to_lowercase().as_str()) {
-                                    issues.push(format!("{}: Unknown priority '{}'", filename, priority));
+                                match priorities.resolve(priority) {
+                                    None => issues.push(format!("{}: Unknown priority '{}' (expected one of: {})", filename, priority, priorities.levels.join(", "))),
+                                    // Case variants resolve the same way; only aliases are flagged
+                                    Some(level) if !level.eq_ignore_ascii_case(priority) => {
+                                        issues.push(format!("{}: Non-canonical priority '{}' (should be '{}')", filename, priority, level));
+                                    }
+                                    Some(_) => {}
                                 }
                             }
                             
@@ -83,9 +91,23 @@ pub fn check_metadata_consistency(workspace_root: &Path) -> Result<Vec<HealthChe
                                 }
                             }
                             
-                            // Check for stale tasks (active > 90 days old)
+                            // Check status against the workflow vocabulary
+                            if let Some(serde_yaml::Value::String(status)) = map.get(serde_yaml::Value::String("status".to_string())) {
+                                if !workflow.is_known(status) {
+                                    issues.push(format!("{}: Unknown status '{}' (expected one of: {})", filename, status, workflow.statuses.join(", ")));
+                                }
+                            }
+
+                            // Check recurrence rules on recurring templates
+                            if let Some(serde_yaml::Value::String(rule)) = map.get(serde_yaml::Value::String("recurrence".to_string())) {
+                                if let Err(cue_common::CueError::ValidationError(e)) = crate::recurrence::Recurrence::parse(rule) {
+                                    issues.push(format!("{}: {}", filename, e));
+                                }
+                            }
+
+                            // Check for stale tasks (in progress > 90 days old)
                             if let Some(serde_yaml::Value::String(status)) = map.get(serde_yaml::Value::String("status".to_string())) {
-                                if status == "active" || status == "in-progress" {
+                                if workflow.is_in_progress(status) {
                                     // Check created or updated date
                                     let date_str = map.get(serde_yaml::Value::String("updated".to_string()))
                                         .or_else(|| map.get(serde_yaml::Value::String("created".to_string())));
@@ -102,6 +124,24 @@ pub fn check_metadata_consistency(workspace_root: &Path) -> Result<Vec<HealthChe
                                     }
                                 }
                             }
+
+                            // Check due date format and flag overdue open tasks
+                            if let Some(serde_yaml::Value::String(due)) = map.get(serde_yaml::Value::String("due".to_string())) {
+                                match crate::task_filters::parse_due_date(due) {
+                                    Some(due_date) => {
+                                        let status = map.get(serde_yaml::Value::String("status".to_string()))
+                                            .and_then(|v| v.as_str())
+                                            .unwrap_or("todo");
+                                        let overdue_days = (now.date_naive() - due_date).num_days();
+                                        if overdue_days > 0 && !workflow.is_terminal(status) {
+                                            issues.push(format!("{}: Overdue task ({}; due {}, {} days overdue)", filename, status, due, overdue_days));
+                                        }
+                                    }
+                                    None => {
+                                        issues.push(format!("{}: Invalid due date '{}' (expected YYYY-MM-DD)", filename, due));
+                                    }
+                                }
+                            }
                         }
                     }
                     Err(_) => continue, // Already caught by frontmatter check
@@ -126,15 +166,17 @@ pub fn check_metadata_consistency(workspace_root: &Path) -> Result<Vec<HealthChe
             fixable: false,
         });
     } else {
-        // Determine if any timestamp issues exist (these are fixable)
-        let has_timestamp_issues = issues.iter().any(|i| i.contains("Invalid timestamp format"));
+        // Timestamp and priority spelling issues are fixable
+        let has_fixable_issues = issues
+            .iter()
+            .any(|i| i.contains("Invalid timestamp format") || i.contains("Non-canonical priority"));
         
         checks.push(HealthCheck {
             name: "Metadata Consistency".to_string(),
             status: CheckStatus::Warn,
             message: format!("Found {} metadata issue(s)", issues.len()),
             details: Some(issues),
-            fixable: has_timestamp_issues, // Timestamp issues are auto-fixable
+            fixable: has_fixable_issues,
         });
     }
     
@@ -146,9 +188,11 @@ pub fn check_metadata_consistency(workspace_root: &Path) -> Result<Vec<HealthChe
 /// Automatically fixes:
 /// - Invalid timestamps (converts to ISO 8601)
 /// - Supports multiple input formats: YYYY-MM-DD, YYYY/MM/DD HH:MM:SS, Unix timestamps
+/// - Priority aliases and case variants (rewritten to the configured level name)
 /// - Tag normalization (if enabled): converts all tags to lowercase
 pub fn repair_metadata(workspace_root: &Path, _check: &HealthCheck, normalize_tags: bool) -> Result<RepairResult> {
     let cards_dir = workspace_root.join(".cuedeck/cards");
+    let priorities = cue_config::Config::load(workspace_root)?.priorities;
     let mut fixed_count = 0;
     let mut skip_count = 0;
     
@@ -160,9 +204,6 @@ pub fn repair_metadata(workspace_root: &Path, _check: &HealthCheck, normalize_ta
         });
     }
     
-    // Parse frontmatter regex
-    let frontmatter_regex = Regex::new(r"(?ms)^---\r?\n(.*?)\r?\n---").unwrap();
-    
     for entry in WalkDir::new(&cards_dir)
         .max_depth(1)
         .into_iter()
@@ -179,19 +220,13 @@ pub fn repair_metadata(workspace_root: &Path, _check: &HealthCheck, normalize_ta
                 }
             };
             
-            // Extract frontmatter
-            let captures = match frontmatter_regex.captures(&content) {
-                Some(c) => c,
-                None => continue,
+            // Edit the frontmatter in place so comments and formatting survive
+            let mut frontmatter = match Frontmatter::parse(&content) {
+                Ok(f) => f,
+                Err(_) => continue,
             };
-            
-            let yaml_str = captures.get(1).unwrap().as_str();
-            let full_match = captures.get(0).unwrap();
-            let yaml_end = full_match.end();
-            
-            // Parse YAML
-            let mut yaml_value: serde_yaml::Value = match serde_yaml::from_str(yaml_str) {
-                Ok(y) => y,
+            let map = match frontmatter.mapping() {
+                Ok(m) => m,
                 Err(_) => {
                     skip_count += 1;
                     continue;
@@ -200,74 +235,47 @@ pub fn repair_metadata(workspace_root: &Path, _check: &HealthCheck, normalize_ta
             
             let mut modified = false;
             
-            // Fix timestamps and tags
-            if let serde_yaml::Value::Mapping(ref mut map) = yaml_value {
-                // Fix 'created' field
-                if let Some(serde_yaml::Value::String(created_str)) = map.get(serde_yaml::Value::String("created".to_string())) {
-                    if DateTime::parse_from_rfc3339(created_str).is_err() {
+            // Fix 'created' and 'updated' timestamps
+            for field in ["created", "updated"] {
+                if let Some(serde_yaml::Value::String(timestamp)) = map.get(field) {
+                    if DateTime::parse_from_rfc3339(timestamp).is_err() {
                         // Try to parse and fix
-                        if let Some(fixed) = try_parse_and_fix_timestamp(created_str) {
-                            map.insert(
-                                serde_yaml::Value::String("created".to_string()),
-                                serde_yaml::Value::String(fixed),
-                            );
+                        if let Some(fixed) = try_parse_and_fix_timestamp(timestamp) {
+                            frontmatter.set_str(field, &fixed);
                             modified = true;
                         }
                     }
                 }
-                
-                // Fix 'updated' field
-                if let Some(serde_yaml::Value::String(updated_str)) = map.get(serde_yaml::Value::String("updated".to_string())) {
-                    if DateTime::parse_from_rfc3339(updated_str).is_err() {
-                        // Try to parse and fix
-                        if let Some(fixed) = try_parse_and_fix_timestamp(updated_str) {
-                            map.insert(
-                                serde_yaml::Value::String("updated".to_string()),
-                                serde_yaml::Value::String(fixed),
-                            );
-                            modified = true;
-                        }
-                    }
+            }
+            
+            // Rewrite priority aliases to the level they name
+            if let Some(serde_yaml::Value::String(priority)) = map.get("priority") {
+                if let Some(level) = priorities.resolve(priority).filter(|l| l != priority) {
+                    frontmatter.set_str("priority", level);
+                    modified = true;
                 }
-                
-                // Normalize tags if enabled
-                if normalize_tags {
-                    if let Some(serde_yaml::Value::Sequence(ref mut tags)) = map.get_mut(serde_yaml::Value::String("tags".to_string())) {
-                        let mut tags_modified = false;
-                        for tag in tags.iter_mut() {
-                            if let serde_yaml::Value::String(tag_str) = tag {
-                                let normalized = tag_str.to_lowercase();
-                                if &normalized != tag_str {
-                                    *tag = serde_yaml::Value::String(normalized);
-                                    tags_modified = true;
-                                }
-                            }
-                        }
-                        if tags_modified {
-                            modified = true;
-                        }
+            }
+
+            // Normalize tags if enabled
+            if normalize_tags {
+                if let Some(serde_yaml::Value::Sequence(tags)) = map.get("tags") {
+                    let normalized: Vec<serde_yaml::Value> = tags
+                        .iter()
+                        .map(|tag| match tag {
+                            serde_yaml::Value::String(t) => serde_yaml::Value::String(t.to_lowercase()),
+                            other => other.clone(),
+                        })
+                        .collect();
+                    if &normalized != tags {
+                        frontmatter.set("tags", &serde_yaml::Value::Sequence(normalized));
+                        modified = true;
                     }
                 }
             }
             
             // Write back if modified
             if modified {
-                // Serialize YAML
-                let new_yaml_str = match serde_yaml::to_string(&yaml_value) {
-                    Ok(s) => s.trim().to_string(),
-                    Err(_) => {
-                        skip_count += 1;
-                        continue;
-                    }
-                };
-                
-                // Reconstruct file with fixed frontmatter
-                let new_frontmatter = format!("---\n{}\n---", new_yaml_str);
-                let body = &content[yaml_end..];
-                let new_content = format!("{}{}", new_frontmatter, body);
-                
-                // Write to file
-                if fs::write(path, new_content).is_ok() {
+                if crate::write_atomic(path, &frontmatter.to_string()).is_ok() {
                     fixed_count += 1;
                 } else {
                     skip_count += 1;
@@ -286,7 +294,7 @@ pub fn repair_metadata(workspace_root: &Path, _check: &HealthCheck, normalize_ta
         Ok(RepairResult {
             check_name: "Metadata Consistency".to_string(),
             success: true,
-            message: "No timestamp issues found to fix".to_string(),
+            message: "No timestamp or priority issues found to fix".to_string(),
         })
     }
 }
@@ -666,6 +674,47 @@ mod tests {
         assert!(details.iter().any(|d| d.contains("Invalid timestamp format")));
     }
 
+    #[test]
+    fn test_check_metadata_consistency_overdue_task() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards_dir = temp.child(".cuedeck/cards");
+        cards_dir.create_dir_all().unwrap();
+        
+        let late = cards_dir.child("late.md");
+        late.write_str("---\ntitle: Late\nstatus: active\npriority: medium\ndue: 2020-01-01\n---\n# Late").unwrap();
+        
+        // Done tasks are never overdue
+        let shipped = cards_dir.child("shipped.md");
+        shipped.write_str("---\ntitle: Shipped\nstatus: done\npriority: medium\ndue: 2020-01-01\n---\n# Shipped").unwrap();
+        
+        let checks = check_metadata_consistency(temp.path()).unwrap();
+        assert_eq!(checks[0].status, CheckStatus::Warn);
+        
+        let details = checks[0].details.as_ref().unwrap();
+        assert!(details.iter().any(|d| d.starts_with("late.md: Overdue task")));
+        assert!(!details.iter().any(|d| d.starts_with("shipped.md")));
+    }
+
+    #[test]
+    fn test_check_metadata_consistency_unknown_status() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards_dir = temp.child(".cuedeck/cards");
+        cards_dir.create_dir_all().unwrap();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[workflow]\nstatuses = [\"todo\", \"review\", \"done\"]\n")
+            .unwrap();
+
+        cards_dir.child("ok.md").write_str("---\ntitle: Ok\nstatus: review\npriority: medium\n---\n").unwrap();
+        cards_dir.child("odd.md").write_str("---\ntitle: Odd\nstatus: active\npriority: medium\n---\n").unwrap();
+
+        let checks = check_metadata_consistency(temp.path()).unwrap();
+        assert_eq!(checks[0].status, CheckStatus::Warn);
+
+        let details = checks[0].details.as_ref().unwrap();
+        assert!(details.iter().any(|d| d.starts_with("odd.md: Unknown status 'active'")));
+        assert!(!details.iter().any(|d| d.starts_with("ok.md")));
+    }
+
     #[test]
     fn test_check_metadata_consistency_rare_tags() {
         let temp = assert_fs::TempDir::new().unwrap();
@@ -691,6 +740,33 @@ mod tests {
         assert!(details.iter().any(|d| d.contains("unique-tag")));
     }
 
+    #[test]
+    fn test_repair_priority_aliases() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards_dir = temp.child(".cuedeck/cards");
+        cards_dir.create_dir_all().unwrap();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[priorities]\nlevels = [\"P0\", \"P1\", \"P2\", \"P3\"]\ndefault = \"P2\"\n\n[priorities.aliases]\nurgent = \"P0\"\n")
+            .unwrap();
+
+        let card = cards_dir.child("alias.md");
+        card.write_str("---\ntitle: Alias\nstatus: todo\npriority: urgent\n---\n").unwrap();
+        cards_dir.child("case.md").write_str("---\ntitle: Case\nstatus: todo\npriority: p1\n---\n").unwrap();
+        cards_dir.child("odd.md").write_str("---\ntitle: Odd\nstatus: todo\npriority: high\n---\n").unwrap();
+
+        let checks = check_metadata_consistency(temp.path()).unwrap();
+        assert!(checks[0].fixable);
+        let details = checks[0].details.as_ref().unwrap();
+        assert!(details.iter().any(|d| d == "alias.md: Non-canonical priority 'urgent' (should be 'P0')"));
+        assert!(details.iter().any(|d| d.starts_with("odd.md: Unknown priority 'high'")));
+        assert!(!details.iter().any(|d| d.starts_with("case.md")));
+
+        let result = super::repair_metadata(temp.path(), &checks[0], false).unwrap();
+        assert!(result.message.contains("Fixed 2 card(s)"));
+        assert!(fs::read_to_string(card.path()).unwrap().contains("priority: P0\n"));
+        assert!(fs::read_to_string(cards_dir.child("case.md").path()).unwrap().contains("priority: P1\n"));
+    }
+
     #[test]
     fn test_repair_invalid_timestamp() {
         let temp = assert_fs::TempDir::new().unwrap();
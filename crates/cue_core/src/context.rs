@@ -76,6 +76,10 @@ pub struct SearchFilters {
     pub priority: Option<String>,
     /// Filter by assignee (exact match)
     pub assignee: Option<String>,
+    /// Parsed query string, with free text already moved into the search text
+    pub query: Option<crate::query::Query>,
+    /// Also search `.cuedeck/archive`
+    pub include_archived: bool,
 }
 
 /// Paginated search result
@@ -90,7 +94,16 @@ pub struct SearchResult {
 }
 
 impl SearchFilters {
-    pub fn matches(&self, doc: &Document) -> bool {
+    pub fn matches(&self, doc: &Document, config: &cue_config::Config) -> bool {
+        if let Some(ref query) = self.query {
+            if !query.matches(doc, config) {
+                return false;
+            }
+        }
+        if self.tags.is_none() && self.priority.is_none() && self.assignee.is_none() {
+            return true;
+        }
+
         let meta = match &doc.frontmatter {
             Some(m) => m,
             None => return false,
@@ -159,6 +172,9 @@ pub fn search_workspace_with_mode(
         mode
     );
 
+    // Nothing to rank by: a filter-only query lists every matching document
+    let mode = if query.trim().is_empty() { SearchMode::Keyword } else { mode };
+
     match mode {
         SearchMode::Keyword => search_workspace_keyword(root, query, _filters.as_ref()),
         SearchMode::Semantic => search_workspace_semantic(root, query, 10, _filters.as_ref()),
@@ -185,6 +201,9 @@ pub fn search_workspace_paginated(
         cursor
     );
 
+    // Nothing to rank by: a filter-only query lists every matching document
+    let mode = if query.trim().is_empty() { SearchMode::Keyword } else { mode };
+
     // Get ALL matching documents first (unpaginated)
     let all_docs = match mode {
         SearchMode::Keyword => search_workspace_keyword_all(root, query, filters.as_ref()),
@@ -241,6 +260,7 @@ fn search_workspace_keyword(root: &Path, query: &str, filters: Option<&SearchFil
 
 /// Keyword-based search - returns ALL results (for pagination)
 fn search_workspace_keyword_all(root: &Path, query: &str, filters: Option<&SearchFilters>) -> Result<Vec<Document>> {
+    let workspace_config = cue_config::Config::load(root)?;
     let query_lower = query.to_lowercase();
     let query_tokens: Vec<&str> = query_lower.split_whitespace().collect();
 
@@ -251,7 +271,7 @@ fn search_workspace_keyword_all(root: &Path, query: &str, filters: Option<&Searc
     let walker = WalkDir::new(root)
         .follow_links(true)
         .into_iter()
-        .filter_entry(|e| !is_ignored(e));
+        .filter_entry(|e| !is_ignored(e, include_archived(filters)));
 
     for entry in walker.filter_map(|e| e.ok()) {
         if entry.file_type().is_file() {
@@ -273,13 +293,13 @@ fn search_workspace_keyword_all(root: &Path, query: &str, filters: Option<&Searc
     }
 
     // Sort by score descending
-    results.sort_by(|a, b| b.1.cmp(&a.1));
+    results.sort_by_key(|r| std::cmp::Reverse(r.1));
 
     // Filter and return ALL results
     let all_docs = results
         .into_iter()
         .filter(|(doc, _)| {
-            filters.is_none_or(|f| f.matches(doc))
+            filters.is_none_or(|f| f.matches(doc, &workspace_config))
         })
         .map(|(doc, _)| doc)
         .collect();
@@ -296,6 +316,7 @@ fn search_workspace_semantic(root: &Path, query: &str, limit: usize, filters: Op
 /// Semantic search using vector embeddings - returns ALL results (for pagination)
 fn search_workspace_semantic_all(root: &Path, query: &str, filters: Option<&SearchFilters>) -> Result<Vec<Document>> {
     use rayon::prelude::*;
+    let workspace_config = cue_config::Config::load(root)?;
 
     tracing::info!("Performing semantic search for: '{}'", query);
 
@@ -308,7 +329,7 @@ fn search_workspace_semantic_all(root: &Path, query: &str, filters: Option<&Sear
     let walker = WalkDir::new(root)
         .follow_links(true)
         .into_iter()
-        .filter_entry(|e| !is_ignored(e));
+        .filter_entry(|e| !is_ignored(e, include_archived(filters)));
 
     for entry in walker.filter_map(|e| e.ok()) {
         if entry.file_type().is_file() {
@@ -367,7 +388,7 @@ fn search_workspace_semantic_all(root: &Path, query: &str, filters: Option<&Sear
     Ok(sorted_candidates
         .into_iter()
         .filter(|(doc, _)| {
-            filters.is_none_or(|f| f.matches(doc))
+            filters.is_none_or(|f| f.matches(doc, &workspace_config))
         })
         .map(|(doc, _)| doc)
         .collect())
@@ -390,6 +411,7 @@ fn search_workspace_hybrid(root: &Path, query: &str, filters: Option<&SearchFilt
 /// Hybrid search: combines keyword and semantic search - returns ALL results (for pagination)
 fn search_workspace_hybrid_all(root: &Path, query: &str, filters: Option<&SearchFilters>) -> Result<Vec<Document>> {
     use rayon::prelude::*;
+    let workspace_config = cue_config::Config::load(root)?;
 
     tracing::info!("Performing hybrid search for: '{}'", query);
     
@@ -402,7 +424,7 @@ fn search_workspace_hybrid_all(root: &Path, query: &str, filters: Option<&Search
     let walker = WalkDir::new(root)
         .follow_links(true)
         .into_iter()
-        .filter_entry(|e| !is_ignored(e));
+        .filter_entry(|e| !is_ignored(e, include_archived(filters)));
 
     for entry in walker.filter_map(|e| e.ok()) {
         if entry.file_type().is_file() {
@@ -477,7 +499,7 @@ fn search_workspace_hybrid_all(root: &Path, query: &str, filters: Option<&Search
     Ok(scored
         .into_iter()
         .filter(|(doc, _)| {
-            filters.is_none_or(|f| f.matches(doc))
+            filters.is_none_or(|f| f.matches(doc, &workspace_config))
         })
         .map(|(doc, _)| doc)
         .collect())
@@ -490,7 +512,11 @@ pub fn save_embedding_cache() -> Result<()> {
     cache.save().map_err(|e| std::io::Error::other(e.to_string()).into())
 }
 
-fn is_ignored(entry: &walkdir::DirEntry) -> bool {
+fn include_archived(filters: Option<&SearchFilters>) -> bool {
+    filters.is_some_and(|f| f.include_archived)
+}
+
+fn is_ignored(entry: &walkdir::DirEntry, include_archived: bool) -> bool {
     let name = entry.file_name().to_string_lossy();
     // Ignore common noise directories
     name == "node_modules"
@@ -498,6 +524,9 @@ fn is_ignored(entry: &walkdir::DirEntry) -> bool {
         || name == "target"
         || name == "dist"
         || name == "vendor"
+        // Deleted cards are kept for restore, not for search
+        || entry.path().ends_with(".cuedeck/trash")
+        || (!include_archived && entry.path().ends_with(".cuedeck/archive"))
 }
 
 fn score_file(path: &Path, query_lower: &str, tokens: &[&str]) -> i32 {
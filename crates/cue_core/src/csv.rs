@@ -0,0 +1,106 @@
+//! Minimal RFC 4180 CSV support for import and export
+//!
+//! Parsing handles quoted fields with embedded commas, quotes (`""`) and
+//! newlines, and both `\n` and `\r\n` line endings.
+
+use cue_common::{CueError, Result};
+
+/// Parse CSV text into records; blank lines are skipped
+pub fn parse(input: &str) -> Result<Vec<Vec<String>>> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|f| !f.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(CueError::ParseError(format!(
+            "Unterminated quoted CSV field (line {})",
+            line
+        )));
+    }
+    record.push(field);
+    if record.iter().any(|f| !f.is_empty()) {
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Format one record as a CSV line (with trailing newline)
+///
+/// Fields are quoted only when they contain a comma, quote or line break.
+pub fn write_record<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let records = parse("id,title\r\n1,\"Fix, then \"\"ship\"\"\"\n\n2,\"two\nlines\"\n").unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1], vec!["1", "Fix, then \"ship\""]);
+        assert_eq!(records[2], vec!["2", "two\nlines"]);
+
+        assert!(parse("id,title\n1,\"open").is_err());
+    }
+
+    #[test]
+    fn test_write_record_round_trips() {
+        let fields = ["a,b", "say \"hi\"", "two\nlines", "plain"];
+        let line = write_record(&fields);
+        assert_eq!(parse(&line).unwrap(), vec![fields.map(String::from).to_vec()]);
+    }
+}
@@ -0,0 +1,394 @@
+//! Standup digest: what changed in the workspace since a point in time
+//!
+//! Cards come from their `created`/`updated` timestamps and the activity log.
+//! Blocked state is worked out at both ends of the window from statuses and
+//! dependencies replayed from the activity log and the current `TaskGraph`.
+//! Docs under `.cuedeck/docs` count as edited when modified in the window,
+//! unless the `DocumentCache` shows the content hash predates it (a touch).
+
+use crate::activity::read_events;
+use crate::cache::DocumentCache;
+use crate::task_filters::TaskFilters;
+use crate::task_graph::TaskGraph;
+use crate::tasks::list_tasks_filtered;
+use chrono::{DateTime, NaiveTime, Utc};
+use cue_common::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// A card listed in the digest
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestCard {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+}
+
+/// A card whose status moved during the window (first `from`, last `to`)
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusChange {
+    pub id: String,
+    pub title: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// A card that became blocked during the window
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockedCard {
+    pub id: String,
+    pub title: String,
+    /// Open dependencies holding it up
+    pub blocked_by: Vec<String>,
+}
+
+/// Everything that changed since `since`
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    /// RFC 3339
+    pub since: String,
+    pub created: Vec<DigestCard>,
+    pub status_changes: Vec<StatusChange>,
+    pub blocked: Vec<BlockedCard>,
+    pub unblocked: Vec<DigestCard>,
+    /// Cards updated without a status change (and not created in the window)
+    pub updated: Vec<DigestCard>,
+    /// Workspace-relative paths of edited docs
+    pub docs: Vec<String>,
+}
+
+/// Start of a digest window: `YYYY-MM-DD` (midnight UTC), an RFC 3339
+/// timestamp, or a duration before `now` such as `1d`
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if let Some(age) = crate::task_filters::parse_relative_duration(value) {
+        return Some(now - age);
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    crate::task_filters::parse_due_date(value).map(|d| d.and_time(NaiveTime::MIN).and_utc())
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn string_list(value: &Option<Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str().map(String::from)).collect(),
+        Some(Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// What the activity log says about a card's window
+#[derive(Default)]
+struct CardActivity {
+    created: Option<DateTime<Utc>>,
+    /// Status before the first change in the window, and after the last
+    first_status: Option<String>,
+    last_status: Option<String>,
+    /// `depends_on` before the first change in the window
+    deps_before: Option<Vec<String>>,
+}
+
+/// Build the digest for changes made at or after `since`
+pub fn digest(workspace_root: &Path, since: DateTime<Utc>) -> Result<Digest> {
+    let workflow = cue_config::Config::load(workspace_root)?.workflow;
+
+    let mut activity: HashMap<String, CardActivity> = HashMap::new();
+    for event in read_events(workspace_root)? {
+        let Some(at) = parse_time(&event.timestamp) else {
+            continue;
+        };
+        let card = activity.entry(event.task_id.clone()).or_default();
+        if event.action == "created" && card.created.is_none() {
+            card.created = Some(at);
+        }
+        if at < since {
+            continue;
+        }
+        for change in &event.changes {
+            match change.field.as_str() {
+                "status" => {
+                    if card.first_status.is_none() {
+                        card.first_status = change.old.as_ref().and_then(|v| v.as_str()).map(String::from);
+                    }
+                    card.last_status = change.new.as_ref().and_then(|v| v.as_str()).map(String::from);
+                }
+                "depends_on" if card.deps_before.is_none() => {
+                    card.deps_before = Some(string_list(&change.old));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let filters = TaskFilters {
+        include_archived: true,
+        ..Default::default()
+    };
+    let cards: BTreeMap<String, cue_common::CardMetadata> = list_tasks_filtered(workspace_root, &filters)?
+        .into_iter()
+        .filter_map(|doc| Some((doc.path.file_stem()?.to_str()?.to_string(), doc.frontmatter?)))
+        .collect();
+    let graph = TaskGraph::from_workspace(workspace_root)?;
+    let no_activity = CardActivity::default();
+
+    // Status at the start of the window: the old value of its first change
+    let status_then = |id: &str| -> Option<String> {
+        let card = activity.get(id).unwrap_or(&no_activity);
+        card.first_status.clone().or_else(|| cards.get(id).map(|m| m.status.clone()))
+    };
+    let is_open = |status: Option<&str>| status.is_some_and(|s| !workflow.is_terminal(s));
+    let open_now = |id: &str| is_open(cards.get(id).map(|m| m.status.as_str()));
+    let open_then = |id: &str| is_open(status_then(id).as_deref());
+
+    let mut digest = Digest {
+        since: since.to_rfc3339(),
+        created: Vec::new(),
+        status_changes: Vec::new(),
+        blocked: Vec::new(),
+        unblocked: Vec::new(),
+        updated: Vec::new(),
+        docs: Vec::new(),
+    };
+
+    for (id, meta) in &cards {
+        let card = activity.get(id).unwrap_or(&no_activity);
+        let listed = DigestCard {
+            id: id.clone(),
+            title: meta.title.clone(),
+            status: meta.status.clone(),
+        };
+        let created = meta.created.as_deref().and_then(parse_time).or(card.created);
+        if created.is_some_and(|t| t >= since) {
+            digest.created.push(listed);
+            continue;
+        }
+
+        let moved = match (&card.first_status, &card.last_status) {
+            (Some(from), Some(to)) if from != to => Some((from.clone(), to.clone())),
+            _ => None,
+        };
+        if let Some((from, to)) = moved {
+            digest.status_changes.push(StatusChange {
+                id: id.clone(),
+                title: meta.title.clone(),
+                from,
+                to,
+            });
+        } else if meta.updated.as_deref().and_then(parse_time).is_some_and(|t| t >= since) {
+            digest.updated.push(listed.clone());
+        }
+
+        let blockers_now: Vec<String> = graph
+            .get_dependencies(id)
+            .into_iter()
+            .filter(|dep| open_now(dep))
+            .collect();
+        let deps_then = card.deps_before.clone().unwrap_or_else(|| graph.get_dependencies(id));
+        let blocked_then = open_then(id) && deps_then.iter().any(|dep| open_then(dep));
+        let blocked_now = open_now(id) && !blockers_now.is_empty();
+
+        if blocked_now && !blocked_then {
+            let mut blocked_by = blockers_now;
+            blocked_by.sort();
+            digest.blocked.push(BlockedCard {
+                id: id.clone(),
+                title: meta.title.clone(),
+                blocked_by,
+            });
+        } else if blocked_then && !blocked_now && open_now(id) {
+            digest.unblocked.push(listed);
+        }
+    }
+
+    digest.docs = edited_docs(workspace_root, since);
+    Ok(digest)
+}
+
+/// Docs modified since `since` whose content actually changed
+fn edited_docs(workspace_root: &Path, since: DateTime<Utc>) -> Vec<String> {
+    let docs_dir = workspace_root.join(".cuedeck/docs");
+    if !docs_dir.exists() {
+        return Vec::new();
+    }
+    let cache = DocumentCache::new(workspace_root).ok().and_then(|mut cache| {
+        cache.load().ok()?;
+        Some(cache)
+    });
+
+    let mut docs: Vec<String> = walkdir::WalkDir::new(&docs_dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|x| x == "md"))
+        .filter(|entry| {
+            let Some(modified) = entry.metadata().ok().and_then(|m| m.modified().ok()) else {
+                return false;
+            };
+            if DateTime::<Utc>::from(modified) < since {
+                return false;
+            }
+            // Same content as a cache entry from before the window: only touched
+            let cached = cache.as_ref().and_then(|c| c.entry(entry.path()));
+            !cached.is_some_and(|c| {
+                DateTime::<Utc>::from(c.modified) < since
+                    && std::fs::read_to_string(entry.path())
+                        .is_ok_and(|content| crate::content_hash(&content) == c.hash)
+            })
+        })
+        .map(|entry| {
+            let path = entry.path();
+            path.strip_prefix(workspace_root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect();
+    docs.sort();
+    docs
+}
+
+impl Digest {
+    /// Whether nothing changed in the window
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty()
+            && self.status_changes.is_empty()
+            && self.blocked.is_empty()
+            && self.unblocked.is_empty()
+            && self.updated.is_empty()
+            && self.docs.is_empty()
+    }
+
+    /// Render as Markdown, omitting empty sections
+    pub fn to_markdown(&self) -> String {
+        let since = parse_time(&self.since)
+            .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| self.since.clone());
+        let mut out = format!("# Digest since {}\n", since);
+        if self.is_empty() {
+            out.push_str("\nNothing changed.\n");
+            return out;
+        }
+
+        let mut section = |heading: &str, lines: Vec<String>| {
+            if lines.is_empty() {
+                return;
+            }
+            out.push_str(&format!("\n## {} ({})\n\n", heading, lines.len()));
+            for line in lines {
+                out.push_str(&format!("- {}\n", line));
+            }
+        };
+        let card_line = |c: &DigestCard| format!("`{}` {} ({})", c.id, c.title, c.status);
+
+        section("Created", self.created.iter().map(card_line).collect());
+        section(
+            "Status changes",
+            self.status_changes
+                .iter()
+                .map(|c| format!("`{}` {}: {} → {}", c.id, c.title, c.from, c.to))
+                .collect(),
+        );
+        section(
+            "Newly blocked",
+            self.blocked
+                .iter()
+                .map(|c| {
+                    let deps: Vec<String> = c.blocked_by.iter().map(|d| format!("`{}`", d)).collect();
+                    format!("`{}` {}, waiting on {}", c.id, c.title, deps.join(", "))
+                })
+                .collect(),
+        );
+        section("Unblocked", self.unblocked.iter().map(card_line).collect());
+        section("Updated", self.updated.iter().map(card_line).collect());
+        section("Docs edited", self.docs.iter().map(|d| format!("`{}`", d)).collect());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        parse_time(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = time("2024-06-10T12:00:00Z");
+        assert_eq!(parse_since("1d", now), Some(time("2024-06-09T12:00:00Z")));
+        assert_eq!(parse_since("2024-06-01", now), Some(time("2024-06-01T00:00:00Z")));
+        assert_eq!(parse_since("2024-06-01T08:00:00+02:00", now), Some(time("2024-06-01T06:00:00Z")));
+        assert_eq!(parse_since("yesterday", now), None);
+    }
+
+    #[test]
+    fn test_digest_sections() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        let card = |id: &str, body: &str| {
+            cards.child(format!("{}.md", id)).write_str(&format!("---\n{}\n---\n", body)).unwrap();
+        };
+        card("api001", "title: API\nstatus: done\ncreated: 2024-06-01T09:00:00Z\nupdated: 2024-06-10T09:00:00Z");
+        card("ui0001", "title: UI\nstatus: todo\ndepends_on: [api001]\ncreated: 2024-06-01T09:00:00Z");
+        card("db0001", "title: DB\nstatus: todo\ncreated: 2024-06-01T09:00:00Z");
+        card("ops001", "title: Ops\nstatus: todo\ndepends_on: [db0001]\ncreated: 2024-06-01T09:00:00Z\nupdated: 2024-06-10T09:00:00Z");
+        card("doc001", "title: Docs\nstatus: todo\ncreated: 2024-06-10T08:00:00Z");
+        card("old001", "title: Old\nstatus: todo\ncreated: 2024-06-01T09:00:00Z\nupdated: 2024-06-02T09:00:00Z");
+        temp.child(".cuedeck/activity.jsonl")
+            .write_str(concat!(
+                r#"{"timestamp":"2024-06-10T09:00:00Z","task_id":"api001","action":"updated","changes":[{"field":"status","old":"active","new":"done"}]}"#,
+                "\n",
+                r#"{"timestamp":"2024-06-10T09:00:00Z","task_id":"ops001","action":"updated","changes":[{"field":"depends_on","new":["db0001"]}]}"#,
+                "\n",
+            ))
+            .unwrap();
+
+        let digest = digest(temp.path(), time("2024-06-10T00:00:00Z")).unwrap();
+        let ids = |cards: &[DigestCard]| cards.iter().map(|c| c.id.clone()).collect::<Vec<_>>();
+
+        assert_eq!(ids(&digest.created), vec!["doc001"]);
+        assert_eq!(digest.status_changes.len(), 1);
+        assert_eq!((digest.status_changes[0].from.as_str(), digest.status_changes[0].to.as_str()), ("active", "done"));
+        assert_eq!(digest.blocked.len(), 1);
+        assert_eq!(digest.blocked[0].id, "ops001");
+        assert_eq!(digest.blocked[0].blocked_by, vec!["db0001"]);
+        assert_eq!(ids(&digest.unblocked), vec!["ui0001"]);
+        assert_eq!(ids(&digest.updated), vec!["ops001"]);
+
+        let markdown = digest.to_markdown();
+        assert!(markdown.starts_with("# Digest since 2024-06-10 00:00 UTC\n"));
+        assert!(markdown.contains("## Status changes (1)\n\n- `api001` API: active → done\n"));
+        assert!(markdown.contains("- `ops001` Ops, waiting on `db0001`\n"));
+        assert!(!markdown.contains("Docs edited"));
+    }
+
+    #[test]
+    fn test_edited_docs_skips_touched_files() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child(".cuedeck/docs/api.md").write_str("# API\n").unwrap();
+        temp.child(".cuedeck/docs/guide.md").write_str("# Guide\n").unwrap();
+
+        let since = Utc::now() - chrono::Duration::hours(1);
+        assert_eq!(edited_docs(temp.path(), since), vec![".cuedeck/docs/api.md", ".cuedeck/docs/guide.md"]);
+
+        // Cache the guide as if it had been parsed before the window
+        let guide = temp.path().join(".cuedeck/docs/guide.md");
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(7200);
+        std::fs::File::options().write(true).open(&guide).unwrap().set_modified(old).unwrap();
+        let mut cache = DocumentCache::new(temp.path()).unwrap();
+        cache.get_or_parse(&guide).unwrap();
+        cache.save().unwrap();
+        // Touch it: new mtime, same content
+        std::fs::File::options().write(true).open(&guide).unwrap().set_modified(std::time::SystemTime::now()).unwrap();
+
+        assert_eq!(edited_docs(temp.path(), since), vec![".cuedeck/docs/api.md"]);
+    }
+}
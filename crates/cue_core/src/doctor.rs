@@ -82,6 +82,12 @@ pub fn run_diagnostics(workspace_root: &Path) -> Result<DoctorReport> {
     // Check 6: Task graph validation
     checks.extend(check_task_graph(workspace_root)?);
 
+    // Check 7: Epic hierarchy
+    checks.extend(crate::task_hierarchy::check_hierarchy(workspace_root)?);
+
+    // Check 8: Card templates
+    checks.extend(crate::templates::check_templates(workspace_root)?);
+
     let healthy = checks.iter().all(|c| c.status == CheckStatus::Pass);
 
     // Gather stats
@@ -115,17 +121,24 @@ fn check_config_syntax(workspace_root: &Path) -> HealthCheck {
     }
 
     match fs::read_to_string(&config_path) {
-        Ok(_content) => {
-            // Basic validation: file is readable
-            // Full TOML validation could be added later with toml crate
-            HealthCheck {
+        // Other commands fall back to defaults for some invalid sections, so
+        // this is where those errors show up
+        Ok(_content) => match cue_config::Config::check_file(workspace_root) {
+            Ok(()) => HealthCheck {
                 name: "Config File".to_string(),
                 status: CheckStatus::Pass,
                 message: "Config file exists and is readable".to_string(),
                 details: None,
                 fixable: false,
-            }
-        }
+            },
+            Err(e) => HealthCheck {
+                name: "Config File".to_string(),
+                status: CheckStatus::Fail,
+                message: "Config file is invalid".to_string(),
+                details: Some(vec![e.to_string()]),
+                fixable: false,
+            },
+        },
         Err(e) => HealthCheck {
             name: "Config File".to_string(),
             status: CheckStatus::Fail,
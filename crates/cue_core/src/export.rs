@@ -0,0 +1,273 @@
+//! Export cards to CSV, JSON Lines and iCalendar
+//!
+//! Output is deterministic: cards are sorted by ID, columns follow the
+//! `CardMetadata` field order, and no timestamps are invented, so an export
+//! can be committed and diffed.
+
+use chrono::{DateTime, Duration, NaiveDate};
+use cue_common::{CardMetadata, CueError, Document, Result};
+use serde::Serialize;
+
+/// Output format for `export_cards`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    Ics,
+}
+
+impl ExportFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::Jsonl),
+            "ics" => Ok(Self::Ics),
+            other => Err(CueError::ValidationError(format!(
+                "Invalid export format '{}'. Must be: csv, jsonl or ics",
+                other
+            ))),
+        }
+    }
+}
+
+/// A card as written to CSV and JSON Lines: its ID, then every metadata field
+#[derive(Serialize)]
+struct ExportedCard<'a> {
+    id: &'a str,
+    #[serde(flatten)]
+    meta: &'a CardMetadata,
+}
+
+/// Render `docs` in `format`, one row, line or event per card
+///
+/// Documents without frontmatter are skipped. Calendar events use the due
+/// date, falling back to the created date; cards with neither are left out.
+pub fn export_cards(docs: &[Document], format: ExportFormat) -> Result<String> {
+    let mut cards: Vec<(&str, &CardMetadata)> = docs
+        .iter()
+        .filter_map(|doc| {
+            let id = doc.path.file_stem()?.to_str()?;
+            Some((id, doc.frontmatter.as_ref()?))
+        })
+        .collect();
+    cards.sort_by(|a, b| a.0.cmp(b.0));
+
+    match format {
+        ExportFormat::Csv => to_csv(&cards),
+        ExportFormat::Jsonl => to_jsonl(&cards),
+        ExportFormat::Ics => Ok(to_ics(&cards)),
+    }
+}
+
+fn to_csv(cards: &[(&str, &CardMetadata)]) -> Result<String> {
+    // Column names come from the struct so new metadata fields are exported too
+    let blank: CardMetadata =
+        serde_yaml::from_str("title: ''").map_err(|e| CueError::ParseError(e.to_string()))?;
+    let mut header = vec!["id".to_string()];
+    header.extend(fields(&blank)?.into_iter().map(|(key, _)| key));
+
+    let mut out = crate::csv::write_record(&header);
+    for (id, meta) in cards {
+        let mut row = vec![id.to_string()];
+        row.extend(fields(meta)?.into_iter().map(|(_, value)| value));
+        out.push_str(&crate::csv::write_record(&row));
+    }
+    Ok(out)
+}
+
+/// Metadata fields in declaration order, rendered as CSV cells
+///
+/// Lists are comma-separated, as on the command line; unset fields are empty.
+fn fields(meta: &CardMetadata) -> Result<Vec<(String, String)>> {
+    let value = serde_yaml::to_value(meta).map_err(|e| CueError::ParseError(e.to_string()))?;
+    let serde_yaml::Value::Mapping(map) = value else {
+        return Ok(Vec::new());
+    };
+
+    Ok(map
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = key.as_str()?.to_string();
+            let cell = match value {
+                serde_yaml::Value::Null => String::new(),
+                serde_yaml::Value::String(s) => s,
+                serde_yaml::Value::Sequence(items) => items
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .collect::<Vec<_>>()
+                    .join(","),
+                other => serde_yaml::to_string(&other).unwrap_or_default().trim().to_string(),
+            };
+            Some((key, cell))
+        })
+        .collect())
+}
+
+fn to_jsonl(cards: &[(&str, &CardMetadata)]) -> Result<String> {
+    let mut out = String::new();
+    for (id, meta) in cards {
+        out.push_str(&serde_json::to_string(&ExportedCard { id, meta })?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn to_ics(cards: &[(&str, &CardMetadata)]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//CueDeck//cue export//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for (id, meta) in cards {
+        let Some(date) = event_date(meta) else {
+            continue;
+        };
+        // DTSTAMP is required; use the card's own timestamps to stay deterministic
+        let stamp = meta
+            .updated
+            .as_deref()
+            .or(meta.created.as_deref())
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.naive_utc().format("%Y%m%dT%H%M%SZ").to_string())
+            .unwrap_or_else(|| format!("{}T000000Z", date.format("%Y%m%d")));
+
+        let mut details = vec![
+            format!("Status: {}", meta.status),
+            format!("Priority: {}", meta.priority),
+        ];
+        if let Some(assignee) = &meta.assignee {
+            details.push(format!("Assignee: {}", assignee));
+        }
+        if let Some(deps) = meta.depends_on.as_ref().filter(|d| !d.is_empty()) {
+            details.push(format!("Depends on: {}", deps.join(", ")));
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@cuedeck", id));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (date + Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!("SUMMARY:{}", escape_text(&format!("[{}] {}", id, meta.title))));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&details.join("\n"))));
+        if let Some(tags) = meta.tags.as_ref().filter(|t| !t.is_empty()) {
+            let tags: Vec<String> = tags.iter().map(|t| escape_text(t)).collect();
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold_line(line) + "\r\n").collect()
+}
+
+/// The day a card's event falls on: due date, else created date
+fn event_date(meta: &CardMetadata) -> Option<NaiveDate> {
+    meta.due
+        .as_deref()
+        .and_then(crate::task_filters::parse_due_date)
+        .or_else(|| {
+            let created = DateTime::parse_from_rfc3339(meta.created.as_deref()?).ok()?;
+            Some(created.date_naive())
+        })
+}
+
+/// Escape an iCalendar TEXT value (RFC 5545 §3.3.11)
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\r', "")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets, continuing with a leading space
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, frontmatter: &str) -> Document {
+        Document {
+            path: format!(".cuedeck/cards/{}.md", id).into(),
+            frontmatter: Some(serde_yaml::from_str(frontmatter).unwrap()),
+            hash: String::new(),
+            tokens: 0,
+            anchors: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    fn cards() -> Vec<Document> {
+        vec![
+            doc("bbb222", "title: Ship, then celebrate\ndue: 2024-06-01\ntags: [release, team]\ndepends_on: [aaa111]"),
+            doc("aaa111", "title: Build\nstatus: active\ncreated: 2024-05-01T10:00:00Z\nassignee: dana"),
+        ]
+    }
+
+    #[test]
+    fn test_export_csv_is_sorted_with_all_fields() {
+        let csv = export_cards(&cards(), ExportFormat::Csv).unwrap();
+        let rows = crate::csv::parse(&csv).unwrap();
+
+        assert_eq!(rows[0][..4], ["id", "title", "status", "assignee"]);
+        assert!(rows[0].contains(&"depends_on".to_string()));
+        assert!(rows[0].contains(&"external_id".to_string()));
+        assert_eq!(rows[1][0], "aaa111");
+        assert_eq!(rows[2][1], "Ship, then celebrate");
+
+        let deps = rows[0].iter().position(|h| h == "depends_on").unwrap();
+        assert_eq!(rows[2][deps], "aaa111");
+        assert_eq!(csv, export_cards(&cards(), ExportFormat::Csv).unwrap());
+    }
+
+    #[test]
+    fn test_export_jsonl() {
+        let jsonl = export_cards(&cards(), ExportFormat::Jsonl).unwrap();
+        let lines: Vec<serde_json::Value> =
+            jsonl.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["id"], "aaa111");
+        assert_eq!(lines[1]["tags"], serde_json::json!(["release", "team"]));
+        assert!(jsonl.starts_with("{\"id\":\"aaa111\",\"title\":\"Build\""));
+    }
+
+    #[test]
+    fn test_export_ics_uses_due_then_created() {
+        let ics = export_cards(&cards(), ExportFormat::Ics).unwrap();
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.contains("UID:aaa111@cuedeck\r\nDTSTAMP:20240501T100000Z\r\nDTSTART;VALUE=DATE:20240501\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20240601\r\nDTEND;VALUE=DATE:20240602\r\n"));
+        assert!(ics.contains("SUMMARY:[bbb222] Ship\\, then celebrate\r\n"));
+        assert!(ics.contains("CATEGORIES:release,team\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = format!("DESCRIPTION:{}", "x".repeat(100));
+        let folded = fold_line(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
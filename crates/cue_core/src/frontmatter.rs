@@ -0,0 +1,454 @@
+//! Format-preserving frontmatter editing
+//!
+//! Card writers change individual keys in place instead of re-serializing the
+//! whole YAML block, so comments, key order, quoting and list style survive
+//! and git diffs only show the lines that actually changed.
+
+use cue_common::{CueError, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_yaml::{Mapping, Value};
+
+lazy_static! {
+    /// Opening `---`, the YAML lines (absent for an empty block) and the closing `---`
+    static ref FRONTMATTER_RE: Regex = Regex::new(r"(?ms)^---\r?\n(?:(.*?)\r?\n)??---").unwrap();
+    /// A top-level `key:` at the start of a line
+    static ref KEY_RE: Regex =
+        Regex::new(r#"^("[^"]*"|'[^']*'|[^\s#'"\-][^:]*?)\s*:(\s|$)"#).unwrap();
+}
+
+/// Keys that are double-quoted when first added (`assignee: "@x"` convention)
+const QUOTED_KEYS: &[&str] = &["assignee", "milestone"];
+
+/// Quoting style of an existing scalar
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Quote {
+    Plain,
+    Single,
+    Double,
+}
+
+/// Frontmatter of a card, split into lines that can be edited in place
+#[derive(Debug, Clone)]
+pub struct Frontmatter {
+    /// Everything before the YAML lines (the opening `---`)
+    prefix: String,
+    lines: Vec<String>,
+    /// Everything after the YAML lines (closing `---` and the body)
+    suffix: String,
+    newline: &'static str,
+}
+
+impl Default for Frontmatter {
+    /// Empty frontmatter for a new card
+    fn default() -> Self {
+        Self {
+            prefix: "---\n".to_string(),
+            lines: Vec::new(),
+            suffix: "\n---\n".to_string(),
+            newline: "\n",
+        }
+    }
+}
+
+/// Line range of one top-level key
+struct Entry {
+    start: usize,
+    /// Exclusive
+    end: usize,
+    /// Key as written, up to (not including) the colon
+    key_text: String,
+    /// Text after the colon on the key line, without a trailing comment
+    inline: String,
+    /// Trailing `# comment` on the key line, including leading spaces
+    comment: String,
+}
+
+fn unquote_key(key: &str) -> &str {
+    key.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// Split `value # comment` outside of quotes
+fn split_comment(text: &str) -> (&str, &str) {
+    let mut quote: Option<char> = None;
+    let mut prev_space = true;
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && prev_space => {
+                let value_end = text[..i].trim_end().len();
+                return (&text[..value_end], &text[value_end..]);
+            }
+            None => {}
+        }
+        prev_space = c.is_whitespace();
+    }
+    (text.trim_end(), "")
+}
+
+/// Whether `fragment` parses as a YAML value equal to `expected`
+fn parses_as(fragment: &str, expected: &Value) -> bool {
+    serde_yaml::from_str::<Mapping>(&format!("k: {}", fragment))
+        .ok()
+        .and_then(|m| m.get("k").cloned())
+        .is_some_and(|v| &v == expected)
+}
+
+fn double_quoted(s: &str) -> String {
+    // A JSON string is a valid YAML double-quoted scalar
+    serde_json::to_string(s).unwrap_or_else(|_| format!("\"{}\"", s))
+}
+
+/// Render a string, keeping `quote` where it is still valid
+fn format_string(s: &str, quote: Quote, in_flow: bool) -> String {
+    let expected = Value::String(s.to_string());
+    match quote {
+        Quote::Double => double_quoted(s),
+        Quote::Single if !s.contains('\n') => format!("'{}'", s.replace('\'', "''")),
+        _ => {
+            let plain_ok = !s.is_empty()
+                && !s.contains('\n')
+                && if in_flow {
+                    parses_as(&format!("[{}]", s), &Value::Sequence(vec![expected.clone()]))
+                } else {
+                    parses_as(s, &expected)
+                };
+            if plain_ok {
+                s.to_string()
+            } else {
+                double_quoted(s)
+            }
+        }
+    }
+}
+
+/// Render a scalar value
+fn format_scalar(value: &Value, quote: Quote, in_flow: bool) -> Option<String> {
+    match value {
+        Value::Null => Some("null".to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(format_string(s, quote, in_flow)),
+        _ => None,
+    }
+}
+
+fn quote_of(text: &str) -> Quote {
+    match text.trim_start().chars().next() {
+        Some('"') => Quote::Double,
+        Some('\'') => Quote::Single,
+        _ => Quote::Plain,
+    }
+}
+
+impl Frontmatter {
+    /// Split card content into frontmatter lines and the surrounding text
+    pub fn parse(content: &str) -> Result<Self> {
+        let captures = FRONTMATTER_RE
+            .captures(content)
+            .ok_or_else(|| CueError::ParseError("No frontmatter found in card".to_string()))?;
+
+        let Some(yaml) = captures.get(1) else {
+            // Empty block: laid out like `Default`, with the closing `---` on its own line
+            let close = captures.get(0).unwrap().end() - 3;
+            let newline = if content[..close].ends_with("\r\n") { "\r\n" } else { "\n" };
+            return Ok(Self {
+                prefix: content[..close].to_string(),
+                lines: Vec::new(),
+                suffix: format!("{}{}", newline, &content[close..]),
+                newline,
+            });
+        };
+
+        let newline = if content[..yaml.start()].ends_with("\r\n") { "\r\n" } else { "\n" };
+        let mut lines: Vec<String> = yaml.as_str().lines().map(String::from).collect();
+        if lines.is_empty() {
+            // A single blank line, kept so the card round-trips unchanged
+            lines.push(String::new());
+        }
+        Ok(Self {
+            prefix: content[..yaml.start()].to_string(),
+            lines,
+            suffix: content[yaml.end()..].to_string(),
+            newline,
+        })
+    }
+
+    /// Parsed frontmatter, for reading values and diffing
+    pub fn mapping(&self) -> Result<Mapping> {
+        match serde_yaml::from_str::<Value>(&self.lines.join("\n"))
+            .map_err(|e| CueError::ParseError(e.to_string()))?
+        {
+            Value::Mapping(map) => Ok(map),
+            Value::Null => Ok(Mapping::new()),
+            _ => Err(CueError::ParseError("Frontmatter must be a mapping".to_string())),
+        }
+    }
+
+    fn find(&self, key: &str) -> Option<Entry> {
+        let start = self.lines.iter().position(|line| {
+            KEY_RE
+                .captures(line)
+                .is_some_and(|c| unquote_key(c.get(1).unwrap().as_str()) == key)
+        })?;
+
+        let line = &self.lines[start];
+        let caps = KEY_RE.captures(line).unwrap();
+        let key_text = caps.get(1).unwrap().as_str().to_string();
+        let after_colon = &line[line[..caps.get(0).unwrap().end()].trim_end().len()..];
+        let (inline, comment) = split_comment(after_colon.trim_start());
+
+        // Continuation: indented lines, blank lines and column-0 list items
+        let mut end = start + 1;
+        while end < self.lines.len() {
+            let next = &self.lines[end];
+            let continues = next.trim().is_empty()
+                || next.starts_with(char::is_whitespace)
+                || next.starts_with("- ")
+                || next == "-";
+            if !continues {
+                break;
+            }
+            end += 1;
+        }
+        // Trailing blank lines belong to the layout, not the value
+        while end > start + 1 && self.lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+
+        Some(Entry {
+            start,
+            end,
+            key_text,
+            inline: inline.to_string(),
+            comment: comment.to_string(),
+        })
+    }
+
+    /// Render `key: value` lines, following the style of `existing` if any
+    fn render(&self, key: &str, value: &Value, existing: Option<&Entry>) -> Vec<String> {
+        let key_text = existing.map_or_else(
+            || {
+                if parses_as_key(key) {
+                    key.to_string()
+                } else {
+                    double_quoted(key)
+                }
+            },
+            |e| e.key_text.clone(),
+        );
+        let comment = existing.map_or("", |e| e.comment.as_str());
+
+        match value {
+            Value::Sequence(items) => {
+                let flow = existing.is_some_and(|e| e.inline.starts_with('['));
+                let all_scalar = items.iter().all(|v| format_scalar(v, Quote::Plain, true).is_some());
+
+                if items.is_empty() || (flow && all_scalar) {
+                    let rendered: Vec<String> = items
+                        .iter()
+                        .filter_map(|v| format_scalar(v, Quote::Plain, true))
+                        .collect();
+                    return vec![format!("{}: [{}]{}", key_text, rendered.join(", "), comment)];
+                }
+
+                // Block style, keeping the existing item indentation
+                let indent = existing
+                    .and_then(|e| {
+                        self.lines[e.start + 1..e.end]
+                            .iter()
+                            .find(|l| l.trim_start().starts_with('-'))
+                            .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+                    })
+                    .unwrap_or_else(|| "  ".to_string());
+
+                let mut lines = vec![format!("{}:{}", key_text, comment)];
+                for item in items {
+                    match format_scalar(item, Quote::Plain, false) {
+                        Some(s) => lines.push(format!("{}- {}", indent, s)),
+                        None => lines.extend(nested_lines(&format!("{}- ", indent), item)),
+                    }
+                }
+                lines
+            }
+            Value::Mapping(_) | Value::Tagged(_) => {
+                let mut lines = vec![format!("{}:{}", key_text, comment)];
+                lines.extend(nested_lines("  ", value));
+                lines
+            }
+            scalar => {
+                let quote = match existing {
+                    Some(e) => quote_of(&e.inline),
+                    None if QUOTED_KEYS.contains(&key) && scalar.is_string() => Quote::Double,
+                    None => Quote::Plain,
+                };
+                let rendered = format_scalar(scalar, quote, false).unwrap_or_default();
+                vec![format!("{}: {}{}", key_text, rendered, comment)]
+            }
+        }
+    }
+
+    /// Set a top-level key, editing only its own lines
+    ///
+    /// New keys are appended at the end of the frontmatter.
+    pub fn set(&mut self, key: &str, value: &Value) {
+        match self.find(key) {
+            Some(entry) => {
+                let lines = self.render(key, value, Some(&entry));
+                self.lines.splice(entry.start..entry.end, lines);
+            }
+            None => {
+                let lines = self.render(key, value, None);
+                // Keep trailing blank lines after the new key
+                let insert_at = self
+                    .lines
+                    .iter()
+                    .rposition(|l| !l.trim().is_empty())
+                    .map_or(0, |i| i + 1);
+                self.lines.splice(insert_at..insert_at, lines);
+            }
+        }
+    }
+
+    /// Set a string value
+    pub fn set_str(&mut self, key: &str, value: &str) {
+        self.set(key, &Value::String(value.to_string()));
+    }
+
+    /// Remove a top-level key and its lines; returns whether it existed
+    pub fn remove(&mut self, key: &str) -> bool {
+        match self.find(key) {
+            Some(entry) => {
+                self.lines.drain(entry.start..entry.end);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Frontmatter {
+    /// Full content: frontmatter followed by the untouched body
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.lines.is_empty() {
+            // No lines: the closing `---` follows the opening one directly
+            let suffix = self.suffix.strip_prefix(self.newline).unwrap_or(&self.suffix);
+            return write!(f, "{}{}", self.prefix, suffix);
+        }
+        write!(f, "{}{}{}", self.prefix, self.lines.join(self.newline), self.suffix)
+    }
+}
+
+fn parses_as_key(key: &str) -> bool {
+    KEY_RE
+        .captures(&format!("{}: x", key))
+        .is_some_and(|c| c.get(1).unwrap().as_str() == key)
+}
+
+/// Serialize a nested value as indented block lines
+fn nested_lines(first_prefix: &str, value: &Value) -> Vec<String> {
+    let yaml = serde_yaml::to_string(value).unwrap_or_default();
+    let pad = " ".repeat(first_prefix.len());
+    yaml.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                format!("{}{}", first_prefix, line)
+            } else {
+                format!("{}{}", pad, line)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARD: &str = "---\ntitle: Login flow # keep me\n# Ownership\nassignee: \"@alice\"\ntags: [auth, api]\ndepends_on:\n- abc123\nstatus: todo\n---\n# Body\n\nText\n";
+
+    fn list(items: &[&str]) -> Value {
+        Value::Sequence(items.iter().map(|s| Value::String(s.to_string())).collect())
+    }
+
+    #[test]
+    fn test_set_only_touches_changed_lines() {
+        let mut fm = Frontmatter::parse(CARD).unwrap();
+        fm.set_str("status", "done");
+        fm.set_str("assignee", "@bob");
+        fm.set_str("title", "Login: v2");
+
+        let out = fm.to_string();
+        assert_eq!(
+            out,
+            "---\ntitle: \"Login: v2\" # keep me\n# Ownership\nassignee: \"@bob\"\ntags: [auth, api]\ndepends_on:\n- abc123\nstatus: done\n---\n# Body\n\nText\n"
+        );
+    }
+
+    #[test]
+    fn test_lists_keep_their_style() {
+        let mut fm = Frontmatter::parse(CARD).unwrap();
+        fm.set("tags", &list(&["auth", "api", "needs review"]));
+        fm.set("depends_on", &list(&["abc123", "def456"]));
+        fm.set("milestone", &Value::String("v1".to_string()));
+
+        let out = fm.to_string();
+        assert!(out.contains("tags: [auth, api, needs review]\n"));
+        assert!(out.contains("depends_on:\n- abc123\n- def456\nstatus: todo\nmilestone: \"v1\"\n---"));
+
+        let map = fm.mapping().unwrap();
+        assert_eq!(map.get("tags"), Some(&list(&["auth", "api", "needs review"])));
+    }
+
+    #[test]
+    fn test_remove_and_new_frontmatter() {
+        let mut fm = Frontmatter::parse(CARD).unwrap();
+        assert!(fm.remove("depends_on"));
+        assert!(!fm.remove("missing"));
+        assert!(!fm.to_string().contains("abc123"));
+
+        let mut fresh = Frontmatter::default();
+        fresh.set_str("title", "Fix: crash");
+        fresh.set_str("assignee", "@dev");
+        fresh.set("tags", &list(&["bug"]));
+        assert_eq!(
+            fresh.to_string(),
+            "---\ntitle: \"Fix: crash\"\nassignee: \"@dev\"\ntags:\n  - bug\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_scalars_that_need_quotes() {
+        let mut fm = Frontmatter::default();
+        for (key, value) in [("a", "true"), ("b", "123"), ("c", "# not a comment"), ("d", "2024-06-01")] {
+            fm.set_str(key, value);
+        }
+        let map = fm.mapping().unwrap();
+        for key in ["a", "b", "c", "d"] {
+            assert!(map.get(key).unwrap().is_string(), "{} should stay a string", key);
+        }
+        assert!(fm.to_string().contains("d: 2024-06-01"));
+    }
+
+    #[test]
+    fn test_empty_frontmatter_block() {
+        let card = "---\n---\n# Body\n";
+        let mut fm = Frontmatter::parse(card).unwrap();
+        assert!(fm.mapping().unwrap().is_empty());
+        assert_eq!(fm.to_string(), card);
+
+        fm.set_str("status", "todo");
+        assert_eq!(fm.to_string(), "---\nstatus: todo\n---\n# Body\n");
+        assert!(fm.remove("status"));
+        assert_eq!(fm.to_string(), card);
+
+        let ruled = "---\n---\nIntro\n---\nMore\n";
+        assert_eq!(Frontmatter::parse(ruled).unwrap().to_string(), ruled);
+        assert!(Frontmatter::parse(ruled).unwrap().mapping().unwrap().is_empty());
+
+        let blank = "---\n\n---\nText\n";
+        assert_eq!(Frontmatter::parse(blank).unwrap().to_string(), blank);
+    }
+}
@@ -10,7 +10,7 @@ use petgraph::algo::{is_cyclic_directed, toposort};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Dependency graph built from document links
 pub struct DependencyGraph {
@@ -286,6 +286,21 @@ impl DependencyGraph {
             .collect()
     }
 
+    /// Documents that link to `path` (incoming edges), sorted
+    pub fn backlinks(&self, path: &Path) -> Vec<PathBuf> {
+        let Some(&node) = self.path_to_node.get(path) else {
+            return Vec::new();
+        };
+        let mut sources: Vec<PathBuf> = self
+            .graph
+            .neighbors_directed(node, petgraph::Direction::Incoming)
+            .map(|n| self.graph[n].clone())
+            .collect();
+        sources.sort();
+        sources.dedup();
+        sources
+    }
+
     /// Get graph statistics
     pub fn stats(&self) -> GraphStats {
         GraphStats {
@@ -389,4 +404,22 @@ mod tests {
         // Both a.md and orphan.md have no incoming edges
         assert_eq!(orphans.len(), 2);
     }
+
+    #[test]
+    fn test_backlinks() {
+        let docs = vec![
+            create_doc("a.md", vec!["c".to_string()]),
+            create_doc("b.md", vec!["c.md".to_string(), "a".to_string()]),
+            create_doc("c.md", vec![]),
+        ];
+
+        let graph = DependencyGraph::build(&docs).unwrap();
+
+        assert_eq!(
+            graph.backlinks(Path::new("c.md")),
+            vec![PathBuf::from("a.md"), PathBuf::from("b.md")]
+        );
+        assert!(graph.backlinks(Path::new("b.md")).is_empty());
+        assert!(graph.backlinks(Path::new("missing.md")).is_empty());
+    }
 }
@@ -0,0 +1,605 @@
+//! Import cards from other trackers
+//!
+//! Reads GitHub issues JSON (`gh issue list --json ...`), Jira CSV exports and
+//! generic CSV files with a column mapping. Each imported card records where
+//! it came from in `external_id` (e.g. `github#12`, `jira:PROJ-7`), so a
+//! re-run skips cards that were already imported instead of duplicating them.
+
+use crate::tasks::{create_task_with_options, list_tasks_filtered, update_task, NewTaskOptions};
+use crate::task_filters::TaskFilters;
+use cue_common::{CueError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Fields a generic CSV column can be mapped onto
+pub const CSV_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "assignee",
+    "tags",
+    "depends_on",
+];
+
+/// Source format of an import file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    /// JSON array from `gh issue list --json number,title,body,labels,assignees,state`
+    Github,
+    /// Jira "Export CSV (all fields)"
+    Jira,
+    /// Any CSV, columns mapped onto card fields
+    Csv,
+}
+
+impl ImportFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "github" => Ok(Self::Github),
+            "jira" => Ok(Self::Jira),
+            "csv" => Ok(Self::Csv),
+            other => Err(CueError::ValidationError(format!(
+                "Invalid import format '{}'. Must be: github, jira or csv",
+                other
+            ))),
+        }
+    }
+}
+
+/// Options for `import_file`
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Card field -> CSV column header, for `ImportFormat::Csv`. Unmapped
+    /// fields use a column with the field's own name, if any.
+    pub mapping: BTreeMap<String, String>,
+    /// Report what would be imported without writing anything
+    pub dry_run: bool,
+}
+
+/// One imported (or already present) card
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedCard {
+    pub external_id: String,
+    /// Card ID (empty for a dry run)
+    pub id: String,
+    pub title: String,
+}
+
+/// Result of `import_file`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    pub created: Vec<ImportedCard>,
+    /// Records whose `external_id` already exists in the workspace
+    pub skipped: Vec<ImportedCard>,
+    /// Dependency links added (or that would be added)
+    pub links: usize,
+    /// Values that could not be mapped and references that could not be resolved
+    pub warnings: Vec<String>,
+}
+
+/// A tracker item normalized to card fields
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ImportRecord {
+    external_id: String,
+    /// Other names the export uses to refer to this item (e.g. Jira issue id)
+    aliases: Vec<String>,
+    title: String,
+    description: Option<String>,
+    status: Option<String>,
+    priority: Option<String>,
+    assignee: Option<String>,
+    tags: Vec<String>,
+    /// References to other records (as the export names them)
+    depends_on: Vec<String>,
+}
+
+/// Import cards from `path`
+///
+/// Cards are created with their description and `external_id` in one write;
+/// status is applied afterwards so workflow transitions are checked.
+/// Dependencies are linked once every record has a card, so forward references
+/// work. Values that don't fit the workspace (unknown priorities, disallowed
+/// statuses, unresolved references) become warnings rather than failing the
+/// whole import.
+pub fn import_file(
+    workspace_root: &Path,
+    path: &Path,
+    format: ImportFormat,
+    options: &ImportOptions,
+) -> Result<ImportReport> {
+    let content = fs::read_to_string(path)?;
+    let records = match format {
+        ImportFormat::Github => parse_github(&content)?,
+        ImportFormat::Jira => parse_jira(&content)?,
+        ImportFormat::Csv => parse_generic_csv(&content, &options.mapping)?,
+    };
+    import_records(workspace_root, records, options.dry_run)
+}
+
+fn import_records(
+    workspace_root: &Path,
+    records: Vec<ImportRecord>,
+    dry_run: bool,
+) -> Result<ImportReport> {
+    let config = cue_config::Config::load(workspace_root)?;
+    let mut report = ImportReport::default();
+
+    // Cards imported by earlier runs, by external ID
+    let filters = TaskFilters {
+        include_archived: true,
+        ..Default::default()
+    };
+    let mut card_ids: HashMap<String, String> = HashMap::new();
+    let mut linked: HashMap<String, Vec<String>> = HashMap::new();
+    for doc in list_tasks_filtered(workspace_root, &filters)? {
+        let Some(meta) = doc.frontmatter else { continue };
+        let id = doc.path.file_stem().and_then(|s| s.to_str()).map(String::from);
+        if let (Some(external), Some(id)) = (meta.external_id, id) {
+            linked.insert(id.clone(), meta.depends_on.unwrap_or_default());
+            card_ids.insert(external, id);
+        }
+    }
+
+    for record in &records {
+        if let Some(id) = card_ids.get(&record.external_id) {
+            report.skipped.push(ImportedCard {
+                external_id: record.external_id.clone(),
+                id: id.clone(),
+                title: record.title.clone(),
+            });
+            continue;
+        }
+
+        let priority = record.priority.as_deref().and_then(|p| {
+            let level = config.priorities.resolve(p).map(String::from);
+            if level.is_none() {
+                report.warnings.push(format!(
+                    "{}: unknown priority '{}', using '{}'",
+                    record.external_id, p, config.priorities.default
+                ));
+            }
+            level
+        });
+
+        let id = if dry_run {
+            String::new()
+        } else {
+            let options = NewTaskOptions {
+                tags: (!record.tags.is_empty()).then(|| record.tags.clone()),
+                priority,
+                assignee: record.assignee.clone(),
+                external_id: Some(record.external_id.clone()),
+                description: record
+                    .description
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|d| !d.is_empty())
+                    .map(String::from),
+                ..Default::default()
+            };
+            let path = create_task_with_options(workspace_root, &record.title, &options)?;
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        if let Some(status) = &record.status {
+            match map_status(status, &config.workflow) {
+                Some(mapped) if !dry_run && mapped != "todo" => {
+                    let mut update = serde_json::Map::new();
+                    update.insert("status".to_string(), mapped.into());
+                    if let Err(e) = update_task(workspace_root, &id, update) {
+                        report.warnings.push(format!("{}: {}", record.external_id, e));
+                    }
+                }
+                Some(_) => {}
+                None => report.warnings.push(format!(
+                    "{}: unknown status '{}', left as 'todo'",
+                    record.external_id, status
+                )),
+            }
+        }
+
+        card_ids.insert(record.external_id.clone(), id.clone());
+        report.created.push(ImportedCard {
+            external_id: record.external_id.clone(),
+            id,
+            title: record.title.clone(),
+        });
+    }
+
+    // Resolve references by external ID or any alias the export uses
+    let mut references: HashMap<&str, &str> = HashMap::new();
+    for record in &records {
+        references.insert(&record.external_id, &record.external_id);
+        for alias in &record.aliases {
+            references.insert(alias, &record.external_id);
+        }
+    }
+
+    for record in &records {
+        let id = &card_ids[&record.external_id];
+        let existing = linked.get(id).map(Vec::as_slice).unwrap_or_default();
+        let mut deps = Vec::new();
+        for reference in &record.depends_on {
+            let external = references
+                .get(reference.as_str())
+                .copied()
+                .unwrap_or(reference.as_str());
+            match card_ids.get(external) {
+                // Links from an earlier run are already in place
+                Some(dep) if existing.contains(dep) => {}
+                Some(dep) => deps.push(dep.clone()),
+                None => report.warnings.push(format!(
+                    "{}: dependency '{}' was not found in the import or the workspace",
+                    record.external_id, reference
+                )),
+            }
+        }
+        if deps.is_empty() {
+            continue;
+        }
+
+        report.links += deps.len();
+        if dry_run {
+            continue;
+        }
+        let mut update = serde_json::Map::new();
+        update.insert("depends_on".to_string(), serde_json::json!({ "add": deps }));
+        if let Err(e) = update_task(workspace_root, id, update) {
+            report.links -= deps.len();
+            report.warnings.push(format!("{}: {}", record.external_id, e));
+        }
+    }
+
+    Ok(report)
+}
+
+/// Map a tracker status onto the workspace workflow
+///
+/// Known statuses are used as-is (case-insensitive, spaces as dashes); common
+/// tracker names for open and closed work map to `todo` and `done`.
+fn map_status(status: &str, workflow: &cue_config::WorkflowConfig) -> Option<String> {
+    let normalized = status.trim().to_lowercase().replace([' ', '_'], "-");
+    let mapped = match normalized.as_str() {
+        s if workflow.is_known(s) => s,
+        "open" | "to-do" | "backlog" | "new" | "selected-for-development" => "todo",
+        "closed" | "resolved" | "complete" | "completed" | "merged" => "done",
+        _ => return None,
+    };
+    workflow.is_known(mapped).then(|| mapped.to_string())
+}
+
+#[derive(Deserialize)]
+struct GithubIssue {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    labels: Vec<GithubName>,
+    #[serde(default)]
+    assignees: Vec<GithubLogin>,
+}
+
+#[derive(Deserialize)]
+struct GithubName {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GithubLogin {
+    login: String,
+}
+
+/// Parse `gh issue list --json` output
+///
+/// Labels become tags, except `priority:<level>` / `priority/<level>` labels
+/// which set the priority. "Depends on #N" and "Blocked by #N" in the body
+/// become dependencies.
+fn parse_github(content: &str) -> Result<Vec<ImportRecord>> {
+    let issues: Vec<GithubIssue> = serde_json::from_str(content)
+        .map_err(|e| CueError::ParseError(format!("Invalid GitHub issues JSON: {}", e)))?;
+    let dep_re = regex::Regex::new(r"(?i)\b(?:depends on|blocked by)\s+#(\d+)")
+        .map_err(|e| CueError::ParseError(e.to_string()))?;
+
+    Ok(issues
+        .into_iter()
+        .map(|issue| {
+            let mut priority = None;
+            let mut tags = Vec::new();
+            for label in issue.labels {
+                let level = label
+                    .name
+                    .strip_prefix("priority:")
+                    .or_else(|| label.name.strip_prefix("priority/"));
+                match level {
+                    Some(level) => priority = Some(level.trim().to_string()),
+                    None => tags.push(label.name),
+                }
+            }
+
+            let body = issue.body.unwrap_or_default();
+            let depends_on = dep_re
+                .captures_iter(&body)
+                .map(|c| format!("github#{}", &c[1]))
+                .collect();
+
+            ImportRecord {
+                external_id: format!("github#{}", issue.number),
+                aliases: vec![format!("#{}", issue.number)],
+                title: issue.title,
+                description: Some(body),
+                status: issue.state,
+                priority,
+                assignee: issue.assignees.into_iter().next().map(|a| a.login),
+                tags,
+                depends_on,
+            }
+        })
+        .collect())
+}
+
+/// CSV rows keyed by header; repeated headers (Jira's `Labels`) keep every value
+struct CsvTable {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl CsvTable {
+    fn parse(content: &str) -> Result<Self> {
+        let mut records = crate::csv::parse(content)?.into_iter();
+        let headers = records
+            .next()
+            .ok_or_else(|| CueError::ParseError("CSV file is empty".to_string()))?
+            .into_iter()
+            .map(|h| h.trim().to_string())
+            .collect();
+        Ok(Self {
+            headers,
+            rows: records.collect(),
+        })
+    }
+
+    fn has_column(&self, name: &str) -> bool {
+        self.headers.iter().any(|h| h.eq_ignore_ascii_case(name))
+    }
+
+    /// Non-empty values in `row` under every column named `name`
+    fn values<'a>(&'a self, row: &'a [String], name: &str) -> Vec<&'a str> {
+        self.headers
+            .iter()
+            .zip(row)
+            .filter(|(h, v)| h.eq_ignore_ascii_case(name) && !v.trim().is_empty())
+            .map(|(_, v)| v.trim())
+            .collect()
+    }
+
+    fn value<'a>(&'a self, row: &'a [String], name: &str) -> Option<&'a str> {
+        self.values(row, name).into_iter().next()
+    }
+}
+
+/// Split a cell holding several values (`a, b` or `a;b`)
+fn split_list(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+}
+
+/// Parse a Jira CSV export
+///
+/// Uses `Issue key`, `Summary`, `Description`, `Status`, `Priority`,
+/// `Assignee` and every `Labels` column. `Inward issue link (Blocks)` lists
+/// issues blocking this one; `Outward issue link (Blocks)` lists issues this
+/// one blocks. Links may name issues by key or by `Issue id`.
+fn parse_jira(content: &str) -> Result<Vec<ImportRecord>> {
+    let table = CsvTable::parse(content)?;
+    for required in ["Issue key", "Summary"] {
+        if !table.has_column(required) {
+            return Err(CueError::ParseError(format!(
+                "Jira CSV is missing the '{}' column",
+                required
+            )));
+        }
+    }
+
+    let mut records: Vec<ImportRecord> = Vec::new();
+    let mut blocks: Vec<(String, String)> = Vec::new();
+
+    for row in &table.rows {
+        let Some(key) = table.value(row, "Issue key") else {
+            continue;
+        };
+        let external_id = format!("jira:{}", key);
+
+        for blocked in table.values(row, "Outward issue link (Blocks)") {
+            for blocked in split_list(blocked) {
+                blocks.push((blocked, external_id.clone()));
+            }
+        }
+
+        let mut aliases = vec![key.to_string()];
+        aliases.extend(table.value(row, "Issue id").map(String::from));
+
+        records.push(ImportRecord {
+            external_id,
+            aliases,
+            title: table.value(row, "Summary").unwrap_or(key).to_string(),
+            description: table.value(row, "Description").map(String::from),
+            status: table.value(row, "Status").map(String::from),
+            priority: table.value(row, "Priority").map(String::from),
+            assignee: table.value(row, "Assignee").map(String::from),
+            tags: table.values(row, "Labels").into_iter().flat_map(split_list).collect(),
+            depends_on: table
+                .values(row, "Inward issue link (Blocks)")
+                .into_iter()
+                .flat_map(split_list)
+                .collect(),
+        });
+    }
+
+    // "A blocks B" on A's row means B depends on A
+    for (blocked, blocker) in blocks {
+        let target = records
+            .iter_mut()
+            .find(|r| r.external_id == blocked || r.aliases.contains(&blocked));
+        if let Some(target) = target {
+            if !target.depends_on.contains(&blocker) {
+                target.depends_on.push(blocker);
+            }
+        }
+    }
+
+    Ok(records)
+}
+
+/// Parse a CSV with columns mapped onto card fields
+///
+/// Only `title` is required. Without an `id` column the title identifies the
+/// row for re-runs; `depends_on` cells name other rows by that identifier.
+fn parse_generic_csv(content: &str, mapping: &BTreeMap<String, String>) -> Result<Vec<ImportRecord>> {
+    if let Some(field) = mapping.keys().find(|f| !CSV_FIELDS.contains(&f.as_str())) {
+        return Err(CueError::ValidationError(format!(
+            "Unknown import field '{}'. Expected one of: {}",
+            field,
+            CSV_FIELDS.join(", ")
+        )));
+    }
+
+    let table = CsvTable::parse(content)?;
+    let column = |field: &str| mapping.get(field).map(String::as_str).unwrap_or(field).to_string();
+    for (field, header) in mapping {
+        if !table.has_column(header) {
+            return Err(CueError::ValidationError(format!(
+                "Column '{}' (mapped to {}) is not in the CSV header",
+                header, field
+            )));
+        }
+    }
+    if !table.has_column(&column("title")) {
+        return Err(CueError::ValidationError(format!(
+            "CSV has no '{}' column; map one with title=<column>",
+            column("title")
+        )));
+    }
+
+    let cell = |row: &[String], field: &str| table.value(row, &column(field)).map(String::from);
+    let mut records = Vec::new();
+    for row in &table.rows {
+        let Some(title) = cell(row, "title") else {
+            continue;
+        };
+        let source = cell(row, "id").unwrap_or_else(|| title.clone());
+
+        records.push(ImportRecord {
+            external_id: format!("csv:{}", source),
+            aliases: vec![source],
+            title,
+            description: cell(row, "description"),
+            status: cell(row, "status"),
+            priority: cell(row, "priority"),
+            assignee: cell(row, "assignee"),
+            tags: cell(row, "tags").as_deref().map(|t| split_list(t).collect()).unwrap_or_default(),
+            depends_on: cell(row, "depends_on")
+                .as_deref()
+                .map(|d| split_list(d).collect())
+                .unwrap_or_default(),
+        });
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn test_parse_github_issues() {
+        let json = r#"[
+            {"number": 1, "title": "Auth", "body": "", "state": "OPEN",
+             "labels": [{"name": "backend"}, {"name": "priority:high"}],
+             "assignees": [{"login": "dana"}]},
+            {"number": 2, "title": "Login UI", "body": "Depends on #1.", "state": "CLOSED",
+             "labels": [], "assignees": []}
+        ]"#;
+
+        let records = parse_github(json).unwrap();
+        assert_eq!(records[0].tags, vec!["backend"]);
+        assert_eq!(records[0].priority.as_deref(), Some("high"));
+        assert_eq!(records[0].assignee.as_deref(), Some("dana"));
+        assert_eq!(records[1].depends_on, vec!["github#1"]);
+    }
+
+    #[test]
+    fn test_parse_jira_links_and_labels() {
+        let csv = "Summary,Issue key,Issue id,Status,Labels,Labels,Outward issue link (Blocks),Inward issue link (Blocks)\n\
+                   Schema,PROJ-1,1001,Done,db,,PROJ-2,\n\
+                   API,PROJ-2,1002,In Progress,api,backend,,\n\
+                   Docs,PROJ-3,1003,To Do,,,,1002\n";
+
+        let records = parse_jira(csv).unwrap();
+        assert_eq!(records[1].tags, vec!["api", "backend"]);
+        assert_eq!(records[1].depends_on, vec!["jira:PROJ-1"]);
+        assert_eq!(records[2].depends_on, vec!["1002"]);
+    }
+
+    #[test]
+    fn test_import_is_rerunnable() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child(".cuedeck/cards").create_dir_all().unwrap();
+        let file = temp.child("backlog.csv");
+        file.write_str("Key,Name,Labels,Needs,Notes\nA,Schema,db,,Design tables\nB,API,\"api, backend\",A,\n")
+            .unwrap();
+
+        let mut mapping = BTreeMap::new();
+        mapping.insert("id".to_string(), "Key".to_string());
+        mapping.insert("title".to_string(), "Name".to_string());
+        mapping.insert("tags".to_string(), "Labels".to_string());
+        mapping.insert("depends_on".to_string(), "Needs".to_string());
+        mapping.insert("description".to_string(), "Notes".to_string());
+        let mut options = ImportOptions { mapping, dry_run: true };
+
+        let preview = import_file(temp.path(), file.path(), ImportFormat::Csv, &options).unwrap();
+        assert_eq!(preview.created.len(), 2);
+        assert_eq!(preview.links, 1);
+        assert!(!temp.child(".cuedeck/cards").read_dir().unwrap().any(|_| true));
+
+        options.dry_run = false;
+        let report = import_file(temp.path(), file.path(), ImportFormat::Csv, &options).unwrap();
+        assert_eq!(report.created.len(), 2);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+
+        let schema = &report.created[0].id;
+        let api = crate::parse_file(temp.child(format!(".cuedeck/cards/{}.md", report.created[1].id)).path())
+            .unwrap()
+            .frontmatter
+            .unwrap();
+        assert_eq!(api.external_id.as_deref(), Some("csv:B"));
+        assert_eq!(api.tags, Some(vec!["api".to_string(), "backend".to_string()]));
+        assert_eq!(api.depends_on, Some(vec![schema.clone()]));
+        let body = fs::read_to_string(temp.child(format!(".cuedeck/cards/{}.md", schema)).path()).unwrap();
+        assert!(body.contains("Design tables"));
+        assert!(!body.contains("[Add description]"));
+
+        // Source and description are part of the initial write
+        let history = crate::activity::task_history(temp.path(), schema).unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(history[0].changes.iter().any(|c| c.field == "external_id"));
+
+        let rerun = import_file(temp.path(), file.path(), ImportFormat::Csv, &options).unwrap();
+        assert!(rerun.created.is_empty());
+        assert_eq!(rerun.skipped.len(), 2);
+    }
+}
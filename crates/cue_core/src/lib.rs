@@ -9,18 +9,40 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub mod activity;
+pub mod archive;
+pub mod branches;
+pub mod bulk;
 pub mod cache;
+pub mod claims;
+pub mod card_ids;
+pub mod commits;
 pub mod consistency;
 pub mod context;
+pub mod digest;
+pub mod csv;
 pub mod doctor;
 pub mod embedding_cache;
 pub mod embeddings;
 pub mod engine;
+pub mod export;
+pub mod frontmatter;
 pub mod graph;
 pub mod graph_viz;
+pub mod import;
+pub mod metrics;
+pub mod milestones;
+pub mod query;
+pub mod recurrence;
+pub mod rename;
 pub mod task_filters;
 pub mod task_graph;
+pub mod task_hierarchy;
 pub mod tasks;
+pub mod templates;
+pub mod timelog;
+pub mod trash;
+pub mod workload;
 
 // Re-exports
 pub use context::search_workspace;
@@ -30,6 +52,108 @@ pub use task_filters::{TaskFilters, DateFilter, DateOperator, DateValue};
 // Re-export commonly used functions
 pub use context::save_embedding_cache;
 
+/// SHA256 hash of file content, as stored in `Document::hash`
+pub fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Replace a file's content via a temp file and rename
+///
+/// Readers see either the old or the new content, never a partial write.
+pub fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    static TMP_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("card");
+    let n = TMP_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let tmp = path.with_file_name(format!(".{}.{}.{}.tmp", file_name, std::process::id(), n));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// How long `FileLock::acquire` waits for another writer
+const LOCK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Exclusive lock on a file, held as an OS lock on a `.<name>.lock` file next to it
+///
+/// The OS releases the lock when its holder exits, so a crashed writer never
+/// leaves a stale lock behind. The lock file is removed when the lock is
+/// dropped; a waiter that locked the removed file retries on the new one.
+pub(crate) struct FileLock {
+    path: PathBuf,
+    _file: fs::File,
+}
+
+impl FileLock {
+    /// Take the lock for `target`, retrying briefly while another writer has it
+    pub(crate) fn acquire(target: &Path) -> std::io::Result<FileLock> {
+        let file_name = target.file_name().and_then(|n| n.to_str()).unwrap_or("card");
+        let path = target.with_file_name(format!(".{}.lock", file_name));
+        let start = std::time::Instant::now();
+
+        loop {
+            let file = fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)?;
+            match file.try_lock() {
+                Ok(()) if is_same_file(&file, &path) => {
+                    return Ok(FileLock { path, _file: file })
+                }
+                Ok(()) => continue,
+                Err(fs::TryLockError::WouldBlock) => {}
+                Err(fs::TryLockError::Error(e)) => return Err(e),
+            }
+
+            if start.elapsed() > LOCK_TIMEOUT {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!("Timed out waiting for lock {}", path.display()),
+                ));
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+}
+
+/// Whether `path` still names the open `file`, i.e. the previous holder
+/// did not remove it between our open and our lock
+#[cfg(unix)]
+fn is_same_file(file: &fs::File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_file: &fs::File, path: &Path) -> bool {
+    path.exists()
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Remove the file while still holding the lock; it is released
+        // when `_file` is closed right after.
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
 /// Parse a markdown file into a Document
 #[tracing::instrument(skip_all, fields(path = ?path))]
 pub fn parse_file(path: &Path) -> Result<Document> {
@@ -42,10 +166,7 @@ pub fn parse_file(path: &Path) -> Result<Document> {
         path: path_str.clone(),
     })?;
 
-    // Calculate SHA256 hash
-    let mut hasher = Sha256::new();
-    hasher.update(content.as_bytes());
-    let hash = format!("{:x}", hasher.finalize());
+    let hash = content_hash(&content);
 
     // Regex for Frontmatter: ^---\n(content)\n---
     // Using simple string splitting for less dev-dependency overhead/complexity if possible,
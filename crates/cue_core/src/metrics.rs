@@ -0,0 +1,347 @@
+//! Delivery trends: burndown, weekly throughput and cycle time
+//!
+//! A card opens at its `created` timestamp and closes when it last moved into
+//! a terminal status, as recorded in the activity log. Cards closed before
+//! the activity log existed fall back to their `updated` timestamp.
+
+use crate::activity::read_events;
+use crate::task_filters::TaskFilters;
+use crate::tasks::list_tasks_filtered;
+use crate::timelog::TimeGrouping;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use cue_common::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Open and closed cards at the end of one day
+#[derive(Debug, Clone, Serialize)]
+pub struct BurndownPoint {
+    /// YYYY-MM-DD
+    pub date: String,
+    /// Cards created by then and not yet closed
+    pub open: usize,
+    /// Cards closed since the start of the range
+    pub closed: usize,
+}
+
+/// Cards closed in one week
+#[derive(Debug, Clone, Serialize)]
+pub struct WeeklyThroughput {
+    /// Monday of the week (YYYY-MM-DD)
+    pub week: String,
+    pub closed: usize,
+}
+
+/// Cycle time (created to closed) percentiles for one assignee or tag
+#[derive(Debug, Clone, Serialize)]
+pub struct CycleTimeBucket {
+    pub key: String,
+    pub cards: usize,
+    pub p50_seconds: i64,
+    pub p85_seconds: i64,
+    pub p95_seconds: i64,
+}
+
+/// Trends over `from..=to`
+#[derive(Debug, Clone, Serialize)]
+pub struct VelocityReport {
+    pub from: String,
+    pub to: String,
+    /// One point per day
+    pub burndown: Vec<BurndownPoint>,
+    /// One entry per week overlapping the range
+    pub throughput: Vec<WeeklyThroughput>,
+    /// Cards closed in the range, grouped, largest group first
+    pub cycle_time: Vec<CycleTimeBucket>,
+    /// Cards closed in the range, ungrouped; `None` if none were
+    pub overall: Option<CycleTimeBucket>,
+}
+
+/// When a card opened and closed
+struct Timeline {
+    created: Option<DateTime<Utc>>,
+    closed: Option<DateTime<Utc>>,
+    keys: Vec<String>,
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn timelines(workspace_root: &Path, by: TimeGrouping) -> Result<Vec<Timeline>> {
+    let workflow = cue_config::Config::load(workspace_root)?.workflow;
+
+    // Replay status changes: the close time is the last move into a terminal
+    // status that was not followed by a reopen
+    let mut created_at: HashMap<String, DateTime<Utc>> = HashMap::new();
+    let mut closed_at: HashMap<String, Option<DateTime<Utc>>> = HashMap::new();
+    for event in read_events(workspace_root)? {
+        let Some(at) = parse_time(&event.timestamp) else {
+            continue;
+        };
+        if event.action == "created" {
+            created_at.entry(event.task_id.clone()).or_insert(at);
+        }
+        for change in event.changes.iter().filter(|c| c.field == "status") {
+            let status = change.new.as_ref().and_then(|v| v.as_str()).unwrap_or_default();
+            let closed = closed_at.entry(event.task_id.clone()).or_default();
+            if !workflow.is_terminal(status) {
+                *closed = None;
+            } else if closed.is_none() {
+                *closed = Some(at);
+            }
+        }
+    }
+
+    let filters = TaskFilters {
+        include_archived: true,
+        ..Default::default()
+    };
+    Ok(list_tasks_filtered(workspace_root, &filters)?
+        .into_iter()
+        .filter_map(|doc| {
+            let id = doc.path.file_stem()?.to_str()?.to_string();
+            let meta = doc.frontmatter?;
+            let created = meta
+                .created
+                .as_deref()
+                .and_then(parse_time)
+                .or_else(|| created_at.get(&id).copied());
+            let closed = workflow.is_terminal(&meta.status).then(|| {
+                closed_at
+                    .get(&id)
+                    .copied()
+                    .flatten()
+                    .or_else(|| meta.updated.as_deref().and_then(parse_time))
+            });
+            let keys = match by {
+                TimeGrouping::Assignee => vec![meta
+                    .assignee
+                    .filter(|a| !a.is_empty())
+                    .unwrap_or_else(|| "(unassigned)".to_string())],
+                TimeGrouping::Tag => meta
+                    .tags
+                    .filter(|t| !t.is_empty())
+                    .unwrap_or_else(|| vec!["(untagged)".to_string()]),
+            };
+            Some(Timeline {
+                created,
+                closed: closed.flatten(),
+                keys,
+            })
+        })
+        .collect())
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[i64], p: usize) -> i64 {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn cycle_bucket(key: String, mut seconds: Vec<i64>) -> CycleTimeBucket {
+    seconds.sort_unstable();
+    CycleTimeBucket {
+        key,
+        cards: seconds.len(),
+        p50_seconds: percentile(&seconds, 50),
+        p85_seconds: percentile(&seconds, 85),
+        p95_seconds: percentile(&seconds, 95),
+    }
+}
+
+/// Burndown, throughput and cycle time for `from..=to` (UTC days)
+///
+/// Cycle times are grouped by assignee or by tag (a card counts once per tag).
+pub fn velocity_report(
+    workspace_root: &Path,
+    from: NaiveDate,
+    to: NaiveDate,
+    by: TimeGrouping,
+) -> Result<VelocityReport> {
+    let cards = timelines(workspace_root, by)?;
+    let in_range = |t: DateTime<Utc>| (from..=to).contains(&t.date_naive());
+
+    let burndown = from
+        .iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| {
+            let done_by = |t: &DateTime<Utc>| t.date_naive() <= day;
+            BurndownPoint {
+                date: day.to_string(),
+                open: cards
+                    .iter()
+                    .filter(|c| c.created.is_some_and(|t| done_by(&t)))
+                    .filter(|c| !c.closed.is_some_and(|t| done_by(&t)))
+                    .count(),
+                closed: cards
+                    .iter()
+                    .filter(|c| c.closed.is_some_and(|t| t.date_naive() >= from && done_by(&t)))
+                    .count(),
+            }
+        })
+        .collect();
+
+    let mut weeks: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    let mut monday = from - Duration::days(from.weekday().num_days_from_monday() as i64);
+    while monday <= to {
+        weeks.insert(monday, 0);
+        monday += Duration::weeks(1);
+    }
+    let mut cycles: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+    let mut all_cycles = Vec::new();
+    for card in &cards {
+        let Some(closed) = card.closed.filter(|t| in_range(*t)) else {
+            continue;
+        };
+        let day = closed.date_naive();
+        let week = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+        *weeks.entry(week).or_default() += 1;
+
+        let Some(cycle) = card.created.map(|t| (closed - t).num_seconds()).filter(|s| *s >= 0) else {
+            continue;
+        };
+        all_cycles.push(cycle);
+        for key in &card.keys {
+            cycles.entry(key.clone()).or_default().push(cycle);
+        }
+    }
+
+    let mut cycle_time: Vec<CycleTimeBucket> = cycles
+        .into_iter()
+        .map(|(key, seconds)| cycle_bucket(key, seconds))
+        .collect();
+    cycle_time.sort_by(|a, b| b.cards.cmp(&a.cards).then_with(|| a.key.cmp(&b.key)));
+
+    Ok(VelocityReport {
+        from: from.to_string(),
+        to: to.to_string(),
+        burndown,
+        throughput: weeks
+            .into_iter()
+            .map(|(week, closed)| WeeklyThroughput {
+                week: week.to_string(),
+                closed,
+            })
+            .collect(),
+        cycle_time,
+        overall: (!all_cycles.is_empty()).then(|| cycle_bucket("all".to_string(), all_cycles)),
+    })
+}
+
+/// A range boundary: `YYYY-MM-DD`, or a duration before `today` such as `30d`
+pub fn parse_day(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    crate::task_filters::parse_due_date(value).or_else(|| {
+        crate::task_filters::parse_relative_duration(value).map(|age| today - age)
+    })
+}
+
+/// Format a cycle time as days with one decimal, e.g. `3.5d`
+pub fn format_days(seconds: i64) -> String {
+    format!("{:.1}d", seconds as f64 / 86_400.0)
+}
+
+/// Horizontal bar chart, one `label  ███ value` row per entry
+///
+/// Bars are scaled so the largest value fills `width` columns.
+pub fn ascii_chart(rows: &[(String, f64, String)], width: usize) -> String {
+    let max = rows.iter().map(|(_, v, _)| *v).fold(0.0, f64::max);
+    let label_width = rows.iter().map(|(l, _, _)| l.chars().count()).max().unwrap_or(0);
+
+    rows.iter()
+        .map(|(label, value, shown)| {
+            let len = if max > 0.0 {
+                (value / max * width as f64).round() as usize
+            } else {
+                0
+            };
+            format!("{:<w$}  {} {}\n", label, "█".repeat(len), shown, w = label_width)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_velocity_report() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards
+            .child("aaa111.md")
+            .write_str("---\ntitle: A\nstatus: done\nassignee: dana\ncreated: 2024-06-03T09:00:00Z\n---\n")
+            .unwrap();
+        cards
+            .child("bbb222.md")
+            .write_str("---\ntitle: B\nstatus: done\nassignee: dana\ncreated: 2024-06-03T09:00:00Z\nupdated: 2024-06-11T09:00:00Z\n---\n")
+            .unwrap();
+        cards
+            .child("ccc333.md")
+            .write_str("---\ntitle: C\nstatus: active\ncreated: 2024-06-04T09:00:00Z\n---\n")
+            .unwrap();
+        // aaa111 was closed, reopened, then closed again on the 5th
+        temp.child(".cuedeck/activity.jsonl")
+            .write_str(concat!(
+                r#"{"timestamp":"2024-06-04T09:00:00Z","task_id":"aaa111","action":"updated","changes":[{"field":"status","old":"todo","new":"done"}]}"#,
+                "\n",
+                r#"{"timestamp":"2024-06-04T10:00:00Z","task_id":"aaa111","action":"updated","changes":[{"field":"status","old":"done","new":"active"}]}"#,
+                "\n",
+                r#"{"timestamp":"2024-06-05T09:00:00Z","task_id":"aaa111","action":"updated","changes":[{"field":"status","old":"active","new":"done"}]}"#,
+                "\n",
+            ))
+            .unwrap();
+
+        let report =
+            velocity_report(temp.path(), date("2024-06-03"), date("2024-06-12"), TimeGrouping::Assignee)
+                .unwrap();
+
+        assert_eq!(report.burndown.len(), 10);
+        assert_eq!((report.burndown[0].open, report.burndown[0].closed), (2, 0));
+        assert_eq!((report.burndown[1].open, report.burndown[1].closed), (3, 0));
+        assert_eq!((report.burndown[2].open, report.burndown[2].closed), (2, 1));
+        assert_eq!((report.burndown[9].open, report.burndown[9].closed), (1, 2));
+
+        let weeks: Vec<(&str, usize)> =
+            report.throughput.iter().map(|w| (w.week.as_str(), w.closed)).collect();
+        assert_eq!(weeks, vec![("2024-06-03", 1), ("2024-06-10", 1)]);
+
+        assert_eq!(report.cycle_time.len(), 1);
+        let dana = &report.cycle_time[0];
+        assert_eq!((dana.key.as_str(), dana.cards), ("dana", 2));
+        assert_eq!(dana.p50_seconds, 2 * 86_400);
+        assert_eq!(dana.p95_seconds, 8 * 86_400);
+    }
+
+    #[test]
+    fn test_parse_day() {
+        let today = date("2024-06-30");
+        assert_eq!(parse_day("2024-06-01", today), Some(date("2024-06-01")));
+        assert_eq!(parse_day("2w", today), Some(date("2024-06-16")));
+        assert_eq!(parse_day("soon", today), None);
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let values: Vec<i64> = (1..=20).collect();
+        assert_eq!(percentile(&values, 50), 10);
+        assert_eq!(percentile(&values, 85), 17);
+        assert_eq!(percentile(&values, 95), 19);
+        assert_eq!(percentile(&[7], 95), 7);
+    }
+
+    #[test]
+    fn test_ascii_chart_scales_to_width() {
+        let chart = ascii_chart(
+            &[("a".to_string(), 2.0, "2".to_string()), ("bb".to_string(), 4.0, "4".to_string())],
+            10,
+        );
+        assert_eq!(chart, format!("a   {} 2\nbb  {} 4\n", "█".repeat(5), "█".repeat(10)));
+    }
+}
@@ -0,0 +1,179 @@
+//! Milestone progress reporting
+//!
+//! Groups task cards by their `milestone` field and summarizes completion
+//! and overdue work per milestone.
+
+use crate::task_filters::parse_due_date;
+use crate::tasks::list_tasks;
+use cue_common::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// An open card that is past its due date
+#[derive(Debug, Clone, Serialize)]
+pub struct OverdueCard {
+    pub id: String,
+    pub title: String,
+    pub due: String,
+    /// Days past the due date
+    pub days_overdue: i64,
+}
+
+/// Completion summary for one milestone
+#[derive(Debug, Clone, Serialize)]
+pub struct MilestoneSummary {
+    pub name: String,
+    /// Cards in the milestone (archived cards are excluded)
+    pub total: usize,
+    /// Cards in a terminal status (`[workflow] terminal`)
+    pub done: usize,
+    /// Open cards past their due date, most overdue first
+    pub overdue: Vec<OverdueCard>,
+}
+
+impl MilestoneSummary {
+    /// Completion percentage (0-100)
+    pub fn percent_complete(&self) -> u32 {
+        if self.total == 0 {
+            return 0;
+        }
+        ((self.done * 100) / self.total) as u32
+    }
+}
+
+/// Summarize every milestone in the workspace, sorted by name
+pub fn milestone_report(workspace_root: &Path) -> Result<Vec<MilestoneSummary>> {
+    let today = chrono::Utc::now().date_naive();
+    let workflow = cue_config::Config::load(workspace_root)?.workflow;
+    let mut milestones: BTreeMap<String, MilestoneSummary> = BTreeMap::new();
+
+    for doc in list_tasks(workspace_root, None, None)? {
+        let Some(meta) = &doc.frontmatter else {
+            continue;
+        };
+        let Some(name) = &meta.milestone else {
+            continue;
+        };
+        if meta.status == "archived" {
+            continue;
+        }
+
+        let summary = milestones
+            .entry(name.clone())
+            .or_insert_with(|| MilestoneSummary {
+                name: name.clone(),
+                total: 0,
+                done: 0,
+                overdue: Vec::new(),
+            });
+
+        summary.total += 1;
+        let closed = workflow.is_terminal(&meta.status);
+        if closed {
+            summary.done += 1;
+        }
+
+        if let Some(due) = meta.due.as_deref() {
+            if let Some(due_date) = parse_due_date(due) {
+                let days_overdue = (today - due_date).num_days();
+                if days_overdue > 0 && !closed {
+                    summary.overdue.push(OverdueCard {
+                        id: doc
+                            .path
+                            .file_stem()
+                            .and_then(|s| s.to_str())
+                            .unwrap_or("unknown")
+                            .to_string(),
+                        title: meta.title.clone(),
+                        due: due.to_string(),
+                        days_overdue,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut report: Vec<MilestoneSummary> = milestones.into_values().collect();
+    for summary in &mut report {
+        summary
+            .overdue
+            .sort_by(|a, b| b.days_overdue.cmp(&a.days_overdue).then(a.id.cmp(&b.id)));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn test_milestone_report() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        cards
+            .child("aaaaaa.md")
+            .write_str("---\ntitle: Done\nstatus: done\nmilestone: v1\n---\n")
+            .unwrap();
+        cards
+            .child("bbbbbb.md")
+            .write_str("---\ntitle: Late\nstatus: active\nmilestone: v1\ndue: 2020-01-01\n---\n")
+            .unwrap();
+        cards
+            .child("cccccc.md")
+            .write_str("---\ntitle: Unplanned\nstatus: todo\n---\n")
+            .unwrap();
+
+        let report = milestone_report(temp.path()).unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].name, "v1");
+        assert_eq!((report[0].done, report[0].total), (1, 2));
+        assert_eq!(report[0].percent_complete(), 50);
+        assert_eq!(report[0].overdue.len(), 1);
+        assert_eq!(report[0].overdue[0].id, "bbbbbb");
+    }
+
+    #[test]
+    fn test_custom_terminal_status() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[workflow]\nstatuses = [\"todo\", \"shipped\"]\nterminal = [\"shipped\"]\n")
+            .unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards
+            .child("aaaaaa.md")
+            .write_str("---\ntitle: Shipped\nstatus: shipped\nmilestone: v1\ndue: 2020-01-01\n---\n")
+            .unwrap();
+        cards
+            .child("bbbbbb.md")
+            .write_str("---\ntitle: Late\nstatus: todo\nmilestone: v1\ndue: 2020-01-01\n---\n")
+            .unwrap();
+
+        let report = milestone_report(temp.path()).unwrap();
+        assert_eq!((report[0].done, report[0].total), (1, 2));
+        let overdue: Vec<&str> = report[0].overdue.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(overdue, vec!["bbbbbb"]);
+
+        // The overdue filter and `due:overdue` query agree
+        let ids = |filters: crate::task_filters::TaskFilters| -> Vec<String> {
+            crate::tasks::list_tasks_filtered(temp.path(), &filters)
+                .unwrap()
+                .iter()
+                .map(|d| d.path.file_stem().unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+        let by_flag = ids(crate::task_filters::TaskFilters {
+            due: Some(crate::task_filters::parse_due_filter("overdue").unwrap()),
+            ..Default::default()
+        });
+        let by_query = ids(crate::task_filters::TaskFilters {
+            query: Some(crate::query::Query::parse("due:overdue").unwrap()),
+            ..Default::default()
+        });
+        assert_eq!(by_flag, vec!["bbbbbb"]);
+        assert_eq!(by_query, vec!["bbbbbb"]);
+    }
+}
@@ -0,0 +1,585 @@
+//! Unified query language for tasks and documents
+//!
+//! One grammar is shared by `cue list -q`, `cue open -q`, `list_tasks` and
+//! `read_context`:
+//!
+//! ```text
+//! status:todo (tag:auth OR tag:api) -assignee:@bob updated>2w "login flow"
+//! ```
+//!
+//! Terms are joined by implicit AND. `OR` binds looser than AND, `NOT` or a
+//! leading `-` negates a term, and parentheses group. `field:value` compares a
+//! metadata field; date fields (`created`, `updated`, `due`) also accept `<` and
+//! `>` with the same values as the `--created`/`--due` flags. Bare words and
+//! quoted phrases match card titles and document text.
+
+use crate::task_filters::{
+    matches_date_filter, matches_date_filter_mtime, matches_due_filter, matches_tag_filter,
+    parse_date_filter, parse_due_filter, DateFilter,
+};
+use cue_common::{CueError, Document, Result};
+use cue_config::Config;
+
+/// Fields that can appear before `:` in a query
+pub const QUERY_FIELDS: &[&str] = &[
+    "status", "tag", "tags", "priority", "assignee", "parent", "milestone", "id", "title",
+    "created", "updated", "due",
+];
+
+/// A single comparison in a query
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    Status(String),
+    Tag(String),
+    Priority(String),
+    Assignee(String),
+    Parent(String),
+    Milestone(String),
+    /// ID prefix (case-insensitive)
+    Id(String),
+    /// Title substring (case-insensitive)
+    Title(String),
+    Created(DateFilter),
+    Updated(DateFilter),
+    Due(DateFilter),
+    /// Free text, matched against the title and file content
+    Text(String),
+}
+
+impl Predicate {
+    /// Field name as written in a query (`text` for free text)
+    pub fn field(&self) -> &'static str {
+        match self {
+            Predicate::Status(_) => "status",
+            Predicate::Tag(_) => "tag",
+            Predicate::Priority(_) => "priority",
+            Predicate::Assignee(_) => "assignee",
+            Predicate::Parent(_) => "parent",
+            Predicate::Milestone(_) => "milestone",
+            Predicate::Id(_) => "id",
+            Predicate::Title(_) => "title",
+            Predicate::Created(_) => "created",
+            Predicate::Updated(_) => "updated",
+            Predicate::Due(_) => "due",
+            Predicate::Text(_) => "text",
+        }
+    }
+}
+
+/// Parsed query AST
+#[derive(Debug, Clone)]
+pub enum Query {
+    /// Matches everything (empty query)
+    All,
+    And(Vec<Query>),
+    Or(Vec<Query>),
+    Not(Box<Query>),
+    Term(Predicate),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    /// Leading `-` on a term
+    Minus,
+    Word(String),
+    Phrase(String),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// Char offset of the token start
+    pos: usize,
+    /// Char length of the token, for the error caret
+    len: usize,
+}
+
+/// Build a parse error that points at the offending token
+fn query_error(input: &str, pos: usize, len: usize, message: &str) -> CueError {
+    CueError::ParseError(format!(
+        "Invalid query at column {}: {}\n  {}\n  {}{}",
+        pos + 1,
+        message,
+        input,
+        " ".repeat(pos),
+        "^".repeat(len.max(1))
+    ))
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        match c {
+            '(' => {
+                tokens.push(Token { kind: TokenKind::LParen, pos: start, len: 1 });
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token { kind: TokenKind::RParen, pos: start, len: 1 });
+                i += 1;
+            }
+            '-' if chars.get(i + 1).is_some_and(|n| !n.is_whitespace()) => {
+                tokens.push(Token { kind: TokenKind::Minus, pos: start, len: 1 });
+                i += 1;
+            }
+            '"' => {
+                i += 1;
+                let mut phrase = String::new();
+                while i < chars.len() && chars[i] != '"' {
+                    phrase.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(query_error(input, start, chars.len() - start, "Unterminated quote"));
+                }
+                i += 1;
+                tokens.push(Token { kind: TokenKind::Phrase(phrase), pos: start, len: i - start });
+            }
+            _ => {
+                // A word runs to whitespace or a paren; a quote right after
+                // `field:` makes the quoted text part of the value
+                let mut word = String::new();
+                while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '(' && chars[i] != ')' {
+                    if chars[i] == '"' && word.ends_with([':', '<', '>']) {
+                        let quote = i;
+                        i += 1;
+                        while i < chars.len() && chars[i] != '"' {
+                            word.push(chars[i]);
+                            i += 1;
+                        }
+                        if i >= chars.len() {
+                            return Err(query_error(input, quote, chars.len() - quote, "Unterminated quote"));
+                        }
+                        i += 1;
+                        break;
+                    }
+                    word.push(chars[i]);
+                    i += 1;
+                }
+                let kind = match word.as_str() {
+                    "AND" => TokenKind::And,
+                    "OR" => TokenKind::Or,
+                    "NOT" => TokenKind::Not,
+                    _ => TokenKind::Word(word),
+                };
+                tokens.push(Token { kind, pos: start, len: i - start });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn error_at(&self, token: &Token, message: &str) -> CueError {
+        query_error(self.input, token.pos, token.len, message)
+    }
+
+    fn error_at_end(&self, message: &str) -> CueError {
+        query_error(self.input, self.input.chars().count(), 1, message)
+    }
+
+    fn parse_or(&mut self) -> Result<Query> {
+        let mut branches = vec![self.parse_and()?];
+        while self.peek().is_some_and(|t| t.kind == TokenKind::Or) {
+            self.pos += 1;
+            branches.push(self.parse_and()?);
+        }
+        Ok(if branches.len() == 1 { branches.remove(0) } else { Query::Or(branches) })
+    }
+
+    fn parse_and(&mut self) -> Result<Query> {
+        let mut terms = vec![self.parse_unary()?];
+        loop {
+            match self.peek().map(|t| &t.kind) {
+                Some(TokenKind::And) => {
+                    self.pos += 1;
+                    terms.push(self.parse_unary()?);
+                }
+                Some(TokenKind::Or) | Some(TokenKind::RParen) | None => break,
+                Some(_) => terms.push(self.parse_unary()?),
+            }
+        }
+        Ok(if terms.len() == 1 { terms.remove(0) } else { Query::And(terms) })
+    }
+
+    fn parse_unary(&mut self) -> Result<Query> {
+        match self.peek().map(|t| &t.kind) {
+            Some(TokenKind::Not) | Some(TokenKind::Minus) => {
+                self.pos += 1;
+                Ok(Query::Not(Box::new(self.parse_unary()?)))
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Query> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(self.error_at_end("Expected a term"));
+        };
+        self.pos += 1;
+
+        match &token.kind {
+            TokenKind::LParen => {
+                let inner = self.parse_or()?;
+                match self.peek() {
+                    Some(t) if t.kind == TokenKind::RParen => {
+                        self.pos += 1;
+                        Ok(inner)
+                    }
+                    Some(t) => {
+                        let t = t.clone();
+                        Err(self.error_at(&t, "Expected ')'"))
+                    }
+                    None => Err(self.error_at(&token, "Unclosed '('")),
+                }
+            }
+            TokenKind::Phrase(phrase) => Ok(Query::Term(Predicate::Text(phrase.clone()))),
+            TokenKind::Word(word) => self.parse_word(&token, word),
+            TokenKind::RParen => Err(self.error_at(&token, "Unexpected ')'")),
+            _ => Err(self.error_at(&token, "Expected a term")),
+        }
+    }
+
+    fn parse_word(&self, token: &Token, word: &str) -> Result<Query> {
+        let Some(split) = word.find([':', '<', '>']) else {
+            return Ok(Query::Term(Predicate::Text(word.to_string())));
+        };
+
+        let field = word[..split].to_lowercase();
+        let op = &word[split..split + 1];
+        let value = &word[split + 1..];
+
+        // Not field-like (URLs, "a-b:c"), treat as text
+        if field.is_empty()
+            || value.starts_with("//")
+            || !field.chars().all(|c| c.is_ascii_alphabetic() || c == '_')
+        {
+            return Ok(Query::Term(Predicate::Text(word.to_string())));
+        }
+        if !QUERY_FIELDS.contains(&field.as_str()) {
+            return Err(self.error_at(
+                token,
+                &format!("Unknown field '{}' (expected one of: {})", field, QUERY_FIELDS.join(", ")),
+            ));
+        }
+        if value.is_empty() {
+            return Err(self.error_at(token, &format!("Missing value for '{}'", field)));
+        }
+
+        let is_date = matches!(field.as_str(), "created" | "updated" | "due");
+        if op != ":" && !is_date {
+            return Err(self.error_at(
+                token,
+                &format!("'{}' only supports ':' (comparisons work on created, updated and due)", field),
+            ));
+        }
+
+        // Date filters take the operator as part of their value (">2w")
+        let date_expr = if op == ":" { value.to_string() } else { format!("{}{}", op, value) };
+        let date_error = |e: CueError| self.error_at(token, &e.to_string());
+
+        let predicate = match field.as_str() {
+            "status" => Predicate::Status(value.to_string()),
+            "tag" | "tags" => Predicate::Tag(value.to_string()),
+            "priority" => Predicate::Priority(value.to_string()),
+            "assignee" => Predicate::Assignee(value.to_string()),
+            "parent" => Predicate::Parent(value.to_string()),
+            "milestone" => Predicate::Milestone(value.to_string()),
+            "id" => Predicate::Id(value.to_string()),
+            "title" => Predicate::Title(value.to_string()),
+            "created" => Predicate::Created(parse_date_filter(&date_expr).map_err(date_error)?),
+            "updated" => Predicate::Updated(parse_date_filter(&date_expr).map_err(date_error)?),
+            "due" => Predicate::Due(parse_due_filter(&date_expr).map_err(date_error)?),
+            _ => unreachable!("field checked against QUERY_FIELDS"),
+        };
+
+        Ok(Query::Term(predicate))
+    }
+}
+
+/// Lazily loaded file content for text predicates
+struct DocText<'a> {
+    doc: &'a Document,
+    content: Option<String>,
+}
+
+impl DocText<'_> {
+    fn contains(&mut self, needle: &str) -> bool {
+        let needle = needle.to_lowercase();
+        if self
+            .doc
+            .frontmatter
+            .as_ref()
+            .is_some_and(|m| m.title.to_lowercase().contains(&needle))
+        {
+            return true;
+        }
+        let doc = self.doc;
+        self.content
+            .get_or_insert_with(|| {
+                std::fs::read_to_string(&doc.path)
+                    .unwrap_or_default()
+                    .to_lowercase()
+            })
+            .contains(&needle)
+    }
+}
+
+fn eq_ignore_at(a: &str, b: &str) -> bool {
+    a.trim_start_matches('@')
+        .eq_ignore_ascii_case(b.trim_start_matches('@'))
+}
+
+impl Query {
+    /// Parse a query string; an empty string matches everything
+    pub fn parse(input: &str) -> Result<Query> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Ok(Query::All);
+        }
+
+        let mut parser = Parser { input, tokens, pos: 0 };
+        let query = parser.parse_or()?;
+
+        if let Some(token) = parser.peek().cloned() {
+            return Err(parser.error_at(&token, "Unexpected token"));
+        }
+        Ok(query)
+    }
+
+    /// Whether a document satisfies the query
+    ///
+    /// `config` supplies priority aliases and the workflow's terminal
+    /// statuses for `due:overdue`.
+    pub fn matches(&self, doc: &Document, config: &Config) -> bool {
+        let mut text = DocText { doc, content: None };
+        self.eval(doc, config, &mut text)
+    }
+
+    fn eval(&self, doc: &Document, config: &Config, text: &mut DocText) -> bool {
+        match self {
+            Query::All => true,
+            Query::And(terms) => terms.iter().all(|q| q.eval(doc, config, text)),
+            Query::Or(terms) => terms.iter().any(|q| q.eval(doc, config, text)),
+            Query::Not(inner) => !inner.eval(doc, config, text),
+            Query::Term(predicate) => Self::eval_predicate(predicate, doc, config, text),
+        }
+    }
+
+    fn eval_predicate(predicate: &Predicate, doc: &Document, config: &Config, text: &mut DocText) -> bool {
+        if let Predicate::Text(needle) = predicate {
+            return text.contains(needle);
+        }
+        if let Predicate::Id(prefix) = predicate {
+            return doc
+                .path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|id| id.to_lowercase().starts_with(&prefix.to_lowercase()));
+        }
+
+        let Some(meta) = &doc.frontmatter else {
+            return false;
+        };
+
+        match predicate {
+            Predicate::Status(s) => meta.status.eq_ignore_ascii_case(s),
+            Predicate::Tag(t) => matches_tag_filter(&meta.tags, std::slice::from_ref(t)),
+            Predicate::Priority(p) => match (config.priorities.resolve(&meta.priority), config.priorities.resolve(p)) {
+                (Some(a), Some(b)) => a == b,
+                _ => meta.priority.eq_ignore_ascii_case(p),
+            },
+            Predicate::Assignee(a) => meta.assignee.as_deref().is_some_and(|m| eq_ignore_at(m, a)),
+            Predicate::Parent(p) => meta.parent.as_deref() == Some(p.as_str()),
+            Predicate::Milestone(m) => meta.milestone.as_deref().is_some_and(|v| v.eq_ignore_ascii_case(m)),
+            Predicate::Title(t) => meta.title.to_lowercase().contains(&t.to_lowercase()),
+            Predicate::Created(f) => matches_date_filter(&meta.created, f).unwrap_or(false),
+            Predicate::Updated(f) => {
+                if meta.updated.is_some() {
+                    matches_date_filter(&meta.updated, f).unwrap_or(false)
+                } else {
+                    std::fs::metadata(&doc.path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|mtime| matches_date_filter_mtime(&mtime, f).ok())
+                        .unwrap_or(false)
+                }
+            }
+            Predicate::Due(f) => {
+                let open = !config.workflow.is_terminal(&meta.status);
+                (open || f.operator != crate::task_filters::DateOperator::Overdue)
+                    && matches_due_filter(&meta.due, f)
+            }
+            Predicate::Text(_) | Predicate::Id(_) => unreachable!("handled above"),
+        }
+    }
+
+    /// Split free text out of the query for ranked search
+    ///
+    /// Returns the text terms every match must contain, joined as a search
+    /// string, and the query with those terms replaced by `All`. Text under
+    /// `OR` or `NOT` stays in the query and is matched there, so
+    /// `tag:auth OR login` keeps its meaning.
+    pub fn split_text(self) -> (String, Query) {
+        let mut terms = Vec::new();
+        let rest = self.strip_text(&mut terms);
+        (terms.join(" "), rest)
+    }
+
+    fn strip_text(self, terms: &mut Vec<String>) -> Query {
+        match self {
+            Query::Term(Predicate::Text(t)) => {
+                terms.push(t);
+                Query::All
+            }
+            Query::And(qs) => Query::And(qs.into_iter().map(|q| q.strip_text(terms)).collect()),
+            other => other,
+        }
+    }
+
+    /// Whether any term compares `field` (e.g. to skip a default status filter)
+    pub fn mentions_field(&self, field: &str) -> bool {
+        match self {
+            Query::All => false,
+            Query::And(qs) | Query::Or(qs) => qs.iter().any(|q| q.mentions_field(field)),
+            Query::Not(inner) => inner.mentions_field(field),
+            Query::Term(p) => p.field() == field,
+        }
+    }
+
+    /// Whether the query is empty or reduced to `All`
+    pub fn is_all(&self) -> bool {
+        match self {
+            Query::All => true,
+            Query::And(qs) => qs.iter().all(Query::is_all),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn card(temp: &assert_fs::TempDir, id: &str, frontmatter: &str) -> Document {
+        let file = temp.child(format!(".cuedeck/cards/{}.md", id));
+        file.write_str(&format!("---\n{}\n---\n# Body\n\nlogin flow notes\n", frontmatter))
+            .unwrap();
+        crate::parse_file(file.path()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_example_query() {
+        let query = Query::parse(r#"status:todo (tag:auth OR tag:api) -assignee:@bob updated>2w "login flow""#).unwrap();
+        let Query::And(terms) = query else { panic!("expected AND") };
+        assert_eq!(terms.len(), 5);
+        assert!(matches!(terms[1], Query::Or(_)));
+        assert!(matches!(terms[2], Query::Not(_)));
+        assert!(matches!(terms[4], Query::Term(Predicate::Text(ref t)) if t == "login flow"));
+    }
+
+    #[test]
+    fn test_parse_error_points_at_token() {
+        let err = Query::parse("status:todo colour:red").unwrap_err().to_string();
+        assert!(err.contains("column 13"));
+        assert!(err.contains("Unknown field 'colour'"));
+        assert!(err.contains("\n              ^^^^^^^^^^"));
+
+        assert!(Query::parse("(tag:a OR tag:b").unwrap_err().to_string().contains("Unclosed '('"));
+        assert!(Query::parse("priority>high").is_err());
+        assert!(Query::parse("created>banana").is_err());
+        assert!(Query::parse("tag:a OR").is_err());
+    }
+
+    #[test]
+    fn test_matches_documents() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        let auth = card(&temp, "aaa111", "title: Auth\nstatus: todo\nassignee: \"@alice\"\ntags: [auth]");
+        let api = card(&temp, "bbb222", "title: API\nstatus: todo\nassignee: \"@bob\"\ntags: [api]");
+        let done = card(&temp, "ccc333", "title: Done\nstatus: done\ntags: [auth]");
+
+        let q = Query::parse("status:todo (tag:auth OR tag:api) -assignee:@bob").unwrap();
+        assert!(q.matches(&auth, &config));
+        assert!(!q.matches(&api, &config));
+        assert!(!q.matches(&done, &config));
+
+        let q = Query::parse(r#""login flow" NOT id:aaa"#).unwrap();
+        assert!(!q.matches(&auth, &config));
+        assert!(q.matches(&api, &config));
+    }
+
+    #[test]
+    fn test_priority_aliases() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[priorities]\nlevels = [\"P0\", \"P1\"]\ndefault = \"P1\"\n\n[priorities.aliases]\nurgent = \"P0\"\n")
+            .unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        let urgent = card(&temp, "aaa111", "title: A\npriority: urgent");
+        let p0 = card(&temp, "bbb222", "title: B\npriority: P0");
+        let p1 = card(&temp, "ccc333", "title: C\npriority: p1");
+
+        let q = Query::parse("priority:urgent").unwrap();
+        assert!(q.matches(&urgent, &config));
+        assert!(q.matches(&p0, &config));
+        assert!(!q.matches(&p1, &config));
+        assert!(Query::parse("priority:P1").unwrap().matches(&p1, &config));
+    }
+
+    #[test]
+    fn test_split_text() {
+        let (text, rest) = Query::parse("auth tag:api -draft").unwrap().split_text();
+        assert_eq!(text, "auth");
+        assert!(!rest.is_all());
+
+        let (text, rest) = Query::parse("login flow").unwrap().split_text();
+        assert_eq!(text, "login flow");
+        assert!(rest.is_all());
+
+        // Text under OR is matched in place rather than required
+        let temp = assert_fs::TempDir::new().unwrap();
+        let config = Config::load(temp.path()).unwrap();
+        let auth = card(&temp, "aaa111", "title: Auth\ntags: [auth]");
+        let doc = |name: &str, content: &str| {
+            let file = temp.child(name);
+            file.write_str(content).unwrap();
+            crate::parse_file(file.path()).unwrap()
+        };
+        let api = doc("api.md", "---\ntitle: API\ntags: [api]\n---\nendpoints\n");
+        let notes = doc("notes.md", "---\ntitle: Notes\n---\nnothing here\n");
+
+        let (text, rest) = Query::parse("tag:api OR login").unwrap().split_text();
+        assert_eq!(text, "");
+        assert!(rest.matches(&auth, &config));
+        assert!(rest.matches(&api, &config));
+        assert!(!rest.matches(&notes, &config));
+    }
+}
@@ -0,0 +1,426 @@
+//! Recurring cards
+//!
+//! A card with a `recurrence` rule (`daily`, `weekly`, `monthly` or a
+//! five-field cron expression) is the template for a repeating chore.
+//! `spawn_due` creates the template's next instance once the latest instance
+//! is done or its period has elapsed. Instances point back at the template
+//! with `recurrence_of` and at the instance before them with
+//! `previous_instance`; since the decision is made from those links alone,
+//! running it again before the next period is a no-op.
+
+use crate::tasks::{create_task_with_options, list_tasks_filtered, NewTaskOptions};
+use crate::task_filters::TaskFilters;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use cue_common::{CardMetadata, CueError, Document, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// How far ahead to search for a cron match (covers Feb 29 rules)
+const CRON_SEARCH_DAYS: i64 = 366 * 4 + 1;
+
+/// A parsed `recurrence` rule
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Monthly,
+    Cron(CronSchedule),
+}
+
+impl Recurrence {
+    /// Parse `daily`, `weekly`, `monthly` (or `@daily` etc.) or a cron expression
+    pub fn parse(rule: &str) -> Result<Self> {
+        match rule.trim().trim_start_matches('@').to_ascii_lowercase().as_str() {
+            "daily" => Ok(Self::Daily),
+            "weekly" => Ok(Self::Weekly),
+            "monthly" => Ok(Self::Monthly),
+            _ => CronSchedule::parse(rule).map(Self::Cron),
+        }
+    }
+
+    /// The first occurrence strictly after `after`
+    ///
+    /// `daily`/`weekly`/`monthly` count from `after` itself; cron rules find
+    /// the next matching minute (UTC).
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Daily => Some(after + Duration::days(1)),
+            Self::Weekly => Some(after + Duration::weeks(1)),
+            Self::Monthly => after.checked_add_months(chrono::Months::new(1)),
+            Self::Cron(schedule) => schedule.next_after(after),
+        }
+    }
+}
+
+/// A `minute hour day-of-month month day-of-week` schedule
+///
+/// Fields accept `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps
+/// (`*/2`, `0-30/10`). Day of week is 0-7 with both 0 and 7 meaning Sunday.
+/// As in cron, when both day fields are restricted a day matching either one
+/// qualifies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(CueError::ValidationError(format!(
+                "Invalid recurrence '{}'. Expected daily, weekly, monthly or a cron expression (minute hour day month weekday)",
+                expr
+            )));
+        }
+
+        let field = |i: usize, min: u32, max: u32| {
+            parse_cron_field(fields[i], min, max).map_err(|e| {
+                CueError::ValidationError(format!("Invalid recurrence '{}': {}", expr, e))
+            })
+        };
+
+        let mut weekdays = field(4, 0, 7)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+
+        Ok(Self {
+            minutes: field(0, 0, 59)?,
+            hours: field(1, 0, 23)?,
+            days: field(2, 1, 31)?,
+            months: field(3, 1, 12)?,
+            weekdays,
+            any_day: fields[2].starts_with('*'),
+            any_weekday: fields[4].starts_with('*'),
+        })
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        if self.any_day || self.any_weekday {
+            day && weekday
+        } else {
+            day || weekday
+        }
+    }
+
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+        for offset in 0..CRON_SEARCH_DAYS {
+            let date = start.date_naive() + Duration::days(offset);
+            if !self.matches_date(date) {
+                continue;
+            }
+            for hour in (0..24).filter(|h| self.hours & (1 << h) != 0) {
+                for minute in (0..60).filter(|m| self.minutes & (1 << m) != 0) {
+                    let candidate = Utc.from_utc_datetime(&date.and_hms_opt(hour, minute, 0)?);
+                    if candidate >= start {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Parse one cron field into a bitmask of allowed values
+fn parse_cron_field(field: &str, min: u32, max: u32) -> std::result::Result<u64, String> {
+    let mut mask = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("bad step in '{}'", part))?;
+                if step == 0 {
+                    return Err(format!("step must be positive in '{}'", part));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((lo, hi)) = range.split_once('-') {
+            let lo = lo.parse().map_err(|_| format!("bad range '{}'", range))?;
+            let hi = hi.parse().map_err(|_| format!("bad range '{}'", range))?;
+            (lo, hi)
+        } else {
+            let value = range.parse().map_err(|_| format!("bad value '{}'", range))?;
+            // `5/15` means "from 5, every 15"
+            (value, if step > 1 { max } else { value })
+        };
+
+        if lo < min || hi > max || lo > hi {
+            return Err(format!("'{}' is outside {}-{}", part, min, max));
+        }
+        for value in (lo..=hi).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+/// A card created by `spawn_due`
+#[derive(Debug, Clone, Serialize)]
+pub struct SpawnedCard {
+    /// The recurring template card
+    pub template: String,
+    /// ID of the new instance (empty for a dry run)
+    pub id: String,
+    pub title: String,
+    /// The instance this one follows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<String>,
+    /// Next occurrence of the rule (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+}
+
+/// A template whose `recurrence` rule could not be parsed
+#[derive(Debug, Clone, Serialize)]
+pub struct InvalidRule {
+    pub template: String,
+    pub rule: String,
+    pub error: String,
+}
+
+/// Result of `spawn_due`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SpawnReport {
+    pub spawned: Vec<SpawnedCard>,
+    pub invalid: Vec<InvalidRule>,
+}
+
+/// Create the next instance of every recurring card that is due
+///
+/// A template is due when it has no instance yet, its latest instance has a
+/// terminal status, or the rule's period has elapsed since that instance was
+/// created. Templates with a terminal status no longer recur. With `dry_run`
+/// nothing is written.
+pub fn spawn_due(workspace_root: &Path, dry_run: bool) -> Result<SpawnReport> {
+    let workflow = cue_config::Config::load(workspace_root)?.workflow;
+    let now = Utc::now();
+
+    let filters = TaskFilters {
+        include_archived: true,
+        ..Default::default()
+    };
+    let cards: Vec<(String, CardMetadata)> = list_tasks_filtered(workspace_root, &filters)?
+        .into_iter()
+        .filter_map(|doc| Some((card_id(&doc)?, doc.frontmatter?)))
+        .collect();
+
+    // Latest instance of each template, by creation time
+    let mut latest: HashMap<&str, (&str, &CardMetadata)> = HashMap::new();
+    for (id, meta) in &cards {
+        let Some(template) = meta.recurrence_of.as_deref() else {
+            continue;
+        };
+        let newer = latest
+            .get(template)
+            .is_none_or(|(_, current)| created_at(meta) > created_at(current));
+        if newer {
+            latest.insert(template, (id, meta));
+        }
+    }
+
+    let mut report = SpawnReport::default();
+    let mut templates: Vec<&(String, CardMetadata)> = cards
+        .iter()
+        .filter(|(_, meta)| meta.recurrence.is_some() && !workflow.is_terminal(&meta.status))
+        .collect();
+    templates.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (template_id, template) in templates {
+        let rule_text = template.recurrence.as_deref().unwrap_or_default();
+        let rule = match Recurrence::parse(rule_text) {
+            Ok(rule) => rule,
+            Err(e) => {
+                report.invalid.push(InvalidRule {
+                    template: template_id.clone(),
+                    rule: rule_text.to_string(),
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let previous = latest.get(template_id.as_str());
+        let due = match previous {
+            None => true,
+            Some((_, instance)) if workflow.is_terminal(&instance.status) => true,
+            Some((_, instance)) => created_at(instance)
+                .and_then(|created| rule.next_after(created))
+                .is_some_and(|next| next <= now),
+        };
+        if !due {
+            continue;
+        }
+
+        let mut spawned = SpawnedCard {
+            template: template_id.clone(),
+            id: String::new(),
+            title: format!("{} ({})", template.title, now.format("%Y-%m-%d")),
+            previous: previous.map(|(id, _)| id.to_string()),
+            due: rule.next_after(now).map(|next| next.format("%Y-%m-%d").to_string()),
+        };
+
+        if !dry_run {
+            // Links are part of the initial frontmatter so the instance is
+            // created in a single write
+            let options = NewTaskOptions {
+                tags: template.tags.clone(),
+                priority: Some(template.priority.clone()),
+                assignee: template.assignee.clone(),
+                due: spawned.due.clone(),
+                recurrence_of: Some(template_id.clone()),
+                previous_instance: spawned.previous.clone(),
+                ..Default::default()
+            };
+            let path = create_task_with_options(workspace_root, &spawned.title, &options)?;
+            spawned.id = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string();
+        }
+
+        report.spawned.push(spawned);
+    }
+
+    Ok(report)
+}
+
+fn card_id(doc: &Document) -> Option<String> {
+    doc.path.file_stem().and_then(|s| s.to_str()).map(String::from)
+}
+
+fn created_at(meta: &CardMetadata) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(meta.created.as_deref()?)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::update_task;
+    use assert_fs::prelude::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_named_rules() {
+        let t = at("2024-01-31T09:00:00Z");
+        assert_eq!(Recurrence::parse("daily").unwrap().next_after(t), Some(at("2024-02-01T09:00:00Z")));
+        assert_eq!(Recurrence::parse("@weekly").unwrap().next_after(t), Some(at("2024-02-07T09:00:00Z")));
+        assert_eq!(Recurrence::parse("Monthly").unwrap().next_after(t), Some(at("2024-02-29T09:00:00Z")));
+    }
+
+    #[test]
+    fn test_cron_rules() {
+        // Mondays at 09:00; 2024-01-03 is a Wednesday
+        let mondays = Recurrence::parse("0 9 * * 1").unwrap();
+        assert_eq!(mondays.next_after(at("2024-01-03T12:00:00Z")), Some(at("2024-01-08T09:00:00Z")));
+        assert_eq!(mondays.next_after(at("2024-01-08T09:00:00Z")), Some(at("2024-01-15T09:00:00Z")));
+
+        // First of the quarter, every 30 minutes past midnight
+        let quarterly = Recurrence::parse("*/30 0 1 1-12/3 *").unwrap();
+        assert_eq!(quarterly.next_after(at("2024-01-01T00:10:00Z")), Some(at("2024-01-01T00:30:00Z")));
+        assert_eq!(quarterly.next_after(at("2024-01-01T00:30:00Z")), Some(at("2024-04-01T00:00:00Z")));
+
+        // Sunday as 7
+        let sundays = Recurrence::parse("0 0 * * 7").unwrap();
+        assert_eq!(sundays.next_after(at("2024-01-01T00:00:00Z")), Some(at("2024-01-07T00:00:00Z")));
+
+        assert!(Recurrence::parse("fortnightly").is_err());
+        assert!(Recurrence::parse("0 25 * * *").is_err());
+        assert!(Recurrence::parse("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_spawn_due_is_idempotent_and_links_instances() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards
+            .child("tmpl01.md")
+            .write_str("---\ntitle: Rotate keys\nstatus: todo\npriority: high\ntags: [ops]\nrecurrence: weekly\n---\n")
+            .unwrap();
+        cards
+            .child("broken.md")
+            .write_str("---\ntitle: Broken\nstatus: todo\nrecurrence: sometimes\n---\n")
+            .unwrap();
+
+        let first = spawn_due(temp.path(), false).unwrap();
+        assert_eq!(first.spawned.len(), 1);
+        assert_eq!(first.invalid.len(), 1);
+        assert_eq!(first.invalid[0].template, "broken");
+        let instance = &first.spawned[0];
+        assert!(instance.previous.is_none());
+
+        let meta = crate::parse_file(cards.child(format!("{}.md", instance.id)).path())
+            .unwrap()
+            .frontmatter
+            .unwrap();
+        assert_eq!(meta.recurrence_of.as_deref(), Some("tmpl01"));
+        assert_eq!(meta.priority, "high");
+        assert!(meta.recurrence.is_none());
+        assert!(meta.due.is_some());
+
+        // The instance is written once, links included
+        let history = crate::activity::task_history(temp.path(), &instance.id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, "created");
+        assert!(history[0].changes.iter().any(|c| c.field == "recurrence_of"));
+
+        // Nothing is due until the instance is done
+        assert!(spawn_due(temp.path(), false).unwrap().spawned.is_empty());
+
+        let mut done = serde_json::Map::new();
+        done.insert("status".to_string(), "done".into());
+        update_task(temp.path(), &instance.id, done).unwrap();
+
+        let preview = spawn_due(temp.path(), true).unwrap();
+        assert_eq!(preview.spawned.len(), 1);
+        assert!(preview.spawned[0].id.is_empty());
+
+        let second = spawn_due(temp.path(), false).unwrap();
+        assert_eq!(second.spawned[0].previous.as_deref(), Some(instance.id.as_str()));
+        assert!(spawn_due(temp.path(), false).unwrap().spawned.is_empty());
+    }
+
+    #[test]
+    fn test_spawn_due_after_period_elapses() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards
+            .child("tmpl01.md")
+            .write_str("---\ntitle: Audit\nstatus: todo\nrecurrence: daily\n---\n")
+            .unwrap();
+        cards
+            .child("old001.md")
+            .write_str("---\ntitle: Audit (2020-01-01)\nstatus: todo\nrecurrence_of: tmpl01\ncreated: 2020-01-01T00:00:00Z\n---\n")
+            .unwrap();
+
+        let report = spawn_due(temp.path(), false).unwrap();
+        assert_eq!(report.spawned.len(), 1);
+        assert_eq!(report.spawned[0].previous.as_deref(), Some("old001"));
+    }
+}
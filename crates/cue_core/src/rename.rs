@@ -0,0 +1,648 @@
+//! Moving and renaming documents
+//!
+//! `move_document` renames a doc or card and rewrites everything that refers
+//! to it by name: `[[links]]` in any workspace markdown file that
+//! `DependencyGraph` reports as a backlink, `depends_on` and `parent`
+//! references in other cards (archived ones included), and the
+//! `DocumentCache` entry for the file. Deleted cards in the trash are left as
+//! they were. A renamed card keeps its activity history through the
+//! `renamed` event.
+//! Embeddings are keyed by content hash, so they survive a rename without
+//! changes.
+//!
+//! Every rewrite is computed before anything is written. The rename happens
+//! last, and rewritten files are restored if a write or the rename fails.
+
+use crate::activity::{record_event_lossy, FieldChange};
+use crate::cache::DocumentCache;
+use crate::card_ids::{is_valid_task_id, resolve_task_id};
+use crate::frontmatter::Frontmatter;
+use crate::graph::DependencyGraph;
+use crate::parse_files_parallel;
+use cue_common::{CueError, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A file that was (or, in a dry run, would be) changed by a move
+#[derive(Debug, Clone, Serialize)]
+pub struct TouchedFile {
+    /// Workspace-relative path, after the move
+    pub path: PathBuf,
+    /// Human-readable description of each edit
+    pub changes: Vec<String>,
+}
+
+/// Outcome of `move_document`
+#[derive(Debug, Clone, Serialize)]
+pub struct MoveReport {
+    /// Workspace-relative source path
+    pub from: PathBuf,
+    /// Workspace-relative destination path
+    pub to: PathBuf,
+    pub dry_run: bool,
+    /// Files whose content was rewritten, sorted by path
+    pub files: Vec<TouchedFile>,
+    /// Whether a parsed-document cache entry was moved to the new path
+    pub cache_moved: bool,
+}
+
+/// A pending content rewrite for one file
+struct Edit {
+    /// Content on disk before the move, for rollback
+    original: String,
+    content: String,
+    changes: Vec<String>,
+}
+
+/// Move `from` to `to`, rewriting every reference to it
+///
+/// Both arguments are paths relative to the workspace root (or absolute);
+/// a bare card ID such as `abc123` is accepted for cards. Cards must stay in
+/// `.cuedeck/cards` and their new filename must be a valid card ID.
+pub fn move_document(workspace_root: &Path, from: &str, to: &str, dry_run: bool) -> Result<MoveReport> {
+    let cards_dir = workspace_root.join(".cuedeck/cards");
+    let archive_dir = crate::archive::archive_dir(workspace_root);
+    let from_path = resolve_source(workspace_root, from)?;
+    let to_path = resolve_destination(workspace_root, to)?;
+
+    validate_move(workspace_root, &cards_dir, &from_path, &to_path)?;
+
+    let is_card = from_path.parent() == Some(cards_dir.as_path());
+    let ids = if is_card {
+        Some((stem(&from_path), stem(&to_path)))
+    } else {
+        None
+    };
+
+    let docs = parse_files_parallel(workspace_documents(workspace_root));
+    let graph = DependencyGraph::build(&docs)?;
+
+    let mut edits: BTreeMap<PathBuf, Edit> = BTreeMap::new();
+
+    // Links pointing at the moved file
+    for source in graph.backlinks(&from_path) {
+        let edit = edit_for(&mut edits, &source)?;
+        let (content, changes) = rewrite_links(&edit.content, |link| {
+            let source_dir = source.parent().unwrap_or(workspace_root);
+            retarget_link(source_dir, link, &from_path, &to_path)
+        });
+        edit.content = content;
+        edit.changes.extend(changes);
+    }
+
+    // Relative links out of the moved file now start from a new directory
+    if from_path.parent() != to_path.parent() {
+        let edit = edit_for(&mut edits, &from_path)?;
+        let (content, changes) = rewrite_links(&edit.content, |link| {
+            rebase_relative_link(&from_path, &to_path, link)
+        });
+        edit.content = content;
+        edit.changes.extend(changes);
+    }
+
+    // Card references by ID
+    let mut card_events = Vec::new();
+    if let Some((old_id, new_id)) = &ids {
+        let is_card_file = |path: &Path| {
+            path.parent()
+                .is_some_and(|dir| dir == cards_dir || dir == archive_dir)
+        };
+        for doc in docs.iter().filter(|d| is_card_file(&d.path)) {
+            let Some(meta) = &doc.frontmatter else {
+                continue;
+            };
+            let depends = meta.depends_on.as_ref().is_some_and(|d| d.contains(old_id));
+            let child = meta.parent.as_deref() == Some(old_id.as_str());
+            if !depends && !child {
+                continue;
+            }
+
+            let edit = edit_for(&mut edits, &doc.path)?;
+            let mut fm = Frontmatter::parse(&edit.content)?;
+            let mut field_changes = Vec::new();
+
+            if let Some(old_deps) = meta.depends_on.as_ref().filter(|_| depends) {
+                let new_deps: Vec<String> = old_deps
+                    .iter()
+                    .map(|d| if d == old_id { new_id.clone() } else { d.clone() })
+                    .collect();
+                let value = new_deps.iter().cloned().map(serde_yaml::Value::String).collect();
+                fm.set("depends_on", &serde_yaml::Value::Sequence(value));
+                edit.changes.push(format!("depends_on: {} -> {}", old_id, new_id));
+                field_changes.push(FieldChange {
+                    field: "depends_on".to_string(),
+                    old: Some(serde_json::json!(old_deps)),
+                    new: Some(serde_json::json!(new_deps)),
+                });
+            }
+            if child {
+                fm.set_str("parent", new_id);
+                edit.changes.push(format!("parent: {} -> {}", old_id, new_id));
+                field_changes.push(FieldChange {
+                    field: "parent".to_string(),
+                    old: Some(serde_json::json!(old_id)),
+                    new: Some(serde_json::json!(new_id)),
+                });
+            }
+
+            edit.content = fm.to_string();
+            card_events.push((stem(&doc.path), field_changes));
+        }
+    }
+
+    edits.retain(|_, edit| !edit.changes.is_empty());
+
+    let cache_file = workspace_root.join(".cuedeck/cache/documents.bin");
+    let mut cache_moved = dry_run
+        && cache_file.exists()
+        && load_cache(workspace_root).is_some_and(|cache| cache.get(&from_path).is_some());
+
+    if !dry_run {
+        // The moved file is rewritten in place, then renamed last
+        apply_edits(&edits)?;
+        let renamed = match to_path.parent() {
+            Some(parent) => fs::create_dir_all(parent),
+            None => Ok(()),
+        }
+        .and_then(|()| fs::rename(&from_path, &to_path));
+        if let Err(e) = renamed {
+            restore_edits(&edits, edits.keys());
+            return Err(e.into());
+        }
+
+        if cache_file.exists() {
+            cache_moved = move_cache_entry(workspace_root, &from_path, &to_path);
+        }
+
+        if let Some((old_id, new_id)) = &ids {
+            record_event_lossy(
+                workspace_root,
+                new_id,
+                "renamed",
+                vec![FieldChange {
+                    field: "id".to_string(),
+                    old: Some(serde_json::json!(old_id)),
+                    new: Some(serde_json::json!(new_id)),
+                }],
+            );
+            for (id, changes) in card_events {
+                record_event_lossy(workspace_root, &id, "updated", changes);
+            }
+        }
+    }
+
+    let relative = |path: &Path| path.strip_prefix(workspace_root).unwrap_or(path).to_path_buf();
+    Ok(MoveReport {
+        from: relative(&from_path),
+        to: relative(&to_path),
+        dry_run,
+        files: edits
+            .into_iter()
+            .map(|(path, edit)| TouchedFile {
+                path: relative(if path == from_path { &to_path } else { &path }),
+                changes: edit.changes,
+            })
+            .collect(),
+        cache_moved,
+    })
+}
+
+/// The pending edit for `path`, starting from its current content
+fn edit_for<'a>(edits: &'a mut BTreeMap<PathBuf, Edit>, path: &Path) -> Result<&'a mut Edit> {
+    if !edits.contains_key(path) {
+        let content = fs::read_to_string(path)?;
+        edits.insert(
+            path.to_path_buf(),
+            Edit {
+                original: content.clone(),
+                content,
+                changes: Vec::new(),
+            },
+        );
+    }
+    Ok(edits.get_mut(path).expect("edit was just inserted"))
+}
+
+/// Write every edit, restoring the files already written if one fails
+fn apply_edits(edits: &BTreeMap<PathBuf, Edit>) -> Result<()> {
+    let mut written = Vec::new();
+    for (path, edit) in edits {
+        if let Err(e) = crate::write_atomic(path, &edit.content) {
+            restore_edits(edits, written);
+            return Err(e.into());
+        }
+        written.push(path);
+    }
+    Ok(())
+}
+
+/// Put back the original content of `paths`; failures are logged
+fn restore_edits<'a>(edits: &BTreeMap<PathBuf, Edit>, paths: impl IntoIterator<Item = &'a PathBuf>) {
+    for path in paths {
+        if let Err(e) = crate::write_atomic(path, &edits[path].original) {
+            tracing::warn!("Failed to restore {:?} after a failed move: {}", path, e);
+        }
+    }
+}
+
+/// Resolve the source argument to an existing file
+fn resolve_source(workspace_root: &Path, input: &str) -> Result<PathBuf> {
+    if is_bare_id(input) {
+        let id = resolve_task_id(workspace_root, input)?;
+        return Ok(workspace_root.join(".cuedeck/cards").join(format!("{}.md", id)));
+    }
+    let path = workspace_path(workspace_root, input)?;
+    if !path.is_file() {
+        return Err(CueError::FileNotFound {
+            path: input.to_string(),
+        });
+    }
+    Ok(path)
+}
+
+/// Resolve the destination argument; a bare ID names a card file
+fn resolve_destination(workspace_root: &Path, input: &str) -> Result<PathBuf> {
+    if is_bare_id(input) {
+        return Ok(workspace_root.join(".cuedeck/cards").join(format!("{}.md", input)));
+    }
+    workspace_path(workspace_root, input)
+}
+
+/// A card ID rather than a path: no separators and no extension
+fn is_bare_id(input: &str) -> bool {
+    !input.contains('/') && !input.contains('\\') && Path::new(input).extension().is_none()
+}
+
+/// Join `input` onto the workspace root, normalizing `.` and rejecting `..`
+fn workspace_path(workspace_root: &Path, input: &str) -> Result<PathBuf> {
+    let input_path = Path::new(input);
+    let relative = input_path.strip_prefix(workspace_root).unwrap_or(input_path);
+    if relative.is_absolute() {
+        return Err(CueError::ValidationError(format!(
+            "Path is outside the workspace: {}",
+            input
+        )));
+    }
+
+    let mut path = workspace_root.to_path_buf();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => {
+                return Err(CueError::ValidationError(format!(
+                    "Path must not contain '..': {}",
+                    input
+                )))
+            }
+        }
+    }
+    Ok(path)
+}
+
+fn validate_move(workspace_root: &Path, cards_dir: &Path, from: &Path, to: &Path) -> Result<()> {
+    let display = |p: &Path| p.strip_prefix(workspace_root).unwrap_or(p).display().to_string();
+
+    if from == to {
+        return Err(CueError::ValidationError(
+            "Source and destination are the same".to_string(),
+        ));
+    }
+    if from.extension().is_none_or(|e| e != "md") || to.extension().is_none_or(|e| e != "md") {
+        return Err(CueError::ValidationError(
+            "Only markdown (.md) files can be moved".to_string(),
+        ));
+    }
+    if to.exists() {
+        return Err(CueError::ValidationError(format!(
+            "Destination already exists: {}",
+            display(to)
+        )));
+    }
+
+    let from_card = from.parent() == Some(cards_dir);
+    let to_card = to.parent() == Some(cards_dir);
+    if from_card != to_card {
+        return Err(CueError::ValidationError(format!(
+            "Cannot move between cards and docs: cards must stay directly in {}",
+            display(cards_dir)
+        )));
+    }
+    if to_card && !is_valid_task_id(&stem(to)) {
+        return Err(CueError::ValidationError(format!(
+            "Invalid card ID '{}': use letters, digits, '-' and '_'",
+            stem(to)
+        )));
+    }
+    Ok(())
+}
+
+/// Markdown files anywhere in the workspace, skipping build and VCS output
+fn workspace_documents(workspace_root: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(workspace_root)
+        .follow_links(true)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            !(name == "node_modules"
+                || name == ".git"
+                || name == "target"
+                || name == "dist"
+                // Deleted cards keep their content as it was when deleted
+                || e.path().ends_with(".cuedeck/trash"))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|x| x == "md"))
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+fn stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Replace `[[link]]` targets for which `retarget` returns a new target
+///
+/// `#anchor` and `|label` suffixes are kept as written.
+fn rewrite_links<F>(content: &str, mut retarget: F) -> (String, Vec<String>)
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let link_regex = regex::Regex::new(r"\[\[(.*?)\]\]").unwrap();
+    let mut changes = Vec::new();
+
+    let rewritten = link_regex.replace_all(content, |caps: &regex::Captures| {
+        let inner = &caps[1];
+        let split = inner.find(['#', '|']).unwrap_or(inner.len());
+        let (target, suffix) = inner.split_at(split);
+
+        match retarget(target) {
+            Some(new_target) if new_target != target => {
+                let replacement = format!("[[{}{}]]", new_target, suffix);
+                changes.push(format!("{} -> {}", &caps[0], replacement));
+                replacement
+            }
+            _ => caps[0].to_string(),
+        }
+    });
+
+    (rewritten.into_owned(), changes)
+}
+
+/// New text for a link in `source_dir` that resolves to `from`, if it does
+///
+/// Resolution mirrors `DependencyGraph::build`, and the rewritten link keeps
+/// the original form: bare stem, filename, relative or absolute path.
+fn retarget_link(source_dir: &Path, link: &str, from: &Path, to: &Path) -> Option<String> {
+    if link.starts_with("./") || link.starts_with("../") {
+        let resolved = source_dir.join(link).canonicalize().ok()?;
+        (resolved == from.canonicalize().ok()?).then(|| relative_link(source_dir, to))
+    } else if link.contains('/') {
+        (Path::new(link) == from).then(|| to.to_string_lossy().into_owned())
+    } else {
+        let name = from.file_name()?.to_str()?;
+        let lower = link.to_lowercase();
+        if lower == name.to_lowercase() {
+            Some(to.file_name()?.to_str()?.to_string())
+        } else if lower == stem(from).to_lowercase() {
+            Some(stem(to))
+        } else {
+            None
+        }
+    }
+}
+
+/// Recompute a relative link in the moved file so it points at the same target
+fn rebase_relative_link(from: &Path, to: &Path, link: &str) -> Option<String> {
+    if !(link.starts_with("./") || link.starts_with("../")) {
+        return None;
+    }
+    let target = from.parent()?.join(link);
+    if !target.exists() {
+        return None;
+    }
+    Some(relative_link(to.parent()?, &normalize(&target)))
+}
+
+/// Lexically resolve `.` and `..` components
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Relative link from `dir` to `target`, in `./` or `../` form
+fn relative_link(dir: &Path, target: &Path) -> String {
+    let dir: Vec<_> = dir.components().collect();
+    let target: Vec<_> = target.components().collect();
+    let common = dir.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); dir.len() - common];
+    parts.extend(
+        target[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+
+    let joined = parts.join("/");
+    if joined.starts_with("..") {
+        joined
+    } else {
+        format!("./{}", joined)
+    }
+}
+
+fn load_cache(workspace_root: &Path) -> Option<DocumentCache> {
+    let mut cache = DocumentCache::new(workspace_root).ok()?;
+    cache.load().ok()?;
+    Some(cache)
+}
+
+/// Move the cache entry; failures only cost a re-parse, so they are logged
+fn move_cache_entry(workspace_root: &Path, from: &Path, to: &Path) -> bool {
+    let Some(mut cache) = load_cache(workspace_root) else {
+        return false;
+    };
+    if !cache.rename(from, to) {
+        return false;
+    }
+    match cache.save() {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Failed to save cache after moving {:?}: {}", from, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn workspace() -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        let docs = temp.child(".cuedeck/docs");
+        cards.create_dir_all().unwrap();
+        docs.create_dir_all().unwrap();
+
+        cards
+            .child("aaa111.md")
+            .write_str("---\ntitle: Login\nstatus: todo\n---\nSee [[design]] and [[design.md#flow|the flow]].\n")
+            .unwrap();
+        cards
+            .child("bbb222.md")
+            .write_str("---\ntitle: Signup\n# keep me\ndepends_on: [aaa111, ccc333]\nparent: aaa111\n---\n")
+            .unwrap();
+        cards
+            .child("ccc333.md")
+            .write_str("---\ntitle: Other\n---\nLinks to [[aaa111]].\n")
+            .unwrap();
+        docs.child("design.md")
+            .write_str("# Design\n\nSee [[./guide/setup.md]].\n")
+            .unwrap();
+        docs.child("guide/setup.md")
+            .write_str("# Setup\n\nBack to [[../design.md]].\n")
+            .unwrap();
+        temp.child("README.md").write_str("Start with [[design]].\n").unwrap();
+        temp.child("target/notes.md").write_str("Old [[design]].\n").unwrap();
+        temp.child(".cuedeck/archive/ddd444.md")
+            .write_str("---\ntitle: Shelved\nstatus: archived\ndepends_on: [aaa111]\n---\n")
+            .unwrap();
+        temp.child(".cuedeck/trash/eee555.md")
+            .write_str("---\ntitle: Deleted\ndepends_on: [aaa111]\n---\nSee [[aaa111]].\n")
+            .unwrap();
+        temp
+    }
+
+    fn read(temp: &assert_fs::TempDir, path: &str) -> String {
+        fs::read_to_string(temp.path().join(path)).unwrap()
+    }
+
+    #[test]
+    fn test_move_doc_rewrites_links() {
+        let temp = workspace();
+        let report = move_document(temp.path(), ".cuedeck/docs/design.md", ".cuedeck/docs/arch/overview.md", false)
+            .unwrap();
+
+        assert_eq!(report.to, PathBuf::from(".cuedeck/docs/arch/overview.md"));
+        assert!(!temp.path().join(".cuedeck/docs/design.md").exists());
+
+        let card = read(&temp, ".cuedeck/cards/aaa111.md");
+        assert!(card.contains("See [[overview]] and [[overview.md#flow|the flow]]."));
+
+        let setup = read(&temp, ".cuedeck/docs/guide/setup.md");
+        assert!(setup.contains("[[../arch/overview.md]]"));
+
+        let moved = read(&temp, ".cuedeck/docs/arch/overview.md");
+        assert!(moved.contains("[[../guide/setup.md]]"));
+
+        // Links outside .cuedeck are rewritten too, but not in build output
+        assert!(read(&temp, "README.md").contains("[[overview]]"));
+        assert!(read(&temp, "target/notes.md").contains("[[design]]"));
+
+        let touched: Vec<_> = report.files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(touched.len(), 4);
+    }
+
+    #[test]
+    fn test_failed_rename_restores_rewritten_files() {
+        let temp = workspace();
+        let card = read(&temp, ".cuedeck/cards/aaa111.md");
+        let setup = read(&temp, ".cuedeck/docs/guide/setup.md");
+
+        // The destination's parent is a file, so the rename fails after the rewrites
+        let result = move_document(temp.path(), ".cuedeck/docs/design.md", "README.md/overview.md", false);
+
+        assert!(result.is_err());
+        assert!(temp.path().join(".cuedeck/docs/design.md").exists());
+        assert_eq!(read(&temp, ".cuedeck/cards/aaa111.md"), card);
+        assert_eq!(read(&temp, ".cuedeck/docs/guide/setup.md"), setup);
+        assert_eq!(read(&temp, "README.md"), "Start with [[design]].\n");
+    }
+
+    #[test]
+    fn test_move_card_updates_references() {
+        let temp = workspace();
+        let mut update = serde_json::Map::new();
+        update.insert("status".to_string(), "active".into());
+        crate::tasks::update_task(temp.path(), "aaa111", update).unwrap();
+        let report = move_document(temp.path(), "aaa111", "login-1", false).unwrap();
+
+        assert!(temp.path().join(".cuedeck/cards/login-1.md").exists());
+        let dependent = read(&temp, ".cuedeck/cards/bbb222.md");
+        assert!(dependent.contains("# keep me"));
+        assert!(dependent.contains("depends_on: [login-1, ccc333]"));
+        assert!(dependent.contains("parent: login-1"));
+        assert!(read(&temp, ".cuedeck/cards/ccc333.md").contains("[[login-1]]"));
+        assert!(read(&temp, ".cuedeck/archive/ddd444.md").contains("depends_on: [login-1]"));
+        assert_eq!(
+            read(&temp, ".cuedeck/trash/eee555.md"),
+            "---\ntitle: Deleted\ndepends_on: [aaa111]\n---\nSee [[aaa111]].\n"
+        );
+        assert_eq!(report.files.len(), 3);
+
+        // History from before the rename follows the card
+        let history = crate::activity::task_history(temp.path(), "login-1").unwrap();
+        let actions: Vec<&str> = history.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, vec!["updated", "renamed"]);
+        assert!(crate::activity::task_history(temp.path(), "aaa111").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let temp = workspace();
+        let before = read(&temp, ".cuedeck/cards/bbb222.md");
+
+        let report = move_document(temp.path(), "aaa111", "login-1", true).unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!(report.files.len(), 3);
+        assert!(temp.path().join(".cuedeck/cards/aaa111.md").exists());
+        assert_eq!(read(&temp, ".cuedeck/cards/bbb222.md"), before);
+    }
+
+    #[test]
+    fn test_rejects_invalid_moves() {
+        let temp = workspace();
+        let invalid = |from: &str, to: &str| {
+            matches!(
+                move_document(temp.path(), from, to, true),
+                Err(CueError::ValidationError(_))
+            )
+        };
+
+        assert!(invalid("aaa111", "bbb222"));
+        assert!(invalid("aaa111", ".cuedeck/docs/login.md"));
+        assert!(invalid(".cuedeck/docs/design.md", "../design.md"));
+        assert!(invalid(".cuedeck/docs/design.md", ".cuedeck/docs/design.txt"));
+        assert!(matches!(
+            move_document(temp.path(), ".cuedeck/docs/missing.md", ".cuedeck/docs/x.md", true),
+            Err(CueError::FileNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_relative_link() {
+        assert_eq!(relative_link(Path::new("/w/docs"), Path::new("/w/docs/a.md")), "./a.md");
+        assert_eq!(relative_link(Path::new("/w/docs/guide"), Path::new("/w/docs/a.md")), "../a.md");
+        assert_eq!(
+            relative_link(Path::new("/w/docs"), Path::new("/w/docs/arch/b.md")),
+            "./arch/b.md"
+        );
+    }
+}
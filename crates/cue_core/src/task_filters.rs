@@ -17,11 +17,29 @@ pub struct TaskFilters {
     /// Priority filter
     pub priority: Option<String>,
     
+    /// Parent (epic) filter
+    pub parent: Option<String>,
+    
+    /// Milestone filter
+    pub milestone: Option<String>,
+    
+    /// Due date filter
+    pub due: Option<DateFilter>,
+    
     /// Created date filter
     pub created: Option<DateFilter>,
     
     /// Updated date filter
     pub updated: Option<DateFilter>,
+
+    /// Parsed query string (`cue list -q`), combined with the other filters
+    pub query: Option<crate::query::Query>,
+
+    /// Also read `.cuedeck/archive` (implied by `status: archived`)
+    pub include_archived: bool,
+
+    /// Only cards without a live claim
+    pub unclaimed: bool,
 }
 
 /// Date filter with operator and value
@@ -42,6 +60,8 @@ pub enum DateOperator {
     After,
     /// Within relative time (updated>2w)
     Within,
+    /// Past due and still open (due:overdue)
+    Overdue,
 }
 
 /// Date value (absolute or relative)
@@ -84,6 +104,35 @@ pub fn parse_date_filter(input: &str) -> Result<DateFilter> {
     }
 }
 
+/// Parse due date filter from string
+/// Examples: "due<1w", "due:overdue", "<2024-06-01", "overdue"
+///
+/// Relative values look forward from today, so `due<1w` means due within the next week.
+pub fn parse_due_filter(input: &str) -> Result<DateFilter> {
+    let expr = input.strip_prefix("due").unwrap_or(input);
+    let expr = expr.strip_prefix(':').unwrap_or(expr);
+
+    if expr.eq_ignore_ascii_case("overdue") {
+        return Ok(DateFilter {
+            operator: DateOperator::Overdue,
+            value: DateValue::Relative(chrono::Duration::zero()),
+        });
+    }
+
+    parse_date_filter(expr)
+}
+
+/// Parse a due date (YYYY-MM-DD or RFC 3339 timestamp)
+pub fn parse_due_date(input: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(input)
+                .map(|dt| dt.date_naive())
+                .ok()
+        })
+}
+
 /// Parse date value (absolute or relative)
 fn parse_date_value(input: &str) -> Result<DateValue> {
     // Try relative duration first (2w, 7d, 1m, 3y)
@@ -109,7 +158,7 @@ fn parse_date_value(input: &str) -> Result<DateValue> {
 }
 
 /// Parse relative duration (7d, 2w, 3m, 1y)
-fn parse_relative_duration(input: &str) -> Option<chrono::Duration> {
+pub fn parse_relative_duration(input: &str) -> Option<chrono::Duration> {
     let re = regex::Regex::new(r"^(\d+)([dwmy])$").ok()?;
     let caps = re.captures(input)?;
     let num: i64 = caps.get(1)?.as_str().parse().ok()?;
@@ -194,6 +243,28 @@ pub fn matches_date_filter(
     }
 }
 
+/// Check if task due date matches filter
+///
+/// Unlike created/updated, relative durations are measured forward from today.
+pub fn matches_due_filter(due_str: &Option<String>, filter: &DateFilter) -> bool {
+    let Some(due) = due_str.as_deref().and_then(parse_due_date) else {
+        return false;
+    };
+
+    let today = chrono::Utc::now().date_naive();
+
+    match (&filter.operator, &filter.value) {
+        (DateOperator::Overdue, _) => due < today,
+        (DateOperator::Equals, DateValue::Absolute(date)) => due == *date,
+        (DateOperator::Before, DateValue::Absolute(date)) => due < *date,
+        (DateOperator::After, DateValue::Absolute(date)) => due > *date,
+        (DateOperator::Before, DateValue::Relative(dur))
+        | (DateOperator::Within, DateValue::Relative(dur)) => due <= today + *dur,
+        (DateOperator::After, DateValue::Relative(dur)) => due > today + *dur,
+        _ => false,
+    }
+}
+
 /// Check if file modification time matches filter (fallback for 'updated')
 pub fn matches_date_filter_mtime(
     mtime: &std::time::SystemTime,
@@ -273,6 +344,21 @@ mod tests {
         assert_eq!(filter.operator, DateOperator::Equals);
     }
 
+    #[test]
+    fn test_due_filter() {
+        let filter = parse_due_filter("due:overdue").unwrap();
+        assert_eq!(filter.operator, DateOperator::Overdue);
+        assert!(matches_due_filter(&Some("2000-01-01".to_string()), &filter));
+        assert!(!matches_due_filter(&Some("2999-01-01".to_string()), &filter));
+
+        let filter = parse_due_filter("due<1w").unwrap();
+        assert_eq!(filter.operator, DateOperator::Before);
+        let soon = (chrono::Utc::now().date_naive() + chrono::Duration::days(3)).to_string();
+        assert!(matches_due_filter(&Some(soon), &filter));
+        assert!(!matches_due_filter(&Some("2999-01-01".to_string()), &filter));
+        assert!(!matches_due_filter(&None, &filter));
+    }
+
     #[test]
     fn test_matches_tag_filter() {
         let task_tags = Some(vec!["auth".to_string(), "backend".to_string()]);
@@ -43,43 +43,38 @@ impl TaskGraph {
         use walkdir::WalkDir;
 
         let mut graph = Self::new();
-        let cards_dir = workspace_root.join(".cuedeck/cards");
 
-        if !cards_dir.exists() {
-            return Ok(graph);
-        }
+        // Archived cards stay in the graph so references to them resolve
+        let card_files: Vec<std::path::PathBuf> = [
+            workspace_root.join(".cuedeck/cards"),
+            crate::archive::archive_dir(workspace_root),
+        ]
+        .iter()
+        .filter(|dir| dir.exists())
+        .flat_map(|dir| WalkDir::new(dir).max_depth(1).into_iter().filter_map(|e| e.ok()))
+        .filter(|entry| {
+            entry.file_type().is_file() && entry.path().extension().is_some_and(|e| e == "md")
+        })
+        .map(|entry| entry.into_path())
+        .collect();
 
         // First pass: add all task nodes
-        for entry in WalkDir::new(&cards_dir)
-            .max_depth(1)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            if entry.file_type().is_file() && entry.path().extension().is_some_and(|e| e == "md")
-            {
-                // Extract task ID from filename (e.g., "abc123.md" -> "abc123")
-                if let Some(stem) = entry.path().file_stem().and_then(|s| s.to_str()) {
-                    graph.add_task(stem);
-                }
+        for path in &card_files {
+            // Extract task ID from filename (e.g., "abc123.md" -> "abc123")
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                graph.add_task(stem);
             }
         }
 
         // Second pass: add dependency edges by parsing frontmatter
-        for entry in WalkDir::new(&cards_dir)
-            .max_depth(1)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            if entry.file_type().is_file() && entry.path().extension().is_some_and(|e| e == "md")
-            {
-                if let Some(task_id) = entry.path().file_stem().and_then(|s| s.to_str()) {
-                    // Parse frontmatter to extract depends_on
-                    if let Ok(content) = fs::read_to_string(entry.path()) {
-                        if let Some(deps) = extract_depends_on(&content) {
-                            for dep_id in deps {
-                                // Ignore errors if dependency doesn't exist (validation will catch this later)
-                                let _ = graph.add_dependency(task_id, &dep_id);
-                            }
+        for path in &card_files {
+            if let Some(task_id) = path.file_stem().and_then(|s| s.to_str()) {
+                // Parse frontmatter to extract depends_on
+                if let Ok(content) = fs::read_to_string(path) {
+                    if let Some(deps) = extract_depends_on(&content) {
+                        for dep_id in deps {
+                            // Ignore errors if dependency doesn't exist (validation will catch this later)
+                            let _ = graph.add_dependency(task_id, &dep_id);
                         }
                     }
                 }
@@ -172,6 +167,38 @@ impl TaskGraph {
         is_cyclic_directed(&test_graph)
     }
 
+    /// Shortest dependency chain from `from` to `to`, both ends included
+    ///
+    /// Used to name the full cycle that a new `to -> from` edge would close.
+    pub fn dependency_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        use std::collections::VecDeque;
+
+        let &start = self.task_to_node.get(from)?;
+        let &goal = self.task_to_node.get(to)?;
+
+        let mut previous: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            if node == goal {
+                let mut path = vec![self.graph[node].clone()];
+                let mut current = node;
+                while let Some(&prev) = previous.get(&current) {
+                    path.push(self.graph[prev].clone());
+                    current = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in self.graph.neighbors(node) {
+                if next != start && !previous.contains_key(&next) {
+                    previous.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
     /// Find a cycle in the graph (for error reporting)
     fn find_cycle(&self) -> Option<Vec<String>> {
         use petgraph::visit::depth_first_search;
@@ -194,14 +221,12 @@ impl TaskGraph {
                         cycle_path.pop();
                     }
                 }
-                DfsEvent::BackEdge(_, target) => {
-                    // Found a back edge - this indicates a cycle
-                    if in_stack.contains(&target) {
-                        // Complete the cycle by  adding the target
-                        cycle_path.push(self.graph[target].clone());
-                        found_cycle = true;
-                        return petgraph::visit::Control::Break(());
-                    }
+                // Found a back edge - this indicates a cycle
+                DfsEvent::BackEdge(_, target) if in_stack.contains(&target) => {
+                    // Complete the cycle by  adding the target
+                    cycle_path.push(self.graph[target].clone());
+                    found_cycle = true;
+                    return petgraph::visit::Control::Break(());
                 }
                 _ => {}
             }
@@ -256,10 +281,9 @@ impl TaskGraph {
         use std::fs;
 
         let mut missing = Vec::new();
-        let cards_dir = workspace_root.join(".cuedeck/cards");
 
         for task_id in self.task_to_node.keys() {
-            let task_path = cards_dir.join(format!("{}.md", task_id));
+            let task_path = crate::archive::card_path(workspace_root, task_id);
             if let Ok(content) = fs::read_to_string(&task_path) {
                 if let Some(deps) = extract_depends_on(&content) {
                     for dep_id in deps {
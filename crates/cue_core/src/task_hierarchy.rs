@@ -0,0 +1,401 @@
+//! Parent/child (epic) hierarchy for task cards
+//!
+//! Cards point at their epic through the `parent` frontmatter field. This module
+//! builds the resulting tree, rolls up child progress onto epics and validates
+//! the hierarchy for doctor.
+
+use crate::doctor::{CheckStatus, HealthCheck};
+use crate::tasks::list_tasks;
+use cue_common::{CueError, Document, Result};
+use cue_config::WorkflowConfig;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Roll-up progress of an epic's direct children
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct EpicProgress {
+    /// Children in a terminal status (`[workflow] terminal`)
+    pub done: usize,
+    /// All children
+    pub total: usize,
+}
+
+/// A task card with its nested child cards
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskNode {
+    /// Task ID (filename stem)
+    pub id: String,
+
+    /// The parsed card
+    #[serde(flatten)]
+    pub document: Document,
+
+    /// Progress of direct children (only set for cards that have children)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<EpicProgress>,
+
+    /// Child cards, in list order
+    pub children: Vec<TaskNode>,
+}
+
+/// Extract the task ID from a card document
+fn doc_id(doc: &Document) -> Option<&str> {
+    doc.path.file_stem().and_then(|s| s.to_str())
+}
+
+/// Build a map of parent ID -> child documents
+fn children_by_parent(tasks: &[Document]) -> HashMap<String, Vec<&Document>> {
+    let mut map: HashMap<String, Vec<&Document>> = HashMap::new();
+    for doc in tasks {
+        if let Some(parent) = doc.frontmatter.as_ref().and_then(|m| m.parent.as_ref()) {
+            map.entry(parent.clone()).or_default().push(doc);
+        }
+    }
+    map
+}
+
+fn progress_of(children: &[&Document], workflow: &WorkflowConfig) -> EpicProgress {
+    let done = children
+        .iter()
+        .filter(|d| d.frontmatter.as_ref().is_some_and(|m| workflow.is_terminal(&m.status)))
+        .count();
+    EpicProgress {
+        done,
+        total: children.len(),
+    }
+}
+
+/// Get the direct children of a card
+pub fn get_children(workspace_root: &Path, parent_id: &str) -> Result<Vec<Document>> {
+    Ok(list_tasks(workspace_root, None, None)?
+        .into_iter()
+        .filter(|d| {
+            d.frontmatter
+                .as_ref()
+                .and_then(|m| m.parent.as_deref())
+                == Some(parent_id)
+        })
+        .collect())
+}
+
+/// Compute done/total progress of an epic's direct children
+pub fn epic_progress(workspace_root: &Path, epic_id: &str) -> Result<EpicProgress> {
+    let children = get_children(workspace_root, epic_id)?;
+    let refs: Vec<&Document> = children.iter().collect();
+    let workflow = cue_config::Config::load(workspace_root)?.workflow;
+    Ok(progress_of(&refs, &workflow))
+}
+
+/// IDs of direct children that are not closed yet
+pub fn open_children(workspace_root: &Path, epic_id: &str) -> Result<Vec<String>> {
+    let workflow = cue_config::Config::load(workspace_root)?.workflow;
+    Ok(get_children(workspace_root, epic_id)?
+        .iter()
+        .filter(|d| {
+            d.frontmatter
+                .as_ref()
+                .is_some_and(|m| !workflow.is_terminal(&m.status))
+        })
+        .filter_map(|d| doc_id(d).map(String::from))
+        .collect())
+}
+
+/// Validate that `parent_id` can become the parent of `task_id`
+///
+/// The parent card must exist and must not be the task itself or one of
+/// its descendants.
+pub fn validate_task_parent(workspace_root: &Path, task_id: &str, parent_id: &str) -> Result<()> {
+    if parent_id == task_id {
+        return Err(CueError::ValidationError(format!(
+            "Card {} cannot be its own parent",
+            task_id
+        )));
+    }
+
+    if !crate::archive::card_exists(workspace_root, parent_id) {
+        return Err(CueError::ValidationError(format!(
+            "Parent card not found: {}",
+            parent_id
+        )));
+    }
+
+    // Walk up from the new parent; reaching task_id means a cycle
+    let parents = parent_map(&list_tasks(workspace_root, None, None)?);
+    let mut chain = vec![task_id.to_string(), parent_id.to_string()];
+    let mut current = parent_id.to_string();
+    while let Some(next) = parents.get(&current) {
+        chain.push(next.clone());
+        if next == task_id {
+            return Err(CueError::CircularDependency(chain.join(" -> ")));
+        }
+        if chain.len() > parents.len() + 2 {
+            break; // Pre-existing cycle above us, reported by doctor
+        }
+        current = next.clone();
+    }
+
+    Ok(())
+}
+
+/// Map of task ID -> parent ID
+fn parent_map(tasks: &[Document]) -> HashMap<String, String> {
+    tasks
+        .iter()
+        .filter_map(|d| {
+            let parent = d.frontmatter.as_ref()?.parent.clone()?;
+            Some((doc_id(d)?.to_string(), parent))
+        })
+        .collect()
+}
+
+/// Arrange cards into a parent/child forest
+///
+/// Cards whose parent is not part of `tasks` become roots, so filtered
+/// listings still show every matching card. Progress is computed against
+/// all cards in the workspace, not just the filtered set.
+pub fn build_task_tree(workspace_root: &Path, tasks: Vec<Document>) -> Result<Vec<TaskNode>> {
+    let all_tasks = list_tasks(workspace_root, None, None)?;
+    let all_children = children_by_parent(&all_tasks);
+    let workflow = cue_config::Config::load(workspace_root)?.workflow;
+
+    let ids: HashSet<String> = tasks
+        .iter()
+        .filter_map(|d| doc_id(d).map(String::from))
+        .collect();
+
+    // Split into roots and children of listed cards, keeping list order
+    let mut roots = Vec::new();
+    let mut listed_children: HashMap<String, Vec<Document>> = HashMap::new();
+    for doc in tasks {
+        let parent = doc.frontmatter.as_ref().and_then(|m| m.parent.clone());
+        match parent {
+            Some(p) if ids.contains(&p) && doc_id(&doc) != Some(p.as_str()) => {
+                listed_children.entry(p).or_default().push(doc)
+            }
+            _ => roots.push(doc),
+        }
+    }
+
+    fn attach(
+        doc: Document,
+        listed_children: &mut HashMap<String, Vec<Document>>,
+        all_children: &HashMap<String, Vec<&Document>>,
+        workflow: &WorkflowConfig,
+        visited: &mut HashSet<String>,
+    ) -> TaskNode {
+        let id = doc_id(&doc).unwrap_or("unknown").to_string();
+        visited.insert(id.clone());
+
+        let progress = all_children.get(&id).map(|c| progress_of(c, workflow));
+        let mut children = Vec::new();
+        for child in listed_children.remove(&id).unwrap_or_default() {
+            if doc_id(&child).is_some_and(|cid| !visited.contains(cid)) {
+                children.push(attach(child, listed_children, all_children, workflow, visited));
+            }
+        }
+
+        TaskNode {
+            id,
+            document: doc,
+            progress,
+            children,
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut forest: Vec<TaskNode> = roots
+        .into_iter()
+        .map(|doc| attach(doc, &mut listed_children, &all_children, &workflow, &mut visited))
+        .collect();
+
+    // Cards caught in a parent cycle never hang off a root; surface them anyway
+    let leftovers: Vec<Document> = listed_children.into_values().flatten().collect();
+    let mut remaining = HashMap::new();
+    for doc in leftovers {
+        if doc_id(&doc).is_some_and(|id| !visited.contains(id)) {
+            forest.push(attach(doc, &mut remaining, &all_children, &workflow, &mut visited));
+        }
+    }
+
+    Ok(forest)
+}
+
+/// Check the epic hierarchy for problems
+///
+/// Reports closed epics that still have open children, parents that do not
+/// exist and parent chains that loop back on themselves.
+pub fn check_hierarchy(workspace_root: &Path) -> Result<Vec<HealthCheck>> {
+    let cards_dir = workspace_root.join(".cuedeck/cards");
+    if !cards_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let tasks = list_tasks(workspace_root, None, None)?;
+    let parents = parent_map(&tasks);
+    let children = children_by_parent(&tasks);
+    let workflow = cue_config::Config::load(workspace_root)?.workflow;
+    let mut issues = Vec::new();
+
+    for doc in &tasks {
+        let (Some(id), Some(meta)) = (doc_id(doc), doc.frontmatter.as_ref()) else {
+            continue;
+        };
+
+        if workflow.is_terminal(&meta.status) {
+            if let Some(kids) = children.get(id) {
+                let open: Vec<&str> = kids
+                    .iter()
+                    .filter(|k| {
+                        k.frontmatter
+                            .as_ref()
+                            .is_some_and(|m| !workflow.is_terminal(&m.status))
+                    })
+                    .filter_map(|k| doc_id(k))
+                    .collect();
+                if !open.is_empty() {
+                    issues.push(format!(
+                        "{}: Epic is {} but has {} open child card(s): {}",
+                        id,
+                        meta.status,
+                        open.len(),
+                        open.join(", ")
+                    ));
+                }
+            }
+        }
+
+        if let Some(parent) = &meta.parent {
+            if !cards_dir.join(format!("{}.md", parent)).exists() {
+                issues.push(format!("{}: Parent card '{}' not found", id, parent));
+            }
+        }
+    }
+
+    // Parent cycles: walk each chain and stop when a card repeats
+    let mut reported: HashSet<String> = HashSet::new();
+    let mut ids: Vec<&String> = parents.keys().collect();
+    ids.sort();
+    for start in ids {
+        let mut seen = vec![start.clone()];
+        let mut current = start;
+        while let Some(next) = parents.get(current) {
+            if let Some(pos) = seen.iter().position(|s| s == next) {
+                let mut cycle: Vec<String> = seen[pos..].to_vec();
+                if !cycle.iter().any(|c| reported.contains(c)) {
+                    reported.extend(cycle.iter().cloned());
+                    cycle.push(next.clone());
+                    issues.push(format!("Parent cycle: {}", cycle.join(" -> ")));
+                }
+                break;
+            }
+            seen.push(next.clone());
+            current = next;
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(vec![HealthCheck {
+            name: "Epic Hierarchy".to_string(),
+            status: CheckStatus::Pass,
+            message: "Epic hierarchy is consistent".to_string(),
+            details: None,
+            fixable: false,
+        }])
+    } else {
+        Ok(vec![HealthCheck {
+            name: "Epic Hierarchy".to_string(),
+            status: CheckStatus::Warn,
+            message: format!("Found {} hierarchy issue(s)", issues.len()),
+            details: Some(issues),
+            fixable: false,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn card(parent: Option<&str>, status: &str) -> String {
+        let parent_line = parent.map(|p| format!("parent: {}\n", p)).unwrap_or_default();
+        format!(
+            "---\ntitle: Card\nstatus: {}\npriority: medium\n{}---\n# Card",
+            status, parent_line
+        )
+    }
+
+    #[test]
+    fn test_epic_progress_counts_done_children() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        cards.child("epic01.md").write_str(&card(None, "active")).unwrap();
+        cards.child("kid001.md").write_str(&card(Some("epic01"), "done")).unwrap();
+        cards.child("kid002.md").write_str(&card(Some("epic01"), "todo")).unwrap();
+
+        let progress = epic_progress(temp.path(), "epic01").unwrap();
+        assert_eq!(progress, EpicProgress { done: 1, total: 2 });
+        assert_eq!(open_children(temp.path(), "epic01").unwrap(), vec!["kid002"]);
+    }
+
+    #[test]
+    fn test_build_task_tree_nests_children() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        cards.child("epic01.md").write_str(&card(None, "todo")).unwrap();
+        cards.child("kid001.md").write_str(&card(Some("epic01"), "todo")).unwrap();
+        cards.child("solo01.md").write_str(&card(None, "todo")).unwrap();
+
+        let tasks = list_tasks(temp.path(), None, None).unwrap();
+        let tree = build_task_tree(temp.path(), tasks).unwrap();
+
+        assert_eq!(tree.len(), 2);
+        let epic = tree.iter().find(|n| n.id == "epic01").unwrap();
+        assert_eq!(epic.children.len(), 1);
+        assert_eq!(epic.children[0].id, "kid001");
+        assert_eq!(epic.progress, Some(EpicProgress { done: 0, total: 1 }));
+    }
+
+    #[test]
+    fn test_validate_task_parent_rejects_cycle() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        cards.child("epic01.md").write_str(&card(None, "todo")).unwrap();
+        cards.child("kid001.md").write_str(&card(Some("epic01"), "todo")).unwrap();
+
+        let result = validate_task_parent(temp.path(), "epic01", "kid001");
+        assert!(matches!(result, Err(CueError::CircularDependency(_))));
+
+        let result = validate_task_parent(temp.path(), "kid001", "missing");
+        assert!(matches!(result, Err(CueError::ValidationError(_))));
+
+        // Archived epics can still be parents
+        temp.child(".cuedeck/archive/epic02.md")
+            .write_str(&card(None, "archived"))
+            .unwrap();
+        validate_task_parent(temp.path(), "kid001", "epic02").unwrap();
+        let options = crate::tasks::NewTaskOptions {
+            parent: Some("epic02".to_string()),
+            ..Default::default()
+        };
+        crate::tasks::create_task_with_options(temp.path(), "Late child", &options).unwrap();
+    }
+
+    #[test]
+    fn test_check_hierarchy_flags_closed_epic_with_open_children() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        cards.child("epic01.md").write_str(&card(None, "done")).unwrap();
+        cards.child("kid001.md").write_str(&card(Some("epic01"), "active")).unwrap();
+
+        let checks = check_hierarchy(temp.path()).unwrap();
+        assert_eq!(checks[0].status, CheckStatus::Warn);
+        let details = checks[0].details.as_ref().unwrap();
+        assert!(details[0].contains("open child card(s): kid001"));
+    }
+}
@@ -1,7 +1,12 @@
+use crate::frontmatter::Frontmatter;
 use crate::parse_file;
-use crate::task_filters::{matches_date_filter, matches_date_filter_mtime, matches_tag_filter, TaskFilters};
+use crate::task_filters::{
+    matches_date_filter, matches_date_filter_mtime, matches_due_filter, matches_tag_filter,
+    DateOperator, TaskFilters,
+};
 use cue_common::{CueError, Document, Result};
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 /// List all task cards in the workspace, optionally filtering
@@ -11,18 +16,9 @@ pub fn list_tasks(
     status_filter: Option<&str>,
     assignee_filter: Option<&str>,
 ) -> Result<Vec<Document>> {
-    let cards_dir = workspace_root.join(".cuedeck/cards");
     let mut tasks = Vec::new();
 
-    if !cards_dir.exists() {
-        return Ok(tasks);
-    }
-
-    for entry in walkdir::WalkDir::new(&cards_dir)
-        .max_depth(1)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    for entry in card_entries(workspace_root, status_filter == Some("archived")) {
         if entry.file_type().is_file() && entry.path().extension().is_some_and(|e| e == "md") {
             // Parse the file
             match parse_file(entry.path()) {
@@ -47,34 +43,70 @@ pub fn list_tasks(
         }
     }
 
-    // Sort by created date (newest first)? Or Priority?
-    // Let's sort by priority then created.
-    // For now simple sort.
-    tasks.sort_by(|a, b| {
-        let p_a = a
-            .frontmatter
-            .as_ref()
-            .map(|m| priority_score(&m.priority))
-            .unwrap_or(0);
-        let p_b = b
-            .frontmatter
-            .as_ref()
-            .map(|m| priority_score(&m.priority))
-            .unwrap_or(0);
-        p_b.cmp(&p_a) // Higher priority first
-    });
-
+    sort_by_priority(&mut tasks, &cue_config::Config::load(workspace_root)?.priorities);
     Ok(tasks)
 }
 
-fn priority_score(p: &str) -> i32 {
-    match p.to_lowercase().as_str() {
-        "critical" => 4,
-        "high" => 3,
-        "medium" => 2,
-        "low" => 1,
-        _ => 0,
+/// Card files in the cards directory, plus the archive if requested
+fn card_entries(workspace_root: &Path, include_archived: bool) -> impl Iterator<Item = walkdir::DirEntry> {
+    let mut dirs = vec![workspace_root.join(".cuedeck/cards")];
+    if include_archived {
+        dirs.push(crate::archive::archive_dir(workspace_root));
     }
+
+    dirs.into_iter()
+        .filter(|dir| dir.exists())
+        .flat_map(|dir| walkdir::WalkDir::new(dir).max_depth(1).into_iter().filter_map(|e| e.ok()))
+}
+
+/// Sort cards most urgent first, using the configured priority scale
+fn sort_by_priority(tasks: &mut [Document], priorities: &cue_config::PriorityConfig) {
+    tasks.sort_by_key(|doc| {
+        std::cmp::Reverse(doc.frontmatter.as_ref().map_or(0, |m| priorities.score(&m.priority)))
+    });
+}
+
+/// Optional metadata for a new task card
+#[derive(Debug, Default, Clone)]
+pub struct NewTaskOptions {
+    /// Tags/Labels
+    pub tags: Option<Vec<String>>,
+
+    /// Priority (defaults to "medium")
+    pub priority: Option<String>,
+
+    /// Assignee
+    pub assignee: Option<String>,
+
+    /// Task IDs this task depends on
+    pub depends_on: Option<Vec<String>>,
+
+    /// Parent (epic) card ID
+    pub parent: Option<String>,
+
+    /// Due date (YYYY-MM-DD)
+    pub due: Option<String>,
+
+    /// Milestone name
+    pub milestone: Option<String>,
+
+    /// Initial status (defaults to "todo")
+    pub status: Option<String>,
+
+    /// Template name from `.cuedeck/templates/`
+    pub template: Option<String>,
+
+    /// Recurring template this card is an instance of
+    pub recurrence_of: Option<String>,
+
+    /// Previous instance of the same recurring template
+    pub previous_instance: Option<String>,
+
+    /// Where an imported card came from (e.g. `github#12`)
+    pub external_id: Option<String>,
+
+    /// Description for the default body (ignored when a template is used)
+    pub description: Option<String>,
 }
 
 /// Create a new task card
@@ -91,43 +123,105 @@ pub fn create_task_with_metadata(
     assignee: Option<&str>,
     depends_on: Option<Vec<String>>,
 ) -> Result<PathBuf> {
-    use rand::Rng;
+    let options = NewTaskOptions {
+        tags,
+        priority: priority.map(String::from),
+        assignee: assignee.map(String::from),
+        depends_on,
+        ..Default::default()
+    };
+    create_task_with_options(workspace_root, title, &options)
+}
+
+/// Create a new task card from a full set of options
+pub fn create_task_with_options(
+    workspace_root: &Path,
+    title: &str,
+    options: &NewTaskOptions,
+) -> Result<PathBuf> {
+    // Template defaults fill any options the caller left unset
+    let template = options
+        .template
+        .as_deref()
+        .map(|name| crate::templates::load_template(workspace_root, name))
+        .transpose()?;
+    let merged;
+    let options = match &template {
+        Some(t) => {
+            merged = t.apply_defaults(options);
+            &merged
+        }
+        None => options,
+    };
+
+    // Validate due date format
+    if let Some(due) = &options.due {
+        if crate::task_filters::parse_due_date(due).is_none() {
+            return Err(CueError::ValidationError(format!(
+                "Invalid due date '{}'. Expected: YYYY-MM-DD",
+                due
+            )));
+        }
+    }
+
+    // Validate parent exists before creating task
+    if let Some(parent_id) = &options.parent {
+        if !crate::archive::card_exists(workspace_root, parent_id) {
+            return Err(CueError::ValidationError(format!(
+                "Parent card not found: {}",
+                parent_id
+            )));
+        }
+    }
 
     // Validate dependencies exist before creating task
-    if let Some(deps) = &depends_on {
+    if let Some(deps) = &options.depends_on {
         for dep_id in deps {
-            let dep_path = workspace_root
-                .join(".cuedeck/cards")
-                .join(format!("{}.md", dep_id));
-            if !dep_path.exists() {
+            if !crate::archive::card_exists(workspace_root, dep_id) {
                 return Err(CueError::DependencyNotFound(dep_id.clone()));
             }
         }
+    }
 
-        // Generate task ID first for validation
-        let id: String = rand::thread_rng()
-            .sample_iter(&rand::distributions::Alphanumeric)
-            .take(6)
-            .map(char::from)
-            .collect::<String>()
-            .to_lowercase();
+    // Validate status and priority against the configured vocabularies
+    let config = cue_config::Config::load(workspace_root)?;
+    if let Some(status) = &options.status {
+        if !config.workflow.is_known(status) {
+            return Err(CueError::ValidationError(format!(
+                "Unknown status '{}'. Expected one of: {}",
+                status,
+                config.workflow.statuses.join(", ")
+            )));
+        }
+    }
+    let mut options = options.clone();
+    options.priority = Some(match &options.priority {
+        Some(p) => config.priorities.validate(p)?,
+        None => config.priorities.default.clone(),
+    });
+    let options = &options;
+
+    // Allocate an ID; the exclusive create in create_task_with_id rejects
+    // any candidate claimed since it was proposed
+    let id_config = config.ids;
+    for _ in 0..crate::card_ids::MAX_ALLOCATION_ATTEMPTS {
+        let id = crate::card_ids::next_candidate_id(workspace_root, &id_config)?;
 
         // Check for circular dependencies before creating task
-        validate_task_dependencies(workspace_root, &id, deps)?;
+        if let Some(deps) = &options.depends_on {
+            validate_task_dependencies(workspace_root, &id, deps)?;
+        }
 
-        // Continue with the generated ID
-        return create_task_with_id(workspace_root, &id, title, tags, priority, assignee, depends_on);
+        match create_task_with_id(workspace_root, &id, title, options, template.as_ref()) {
+            Err(CueError::IoError(e)) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            result => return result,
+        }
     }
 
-    // No dependencies, create task normally
-    let id: String = rand::thread_rng()
-        .sample_iter(&rand::distributions::Alphanumeric)
-        .take(6)
-        .map(char::from)
-        .collect::<String>()
-        .to_lowercase();
-    
-    create_task_with_id(workspace_root, &id, title, tags, priority, assignee, depends_on)
+    Err(CueError::ValidationError(format!(
+        "Could not allocate a unique card ID after {} attempts",
+        crate::card_ids::MAX_ALLOCATION_ATTEMPTS
+    )))
 }
 
 /// Internal helper to create task with pre-generated ID
@@ -135,10 +229,8 @@ fn create_task_with_id(
     workspace_root: &Path,
     id: &str,
     title: &str,
-    tags: Option<Vec<String>>,
-    priority: Option<&str>,
-    assignee: Option<&str>,
-    depends_on: Option<Vec<String>>,
+    options: &NewTaskOptions,
+    template: Option<&crate::templates::CardTemplate>,
 ) -> Result<PathBuf> {
 
     let filename = workspace_root
@@ -151,75 +243,208 @@ fn create_task_with_id(
     }
 
     // Build frontmatter with optional fields
-    let priority_str = priority.unwrap_or("medium");
+    let priority_str = options.priority.as_deref().unwrap_or("medium");
+    let status_str = options.status.as_deref().unwrap_or("todo");
     let created_str = chrono::Utc::now().to_rfc3339();
 
-    let mut frontmatter = format!(
-        r#"---
-title: {}
-status: todo
-priority: {}
-created: {}"#,
-        title, priority_str, created_str
-    );
-
-    // Add assignee if provided (don't quote if starts with @)
-    if let Some(a) = assignee {
-        if !a.is_empty() {
-            if a.starts_with('@') {
-                frontmatter.push_str(&format!("\nassignee: {}", a));
-            } else {
-                frontmatter.push_str(&format!("\nassignee: \"{}\"", a));
-            }
-        }
+    let mut frontmatter = Frontmatter::default();
+    frontmatter.set_str("title", title);
+    frontmatter.set_str("status", status_str);
+    frontmatter.set_str("priority", priority_str);
+    frontmatter.set_str("created", &created_str);
+
+    // Add assignee if provided
+    if let Some(a) = options.assignee.as_deref().filter(|a| !a.is_empty()) {
+        frontmatter.set_str("assignee", a);
     }
 
-    // Add tags if provided
-    if let Some(tag_list) = tags {
-        if !tag_list.is_empty() {
-            frontmatter.push_str("\ntags:");
-            for tag in tag_list {
-                frontmatter.push_str(&format!("\n  - {}", tag));
-            }
-        }
+    // Add tags and depends_on if provided
+    let string_list = |items: &[String]| {
+        serde_yaml::Value::Sequence(items.iter().cloned().map(serde_yaml::Value::String).collect())
+    };
+    if let Some(tag_list) = options.tags.as_deref().filter(|t| !t.is_empty()) {
+        frontmatter.set("tags", &string_list(tag_list));
+    }
+    if let Some(dep_list) = options.depends_on.as_deref().filter(|d| !d.is_empty()) {
+        frontmatter.set("depends_on", &string_list(dep_list));
     }
 
-    // Add depends_on if provided
-    if let Some(dep_list) = depends_on {
-        if !dep_list.is_empty() {
-            frontmatter.push_str("\ndepends_on:");
-            for dep in dep_list {
-                frontmatter.push_str(&format!("\n  - {}", dep));
-            }
-        }
+    // Add parent (epic) if provided
+    if let Some(parent_id) = &options.parent {
+        frontmatter.set_str("parent", parent_id);
+    }
+
+    // Add due date and milestone if provided
+    if let Some(due) = &options.due {
+        frontmatter.set_str("due", due);
+    }
+    if let Some(milestone) = &options.milestone {
+        frontmatter.set_str("milestone", milestone);
+    }
+
+    // Add recurrence links if provided
+    if let Some(template_id) = &options.recurrence_of {
+        frontmatter.set_str("recurrence_of", template_id);
+    }
+    if let Some(previous) = &options.previous_instance {
+        frontmatter.set_str("previous_instance", previous);
+    }
+
+    // Add import source if provided
+    if let Some(external_id) = &options.external_id {
+        frontmatter.set_str("external_id", external_id);
     }
 
-    frontmatter.push_str("\n---\n");
+    let content = match template {
+        Some(t) => {
+            let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+            let author = crate::activity::current_author(workspace_root).unwrap_or_default();
+            let vars = crate::templates::TemplateVars {
+                title,
+                id,
+                date: &date,
+                author: &author,
+            };
+
+            // Template-only fields (e.g. `type: bug`) are copied as-is
+            for (key, value) in t.extra_fields(&vars) {
+                if let Some(key) = key.as_str() {
+                    frontmatter.set(key, &value);
+                }
+            }
 
-    let template = format!(
-        r#"{}
+            format!("{}{}", frontmatter, t.render_body(&vars))
+        }
+        None => format!(
+            r#"{}
 # {}
 
 ## Description
 
-[Add description]
+{}
 "#,
-        frontmatter, title
-    );
+            frontmatter,
+            title,
+            options.description.as_deref().unwrap_or("[Add description]")
+        ),
+    };
+
+    // create_new fails if the ID is already taken instead of overwriting a card
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&filename)?;
+    file.write_all(content.as_bytes())?;
+
+    // Record creation with the initial field values
+    let initial = frontmatter.mapping().unwrap_or_default();
+    let changes = crate::activity::diff_frontmatter(&serde_yaml::Mapping::new(), &initial);
+    crate::activity::record_event_lossy(workspace_root, id, "created", changes);
 
-    fs::write(&filename, template)?;
     Ok(filename)
 }
 
-/// Update a task's metadata
-pub fn update_task(
+/// Frontmatter lists that accept add/remove operations in `update_task`
+pub const LIST_FIELDS: &[&str] = &["tags", "depends_on"];
+
+/// Resolve an update to a list field into the new list
+///
+/// Accepts a full replacement (`["a", "b"]`), `null` to clear, or
+/// `{"add": [...], "remove": [...]}` applied to the current values.
+fn apply_list_update(
+    current: Option<&serde_yaml::Value>,
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Option<Vec<String>>> {
+    let strings = |v: &serde_json::Value, what: &str| -> Result<Vec<String>> {
+        let items = match v {
+            serde_json::Value::Array(items) => items,
+            serde_json::Value::String(s) => return Ok(vec![s.clone()]),
+            _ => {
+                return Err(CueError::ValidationError(format!(
+                    "'{}' {} must be a list of strings",
+                    field, what
+                )))
+            }
+        };
+        items
+            .iter()
+            .map(|item| {
+                item.as_str().map(String::from).ok_or_else(|| {
+                    CueError::ValidationError(format!(
+                        "'{}' {} must be a list of strings",
+                        field, what
+                    ))
+                })
+            })
+            .collect()
+    };
+
+    match value {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::Object(ops) => {
+            if let Some(unknown) = ops.keys().find(|k| *k != "add" && *k != "remove") {
+                return Err(CueError::ValidationError(format!(
+                    "Unknown operation '{}' for '{}' (expected add or remove)",
+                    unknown, field
+                )));
+            }
+
+            let mut list: Vec<String> = current
+                .and_then(|v| v.as_sequence())
+                .map(|seq| seq.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                .unwrap_or_default();
+
+            if let Some(remove) = ops.get("remove") {
+                let remove = strings(remove, "remove")?;
+                list.retain(|item| !remove.iter().any(|r| r.eq_ignore_ascii_case(item)));
+            }
+            if let Some(add) = ops.get("add") {
+                for item in strings(add, "add")? {
+                    if !list.iter().any(|existing| existing.eq_ignore_ascii_case(&item)) {
+                        list.push(item);
+                    }
+                }
+            }
+            Ok(Some(list))
+        }
+        other => strings(other, "value").map(Some),
+    }
+}
+
+/// A card update that has been computed but not yet written
+#[derive(Debug, Clone)]
+pub(crate) struct PendingUpdate {
+    pub id: String,
+    pub path: PathBuf,
+    pub old_content: String,
+    pub new_content: String,
+    pub changes: Vec<crate::activity::FieldChange>,
+}
+
+impl PendingUpdate {
+    /// Activity log action; archiving gets its own action
+    pub fn action(&self) -> &'static str {
+        let archived = self.changes.iter().any(|c| {
+            c.field == "status" && c.new.as_ref().and_then(|v| v.as_str()) == Some("archived")
+        });
+        let claim = self.changes.iter().find(|c| c.field == "claimed_by");
+        match claim {
+            _ if archived => "archived",
+            Some(c) if c.new.is_some() => "claimed",
+            Some(_) => "released",
+            None => "updated",
+        }
+    }
+}
+
+/// Compute the new content of a card without writing it
+pub(crate) fn prepare_update(
     workspace_root: &Path,
     id: &str,
-    updates: serde_json::Map<String, serde_json::Value>,
-) -> Result<Document> {
-    let path = workspace_root
-        .join(".cuedeck/cards")
-        .join(format!("{}.md", id));
+    updates: &serde_json::Map<String, serde_json::Value>,
+) -> Result<PendingUpdate> {
+    let path = crate::archive::card_path(workspace_root, id);
 
     if !path.exists() {
         return Err(CueError::FileNotFound {
@@ -227,67 +452,172 @@ pub fn update_task(
         });
     }
 
-    // Read and Parse
-    // usage of parse_file is good for reading, but writing back requires manipulating raw content
-    // to preserve body while updating frontmatter.
+    // Validate parent (epic) changes before touching the file
+    if let Some(serde_json::Value::String(parent_id)) = updates.get("parent") {
+        crate::task_hierarchy::validate_task_parent(workspace_root, id, parent_id)?;
+    }
 
+    // Edit the frontmatter in place so untouched lines (comments, quoting,
+    // key order) and the body are preserved byte for byte
     let content = fs::read_to_string(&path)?;
-    let frontmatter_regex = regex::Regex::new(r"(?ms)^---\r?\n(.*?)\r?\n---").unwrap();
-
-    if let Some(captures) = frontmatter_regex.captures(&content) {
-        let yaml_str = captures.get(1).unwrap().as_str();
-        let mut meta: serde_yaml::Value =
-            serde_yaml::from_str(yaml_str).map_err(|e| CueError::ParseError(e.to_string()))?;
-
-        // Apply updates
-        if let serde_yaml::Value::Mapping(ref mut map) = meta {
-            for (k, v) in updates {
-                // Convert JSON value to YAML value (basic types)
-                let yaml_v = match v {
-                    serde_json::Value::String(s) => serde_yaml::Value::String(s),
-                    serde_json::Value::Bool(b) => serde_yaml::Value::Bool(b),
-                    serde_json::Value::Number(n) => {
-                        if let Some(i) = n.as_i64() {
-                            serde_yaml::Value::Number(i.into())
-                        } else if let Some(f) = n.as_f64() {
-                            serde_yaml::Value::Number(f.into())
-                        } else {
-                            continue;
-                        }
-                    }
-                    serde_json::Value::Null => serde_yaml::Value::Null,
-                    _ => continue, // Skip arrays/objects for now
-                };
-                map.insert(serde_yaml::Value::String(k), yaml_v);
+    let mut frontmatter = Frontmatter::parse(&content)?;
+    let before = frontmatter.mapping()?;
+
+    // Status changes must follow the configured workflow
+    let config = cue_config::Config::load(workspace_root)?;
+    if let Some(status) = updates.get("status") {
+        let to = status.as_str().ok_or_else(|| {
+            CueError::ValidationError("Field 'status' must be a string".to_string())
+        })?;
+        let from = before.get("status").and_then(|v| v.as_str()).unwrap_or("todo");
+        config.workflow.check_transition(from, to)?;
+    }
+
+    // Priorities are stored under their canonical level name
+    let priority = match updates.get("priority") {
+        Some(serde_json::Value::String(p)) => Some(config.priorities.validate(p)?),
+        _ => None,
+    };
+
+    // Apply updates
+    for (k, v) in updates {
+        if let (Some(level), "priority") = (&priority, k.as_str()) {
+            frontmatter.set_str(k, level);
+            continue;
+        }
+
+        // Estimates are numbers; the CLI passes every value as a string
+        if k == "estimate" && !v.is_null() {
+            let estimate = v
+                .as_f64()
+                .or_else(|| v.as_str().and_then(|s| s.trim().parse::<f64>().ok()))
+                .filter(|e| e.is_finite() && *e >= 0.0)
+                .ok_or_else(|| {
+                    CueError::ValidationError(format!(
+                        "Field 'estimate' must be a non-negative number, got {}",
+                        v
+                    ))
+                })?;
+            let value = serde_yaml::to_value(estimate).map_err(|e| CueError::ParseError(e.to_string()))?;
+            frontmatter.set(k, &value);
+            continue;
+        }
+
+        if LIST_FIELDS.contains(&k.as_str()) {
+            let list = apply_list_update(before.get(k.as_str()), k, v)?;
+            if k == "depends_on" {
+                validate_task_dependencies(workspace_root, id, list.as_deref().unwrap_or_default())?;
             }
-            
-            // Auto-set 'updated' timestamp
-            let updated_str = chrono::Utc::now().to_rfc3339();
-            map.insert(
-                serde_yaml::Value::String("updated".to_string()),
-                serde_yaml::Value::String(updated_str),
-            );
+            match list {
+                Some(items) if !items.is_empty() => {
+                    let items = items.into_iter().map(serde_yaml::Value::String).collect();
+                    frontmatter.set(k, &serde_yaml::Value::Sequence(items));
+                }
+                _ => {
+                    frontmatter.remove(k);
+                }
+            }
+            continue;
         }
 
-        let new_yaml =
-            serde_yaml::to_string(&meta).map_err(|e| CueError::ParseError(e.to_string()))?;
+        // Convert JSON value to YAML value; null clears the field
+        let yaml_v = match v {
+            serde_json::Value::Null => {
+                frontmatter.remove(k);
+                continue;
+            }
+            serde_json::Value::Object(_) => {
+                return Err(CueError::ValidationError(format!(
+                    "Field '{}' does not accept an object",
+                    k
+                )))
+            }
+            other => serde_yaml::to_value(other).map_err(|e| CueError::ParseError(e.to_string()))?,
+        };
+        frontmatter.set(k, &yaml_v);
+    }
+
+    // Auto-set 'updated' timestamp
+    frontmatter.set_str("updated", &chrono::Utc::now().to_rfc3339());
 
-        // Reconstruct content
-        let body_start = captures.get(0).unwrap().end();
-        let new_content = format!("---\n{}---{}", new_yaml.trim(), &content[body_start..]);
+    let new_content = frontmatter.to_string();
+    let after = frontmatter.mapping()?;
+    let changes = crate::activity::diff_frontmatter(&before, &after);
+
+    Ok(PendingUpdate {
+        id: id.to_string(),
+        path,
+        old_content: content,
+        new_content,
+        changes,
+    })
+}
 
-        fs::write(&path, new_content)?;
+/// Update a task's metadata
+pub fn update_task(
+    workspace_root: &Path,
+    id: &str,
+    updates: serde_json::Map<String, serde_json::Value>,
+) -> Result<Document> {
+    update_task_checked(workspace_root, id, updates, None)
+}
 
-        // Return updated doc
-        parse_file(&path)
+/// Update a task's metadata if it still has the content hash the caller read
+///
+/// The card is read, checked and written under its lock file, so concurrent
+/// updates are applied one after another. With `expected_hash` set (a
+/// `Document::hash`), the update fails with `CueError::Conflict` if the card
+/// changed since, so concurrent writers cannot silently overwrite each other.
+pub fn update_task_checked(
+    workspace_root: &Path,
+    id: &str,
+    updates: serde_json::Map<String, serde_json::Value>,
+    expected_hash: Option<&str>,
+) -> Result<Document> {
+    // A missing card is reported by prepare_update
+    let path = crate::archive::card_path(workspace_root, id);
+    let _lock = if path.exists() {
+        Some(crate::FileLock::acquire(&path)?)
     } else {
-        Err(CueError::ParseError(
-            "No frontmatter found in card".to_string(),
-        ))
+        None
+    };
+
+    let pending = prepare_update(workspace_root, id, &updates)?;
+    if let Some(expected) = expected_hash {
+        // The update was computed from old_content, so that is what must match
+        if crate::content_hash(&pending.old_content) != expected {
+            return Err(conflict(&pending.path, &pending.old_content));
+        }
+    }
+    crate::write_atomic(&pending.path, &pending.new_content)?;
+
+    // Archiving moves the card out of the cards directory (and back)
+    let path = crate::archive::settle_card(workspace_root, &pending.path)?;
+
+    // Append to the activity log
+    crate::activity::record_event_lossy(workspace_root, id, pending.action(), pending.changes);
+
+    // Return updated doc
+    parse_file(&path)
+}
+
+fn conflict(path: &Path, content: &str) -> CueError {
+    let frontmatter = Frontmatter::parse(content)
+        .and_then(|f| f.mapping())
+        .ok()
+        .and_then(|m| serde_yaml::from_value(serde_yaml::Value::Mapping(m)).ok())
+        .map(Box::new);
+    CueError::Conflict {
+        path: path.to_string_lossy().to_string(),
+        current_hash: crate::content_hash(content),
+        frontmatter,
     }
 }
 
 /// Validate task dependencies don't create cycles
+///
+/// `new_deps` replaces the task's current dependencies. A cycle is reported
+/// as the full chain, e.g. `a -> b -> c -> a`.
 pub fn validate_task_dependencies(
     workspace_root: &Path,
     task_id: &str,
@@ -296,28 +626,29 @@ pub fn validate_task_dependencies(
     use crate::task_graph::TaskGraph;
 
     // Build current task graph
-    let mut graph = TaskGraph::from_workspace(workspace_root)?;
+    let graph = TaskGraph::from_workspace(workspace_root)?;
 
-    // Simulate adding new dependencies
     for dep_id in new_deps {
-        // Check if would create cycle
-        if graph.would_create_cycle(task_id, dep_id) {
+        if dep_id == task_id {
             return Err(CueError::CircularDependency(format!(
                 "{} -> {}",
                 task_id, dep_id
             )));
         }
 
-        // Check if dependency exists
-        let dep_path = workspace_root
-            .join(".cuedeck/cards")
-            .join(format!("{}.md", dep_id));
-        if !dep_path.exists() {
+        // Check if dependency exists (archived cards count)
+        if !crate::archive::card_exists(workspace_root, dep_id) {
             return Err(CueError::DependencyNotFound(dep_id.clone()));
         }
 
-        // Add temporarily to check next dependency
-        graph.add_dependency(task_id, dep_id)?;
+        // A chain back from the dependency to this task closes a cycle
+        if let Some(chain) = graph.dependency_path(dep_id, task_id) {
+            return Err(CueError::CircularDependency(format!(
+                "{} -> {}",
+                task_id,
+                chain.join(" -> ")
+            )));
+        }
     }
 
     Ok(())
@@ -367,18 +698,15 @@ pub fn list_tasks_filtered(
     workspace_root: &Path,
     filters: &TaskFilters,
 ) -> Result<Vec<Document>> {
-    let cards_dir = workspace_root.join(".cuedeck/cards");
+    // A status term in the query may select archived cards
+    let include_archived = filters.include_archived
+        || filters.status.as_deref() == Some("archived")
+        || filters.query.as_ref().is_some_and(|q| q.mentions_field("status"));
+    let config = cue_config::Config::load(workspace_root)?;
+    let now = chrono::Utc::now();
     let mut tasks = Vec::new();
 
-    if !cards_dir.exists() {
-        return Ok(tasks);
-    }
-
-    for entry in walkdir::WalkDir::new(&cards_dir)
-        .max_depth(1)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    for entry in card_entries(workspace_root, include_archived) {
         if entry.file_type().is_file() && entry.path().extension().is_some_and(|e| e == "md") {
             match parse_file(entry.path()) {
                 Ok(doc) => {
@@ -399,7 +727,37 @@ pub fn list_tasks_filtered(
 
                         // Priority filter
                         if let Some(priority) = &filters.priority {
-                            if meta.priority.to_lowercase() != priority.to_lowercase() {
+                            let same = match (config.priorities.resolve(&meta.priority), config.priorities.resolve(priority)) {
+                                (Some(a), Some(b)) => a == b,
+                                _ => meta.priority.eq_ignore_ascii_case(priority),
+                            };
+                            if !same {
+                                continue;
+                            }
+                        }
+
+                        // Parent (epic) filter
+                        if let Some(parent) = &filters.parent {
+                            if meta.parent.as_deref() != Some(parent.as_str()) {
+                                continue;
+                            }
+                        }
+
+                        // Milestone filter
+                        if let Some(milestone) = &filters.milestone {
+                            if meta.milestone.as_deref() != Some(milestone.as_str()) {
+                                continue;
+                            }
+                        }
+
+                        // Due date filter (overdue only counts open tasks)
+                        if let Some(due_filter) = &filters.due {
+                            if due_filter.operator == DateOperator::Overdue
+                                && config.workflow.is_terminal(&meta.status)
+                            {
+                                continue;
+                            }
+                            if !matches_due_filter(&meta.due, due_filter) {
                                 continue;
                             }
                         }
@@ -439,6 +797,18 @@ pub fn list_tasks_filtered(
                             }
                         }
 
+                        // Query language filter
+                        if let Some(query) = &filters.query {
+                            if !query.matches(&doc, &config) {
+                                continue;
+                            }
+                        }
+
+                        // Claim filter (expired claims count as unclaimed)
+                        if filters.unclaimed && crate::claims::active_claim(meta, now).is_some() {
+                            continue;
+                        }
+
                         tasks.push(doc);
                     }
                 }
@@ -447,21 +817,7 @@ pub fn list_tasks_filtered(
         }
     }
 
-    // Sort by priority then created (existing logic)
-    tasks.sort_by(|a, b| {
-        let p_a = a
-            .frontmatter
-            .as_ref()
-            .map(|m| priority_score(&m.priority))
-            .unwrap_or(0);
-        let p_b = b
-            .frontmatter
-            .as_ref()
-            .map(|m| priority_score(&m.priority))
-            .unwrap_or(0);
-        p_b.cmp(&p_a) // Higher priority first
-    });
-
+    sort_by_priority(&mut tasks, &config.priorities);
     Ok(tasks)
 }
 
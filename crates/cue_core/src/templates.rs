@@ -0,0 +1,328 @@
+//! Card templates
+//!
+//! Templates live in `.cuedeck/templates/<name>.md`. Their frontmatter supplies
+//! default metadata for new cards and their body replaces the stock card body.
+//! `{{title}}`, `{{id}}`, `{{date}}` and `{{author}}` placeholders are filled in
+//! when the card is created.
+
+use crate::doctor::{CheckStatus, HealthCheck};
+use crate::tasks::NewTaskOptions;
+use cue_common::{CueError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Templates directory, relative to the workspace root
+pub const TEMPLATES_DIR: &str = ".cuedeck/templates";
+
+/// Placeholders that are substituted on create
+pub const PLACEHOLDERS: &[&str] = &["title", "id", "date", "author"];
+
+/// Frontmatter keys that are always set by create and cannot be templated
+const RESERVED_KEYS: &[&str] = &["title", "created", "updated"];
+
+/// Keys that map onto `NewTaskOptions` rather than being copied verbatim
+const OPTION_KEYS: &[&str] = &[
+    "status",
+    "priority",
+    "assignee",
+    "tags",
+    "depends_on",
+    "parent",
+    "due",
+    "milestone",
+];
+
+/// A parsed card template
+#[derive(Debug, Clone)]
+pub struct CardTemplate {
+    pub name: String,
+    /// Frontmatter defaults
+    pub defaults: serde_yaml::Mapping,
+    /// Body with unrendered placeholders
+    pub body: String,
+}
+
+/// Values substituted into template placeholders
+pub struct TemplateVars<'a> {
+    pub title: &'a str,
+    pub id: &'a str,
+    pub date: &'a str,
+    pub author: &'a str,
+}
+
+fn template_path(workspace_root: &Path, name: &str) -> PathBuf {
+    workspace_root
+        .join(TEMPLATES_DIR)
+        .join(format!("{}.md", name))
+}
+
+/// Parse template file content
+fn parse_template(name: &str, content: &str) -> Result<CardTemplate> {
+    let frontmatter_regex = regex::Regex::new(r"(?ms)^---\r?\n(.*?)\r?\n---\r?\n?").unwrap();
+
+    let Some(captures) = frontmatter_regex.captures(content) else {
+        return Ok(CardTemplate {
+            name: name.to_string(),
+            defaults: serde_yaml::Mapping::new(),
+            body: content.to_string(),
+        });
+    };
+
+    let yaml_str = captures.get(1).unwrap().as_str();
+    let defaults = match serde_yaml::from_str::<serde_yaml::Value>(yaml_str)
+        .map_err(|e| CueError::ParseError(format!("Template '{}': {}", name, e)))?
+    {
+        serde_yaml::Value::Mapping(map) => map,
+        serde_yaml::Value::Null => serde_yaml::Mapping::new(),
+        _ => {
+            return Err(CueError::ParseError(format!(
+                "Template '{}': frontmatter must be a mapping",
+                name
+            )))
+        }
+    };
+
+    Ok(CardTemplate {
+        name: name.to_string(),
+        defaults,
+        body: content[captures.get(0).unwrap().end()..].to_string(),
+    })
+}
+
+/// Load a template by name
+pub fn load_template(workspace_root: &Path, name: &str) -> Result<CardTemplate> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(CueError::ValidationError(format!(
+            "Invalid template name: {}",
+            name
+        )));
+    }
+
+    let path = template_path(workspace_root, name);
+    if !path.exists() {
+        return Err(CueError::ValidationError(format!(
+            "Template not found: {} (expected {})",
+            name,
+            path.display()
+        )));
+    }
+
+    parse_template(name, &fs::read_to_string(&path)?)
+}
+
+/// Names of all templates in the workspace, sorted
+pub fn list_templates(workspace_root: &Path) -> Result<Vec<String>> {
+    let dir = workspace_root.join(TEMPLATES_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut names: Vec<String> = fs::read_dir(&dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "md"))
+        .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(String::from))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Replace `{{name}}` placeholders, leaving unknown ones untouched
+pub fn render(text: &str, vars: &TemplateVars) -> String {
+    let placeholder_regex = regex::Regex::new(r"\{\{\s*(\w+)\s*\}\}").unwrap();
+    placeholder_regex
+        .replace_all(text, |caps: &regex::Captures| match &caps[1] {
+            "title" => vars.title.to_string(),
+            "id" => vars.id.to_string(),
+            "date" => vars.date.to_string(),
+            "author" => vars.author.to_string(),
+            _ => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+fn yaml_string(map: &serde_yaml::Mapping, key: &str) -> Option<String> {
+    map.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+fn yaml_list(map: &serde_yaml::Mapping, key: &str) -> Option<Vec<String>> {
+    map.get(key).and_then(|v| v.as_sequence()).map(|seq| {
+        seq.iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()
+    })
+}
+
+impl CardTemplate {
+    /// Fill options the caller left unset from the template defaults
+    pub fn apply_defaults(&self, options: &NewTaskOptions) -> NewTaskOptions {
+        let d = &self.defaults;
+        NewTaskOptions {
+            status: options.status.clone().or_else(|| yaml_string(d, "status")),
+            priority: options.priority.clone().or_else(|| yaml_string(d, "priority")),
+            assignee: options.assignee.clone().or_else(|| yaml_string(d, "assignee")),
+            tags: options.tags.clone().or_else(|| yaml_list(d, "tags")),
+            depends_on: options.depends_on.clone().or_else(|| yaml_list(d, "depends_on")),
+            parent: options.parent.clone().or_else(|| yaml_string(d, "parent")),
+            due: options.due.clone().or_else(|| yaml_string(d, "due")),
+            milestone: options.milestone.clone().or_else(|| yaml_string(d, "milestone")),
+            template: options.template.clone(),
+            recurrence_of: options.recurrence_of.clone(),
+            previous_instance: options.previous_instance.clone(),
+            external_id: options.external_id.clone(),
+            description: options.description.clone(),
+        }
+    }
+
+    /// Frontmatter keys without an option equivalent (copied verbatim)
+    pub fn extra_fields(&self, vars: &TemplateVars) -> serde_yaml::Mapping {
+        self.defaults
+            .iter()
+            .filter(|(k, _)| {
+                k.as_str()
+                    .is_some_and(|k| !RESERVED_KEYS.contains(&k) && !OPTION_KEYS.contains(&k))
+            })
+            .map(|(k, v)| {
+                let value = match v {
+                    serde_yaml::Value::String(s) => serde_yaml::Value::String(render(s, vars)),
+                    other => other.clone(),
+                };
+                (k.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Render the card body
+    pub fn render_body(&self, vars: &TemplateVars) -> String {
+        render(&self.body, vars)
+    }
+}
+
+/// Problems in a single template, empty if it is valid
+fn validate_template(name: &str, content: &str) -> Vec<String> {
+    let template = match parse_template(name, content) {
+        Ok(t) => t,
+        Err(e) => return vec![format!("{}.md: {}", name, e)],
+    };
+
+    let mut issues = Vec::new();
+    let d = &template.defaults;
+
+    for key in ["status", "priority", "assignee", "parent", "due", "milestone"] {
+        if d.get(key).is_some_and(|v| !v.is_string()) {
+            issues.push(format!("{}.md: '{}' must be a string", name, key));
+        }
+    }
+    for key in ["tags", "depends_on"] {
+        if d.get(key).is_some_and(|v| !v.is_sequence()) {
+            issues.push(format!("{}.md: '{}' must be a list", name, key));
+        }
+    }
+    for key in RESERVED_KEYS {
+        if d.contains_key(*key) {
+            issues.push(format!("{}.md: '{}' is set on create and will be ignored", name, key));
+        }
+    }
+    if let Some(due) = yaml_string(d, "due") {
+        if crate::task_filters::parse_due_date(&due).is_none() {
+            issues.push(format!("{}.md: Invalid due date '{}'", name, due));
+        }
+    }
+
+    let placeholder_regex = regex::Regex::new(r"\{\{\s*(\w+)\s*\}\}").unwrap();
+    for caps in placeholder_regex.captures_iter(content) {
+        if !PLACEHOLDERS.contains(&&caps[1]) {
+            issues.push(format!("{}.md: Unknown placeholder '{}'", name, &caps[0]));
+        }
+    }
+    if content.matches("{{").count() != content.matches("}}").count() {
+        issues.push(format!("{}.md: Unbalanced placeholder braces", name));
+    }
+
+    issues
+}
+
+/// Check all templates for malformed frontmatter and placeholders
+pub fn check_templates(workspace_root: &Path) -> Result<Vec<HealthCheck>> {
+    let names = list_templates(workspace_root)?;
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut issues = Vec::new();
+    for name in &names {
+        let content = fs::read_to_string(template_path(workspace_root, name))?;
+        issues.extend(validate_template(name, &content));
+    }
+
+    if issues.is_empty() {
+        Ok(vec![HealthCheck {
+            name: "Card Templates".to_string(),
+            status: CheckStatus::Pass,
+            message: format!("{} template(s) valid", names.len()),
+            details: None,
+            fixable: false,
+        }])
+    } else {
+        Ok(vec![HealthCheck {
+            name: "Card Templates".to_string(),
+            status: CheckStatus::Warn,
+            message: format!("Found {} template issue(s)", issues.len()),
+            details: Some(issues),
+            fixable: false,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUG: &str = "---\npriority: high\ntags: [bug]\ntype: bug\n---\n# {{title}}\n\nReported by {{author}} on {{date}} ({{id}})\n\n## Steps to Reproduce\n";
+
+    fn vars() -> TemplateVars<'static> {
+        TemplateVars {
+            title: "Crash on login",
+            id: "abc123",
+            date: "2026-01-02",
+            author: "sam",
+        }
+    }
+
+    #[test]
+    fn test_render_template_body() {
+        let template = parse_template("bug", BUG).unwrap();
+        let body = template.render_body(&vars());
+        assert!(body.starts_with("# Crash on login"));
+        assert!(body.contains("Reported by sam on 2026-01-02 (abc123)"));
+    }
+
+    #[test]
+    fn test_apply_defaults_keeps_explicit_options() {
+        let template = parse_template("bug", BUG).unwrap();
+        let options = NewTaskOptions {
+            priority: Some("low".to_string()),
+            ..Default::default()
+        };
+
+        let merged = template.apply_defaults(&options);
+        assert_eq!(merged.priority.as_deref(), Some("low"));
+        assert_eq!(merged.tags, Some(vec!["bug".to_string()]));
+
+        let extra = template.extra_fields(&vars());
+        assert_eq!(extra.len(), 1);
+        assert_eq!(extra.get("type").and_then(|v| v.as_str()), Some("bug"));
+    }
+
+    #[test]
+    fn test_validate_template_flags_problems() {
+        assert!(validate_template("bug", BUG).is_empty());
+
+        let issues = validate_template("bad", "---\ntags: bug\n---\n# {{titel}}\n");
+        assert!(issues.iter().any(|i| i.contains("'tags' must be a list")));
+        assert!(issues.iter().any(|i| i.contains("Unknown placeholder '{{titel}}'")));
+
+        let issues = validate_template("broken", "---\npriority: [unclosed\n---\n");
+        assert_eq!(issues.len(), 1);
+    }
+}
@@ -0,0 +1,349 @@
+//! Work session tracking for task cards
+//!
+//! Sessions are appended as JSON lines to `.cuedeck/time.jsonl`. Starting a
+//! session writes an open entry (no `end`); stopping it appends the same
+//! entry with `end` set. When reading, a later line with the same card,
+//! author and start replaces the earlier one, so the file stays append-only.
+
+use crate::activity::current_author;
+use crate::card_ids::resolve_task_id;
+use chrono::{DateTime, Utc};
+use cue_common::{CueError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Location of the time log, relative to the workspace root
+pub const TIME_LOG: &str = ".cuedeck/time.jsonl";
+
+/// One stretch of work on a card
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkSession {
+    pub task_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// RFC 3339 start time
+    pub start: String,
+    /// RFC 3339 end time; `None` while the session is running
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+}
+
+impl WorkSession {
+    /// Time worked, counting a running session up to `now`
+    ///
+    /// Only the part after `since` counts, if given.
+    pub fn duration(&self, now: DateTime<Utc>, since: Option<DateTime<Utc>>) -> chrono::Duration {
+        let Some(start) = parse_time(&self.start) else {
+            return chrono::Duration::zero();
+        };
+        let end = match self.end.as_deref() {
+            None => now,
+            Some(end) => match parse_time(end) {
+                Some(end) => end,
+                None => return chrono::Duration::zero(),
+            },
+        };
+        let start = since.map_or(start, |s| start.max(s));
+        (end - start).max(chrono::Duration::zero())
+    }
+
+    fn key(&self) -> (&str, Option<&str>, &str) {
+        (&self.task_id, self.author.as_deref(), &self.start)
+    }
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// All sessions, in the order they were started
+///
+/// Malformed lines are skipped so one bad entry cannot hide the rest.
+pub fn read_sessions(workspace_root: &Path) -> Result<Vec<WorkSession>> {
+    let path = workspace_root.join(TIME_LOG);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions: Vec<WorkSession> = Vec::new();
+    for line in fs::read_to_string(&path)?.lines().filter(|l| !l.trim().is_empty()) {
+        let Ok(session) = serde_json::from_str::<WorkSession>(line) else {
+            continue;
+        };
+        match sessions.iter_mut().find(|s| s.key() == session.key()) {
+            Some(existing) => *existing = session,
+            None => sessions.push(session),
+        }
+    }
+    Ok(sessions)
+}
+
+fn append(workspace_root: &Path, session: &WorkSession) -> Result<()> {
+    let path = workspace_root.join(TIME_LOG);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // One write per line keeps concurrent appends from interleaving
+    let mut line = serde_json::to_string(session)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn open_session(workspace_root: &Path, id: &str, author: Option<&str>) -> Result<Option<WorkSession>> {
+    Ok(read_sessions(workspace_root)?
+        .into_iter()
+        .find(|s| s.task_id == id && s.author.as_deref() == author && s.end.is_none()))
+}
+
+/// Start a work session on a card
+///
+/// `author` defaults to the session author (MCP client or `[author] name`).
+/// Fails if that author already has a session running on the card.
+pub fn start_session(workspace_root: &Path, id: &str, author: Option<&str>) -> Result<WorkSession> {
+    let id = resolve_task_id(workspace_root, id)?;
+    let author = author.map(String::from).or_else(|| current_author(workspace_root));
+
+    if let Some(running) = open_session(workspace_root, &id, author.as_deref())? {
+        return Err(CueError::ValidationError(format!(
+            "A session on {} is already running (started {})",
+            id, running.start
+        )));
+    }
+
+    let session = WorkSession {
+        task_id: id,
+        author,
+        start: Utc::now().to_rfc3339(),
+        end: None,
+    };
+    append(workspace_root, &session)?;
+    Ok(session)
+}
+
+/// Stop the running work session on a card
+pub fn stop_session(workspace_root: &Path, id: &str, author: Option<&str>) -> Result<WorkSession> {
+    let id = resolve_task_id(workspace_root, id)?;
+    let author = author.map(String::from).or_else(|| current_author(workspace_root));
+
+    let mut session = open_session(workspace_root, &id, author.as_deref())?.ok_or_else(|| {
+        CueError::ValidationError(format!("No session on {} is running", id))
+    })?;
+    session.end = Some(Utc::now().to_rfc3339());
+    append(workspace_root, &session)?;
+    Ok(session)
+}
+
+/// Record a finished session after the fact
+pub fn log_session(
+    workspace_root: &Path,
+    id: &str,
+    author: Option<&str>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<WorkSession> {
+    if end <= start {
+        return Err(CueError::ValidationError(
+            "Session end must be after its start".to_string(),
+        ));
+    }
+    if end > Utc::now() {
+        return Err(CueError::ValidationError(
+            "Session end must not be in the future".to_string(),
+        ));
+    }
+
+    let session = WorkSession {
+        task_id: resolve_task_id(workspace_root, id)?,
+        author: author.map(String::from).or_else(|| current_author(workspace_root)),
+        start: start.to_rfc3339(),
+        end: Some(end.to_rfc3339()),
+    };
+    append(workspace_root, &session)?;
+    Ok(session)
+}
+
+/// Total time logged per card, counting running sessions up to now
+pub fn time_by_task(workspace_root: &Path) -> Result<HashMap<String, chrono::Duration>> {
+    let now = Utc::now();
+    let mut totals: HashMap<String, chrono::Duration> = HashMap::new();
+    for session in read_sessions(workspace_root)? {
+        *totals.entry(session.task_id.clone()).or_insert_with(chrono::Duration::zero) +=
+            session.duration(now, None);
+    }
+    Ok(totals)
+}
+
+/// How `time_report` groups sessions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeGrouping {
+    /// The card's assignee
+    Assignee,
+    /// Each of the card's tags (a session counts once per tag)
+    Tag,
+}
+
+impl TimeGrouping {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "assignee" => Ok(Self::Assignee),
+            "tag" => Ok(Self::Tag),
+            other => Err(CueError::ValidationError(format!(
+                "Invalid grouping '{}'. Must be: assignee or tag",
+                other
+            ))),
+        }
+    }
+}
+
+/// Time logged for one assignee or tag
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeBucket {
+    pub key: String,
+    pub seconds: i64,
+    pub sessions: usize,
+    /// Cards with time in this bucket, sorted
+    pub cards: Vec<String>,
+}
+
+/// Time logged since `since`, grouped by assignee or tag, largest first
+pub fn time_report(
+    workspace_root: &Path,
+    since: Option<DateTime<Utc>>,
+    by: TimeGrouping,
+) -> Result<Vec<TimeBucket>> {
+    let now = Utc::now();
+    let mut buckets: BTreeMap<String, (chrono::Duration, usize, BTreeSet<String>)> = BTreeMap::new();
+    let mut keys_by_card: HashMap<String, Vec<String>> = HashMap::new();
+
+    for session in read_sessions(workspace_root)? {
+        let worked = session.duration(now, since);
+        if worked <= chrono::Duration::zero() {
+            continue;
+        }
+
+        let keys = keys_by_card
+            .entry(session.task_id.clone())
+            .or_insert_with(|| group_keys(workspace_root, &session.task_id, by));
+        for key in keys.iter() {
+            let bucket = buckets
+                .entry(key.clone())
+                .or_insert_with(|| (chrono::Duration::zero(), 0, BTreeSet::new()));
+            bucket.0 += worked;
+            bucket.1 += 1;
+            bucket.2.insert(session.task_id.clone());
+        }
+    }
+
+    let mut report: Vec<TimeBucket> = buckets
+        .into_iter()
+        .map(|(key, (worked, sessions, cards))| TimeBucket {
+            key,
+            seconds: worked.num_seconds(),
+            sessions,
+            cards: cards.into_iter().collect(),
+        })
+        .collect();
+    report.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.key.cmp(&b.key)));
+    Ok(report)
+}
+
+fn group_keys(workspace_root: &Path, id: &str, by: TimeGrouping) -> Vec<String> {
+    let meta = crate::parse_file(&crate::archive::card_path(workspace_root, id))
+        .ok()
+        .and_then(|doc| doc.frontmatter);
+
+    match by {
+        TimeGrouping::Assignee => vec![meta
+            .and_then(|m| m.assignee)
+            .filter(|a| !a.is_empty())
+            .unwrap_or_else(|| "(unassigned)".to_string())],
+        TimeGrouping::Tag => meta
+            .and_then(|m| m.tags)
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| vec!["(untagged)".to_string()]),
+    }
+}
+
+/// Format a duration as e.g. `2h 05m`, or `45m` under an hour
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn workspace() -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards
+            .child("base11.md")
+            .write_str("---\ntitle: Base\nassignee: dana\ntags: [api, auth]\n---\n")
+            .unwrap();
+        cards.child("other1.md").write_str("---\ntitle: Other\n---\n").unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_start_and_stop_session() {
+        let temp = workspace();
+
+        let started = start_session(temp.path(), "base11", Some("agent")).unwrap();
+        assert!(start_session(temp.path(), "base11", Some("agent")).is_err());
+        // Another author can work on the same card
+        start_session(temp.path(), "base11", Some("other")).unwrap();
+
+        let stopped = stop_session(temp.path(), "base11", Some("agent")).unwrap();
+        assert_eq!(stopped.start, started.start);
+        assert!(stopped.end.is_some());
+        assert!(stop_session(temp.path(), "base11", Some("agent")).is_err());
+
+        let sessions = read_sessions(temp.path()).unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions[0].end.is_some());
+        assert!(sessions[1].end.is_none());
+    }
+
+    #[test]
+    fn test_time_report_groups_and_clips() {
+        let temp = workspace();
+        let now = Utc::now();
+        let hours_ago = |h| now - chrono::Duration::hours(h);
+
+        log_session(temp.path(), "base11", Some("dana"), hours_ago(5), hours_ago(3)).unwrap();
+        log_session(temp.path(), "other1", Some("lee"), hours_ago(2), hours_ago(1)).unwrap();
+
+        let by_assignee = time_report(temp.path(), None, TimeGrouping::Assignee).unwrap();
+        assert_eq!(by_assignee[0].key, "dana");
+        assert_eq!(by_assignee[0].seconds, 2 * 3600);
+        assert_eq!(by_assignee[1].key, "(unassigned)");
+
+        // Only the last four hours count: one hour of the first session
+        let recent = time_report(temp.path(), Some(hours_ago(4)), TimeGrouping::Tag).unwrap();
+        let api = recent.iter().find(|b| b.key == "api").unwrap();
+        assert_eq!(api.seconds, 3600);
+        assert_eq!(api.cards, vec!["base11".to_string()]);
+
+        assert!(log_session(temp.path(), "base11", None, hours_ago(1), hours_ago(2)).is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(chrono::Duration::minutes(45)), "45m");
+        assert_eq!(format_duration(chrono::Duration::minutes(125)), "2h 05m");
+    }
+}
@@ -0,0 +1,226 @@
+//! Card deletion
+//!
+//! Deleted cards are moved to `.cuedeck/trash/` rather than removed, so a
+//! mistaken delete can be undone by moving the file back. Cards that still
+//! reference the deleted card (via `depends_on` or `parent`) block the delete
+//! unless the caller asks to cascade or force it.
+
+use crate::activity::record_event_lossy;
+use crate::bulk::write_all_or_nothing;
+use crate::card_ids::resolve_task_id;
+use crate::task_graph::TaskGraph;
+use crate::task_hierarchy::get_children;
+use crate::tasks::prepare_update;
+use cue_common::{CueError, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How `delete_task` treats cards that reference the deleted card
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DeleteOptions {
+    /// Remove the card from other cards' `depends_on` and `parent`
+    pub cascade: bool,
+    /// Delete even if other cards reference it, leaving the references
+    pub force: bool,
+}
+
+/// Outcome of `delete_task`
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteReport {
+    pub id: String,
+    /// Workspace-relative path the card was moved to
+    pub trash_path: PathBuf,
+    /// Cards listing the deleted card in `depends_on`
+    pub dependents: Vec<String>,
+    /// Cards whose `parent` is the deleted card
+    pub children: Vec<String>,
+    /// Whether the references above were removed
+    pub cascaded: bool,
+}
+
+/// Directory deleted cards are moved to
+pub fn trash_dir(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".cuedeck/trash")
+}
+
+/// IDs of cards in the trash, sorted and deduplicated
+///
+/// Trash files are named `<id>.md`, or `<id>.<timestamp>.md` when a card
+/// with the same ID was deleted before.
+pub fn list_trashed_ids(workspace_root: &Path) -> Result<Vec<String>> {
+    let dir = trash_dir(workspace_root);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut ids: Vec<String> = fs::read_dir(&dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "md"))
+        .filter_map(|p| {
+            let name = p.file_name()?.to_str()?;
+            name.split('.').next().map(String::from)
+        })
+        .collect();
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// Move a card to the trash
+///
+/// Fails with a validation error listing the referencing cards unless
+/// `options.cascade` or `options.force` is set.
+pub fn delete_task(workspace_root: &Path, id: &str, options: &DeleteOptions) -> Result<DeleteReport> {
+    let id = resolve_task_id(workspace_root, id)?;
+    let card_path = crate::archive::card_path(workspace_root, &id);
+
+    let dependents = TaskGraph::from_workspace(workspace_root)?.get_dependents(&id);
+    let mut children: Vec<String> = get_children(workspace_root, &id)?
+        .iter()
+        .filter_map(|d| d.path.file_stem().and_then(|s| s.to_str()).map(String::from))
+        .collect();
+    children.sort();
+
+    let referenced = !dependents.is_empty() || !children.is_empty();
+    if referenced && !options.cascade && !options.force {
+        let mut refs = Vec::new();
+        if !dependents.is_empty() {
+            refs.push(format!("depended on by {}", dependents.join(", ")));
+        }
+        if !children.is_empty() {
+            refs.push(format!("parent of {}", children.join(", ")));
+        }
+        return Err(CueError::ValidationError(format!(
+            "Card {} is still referenced: {}",
+            id,
+            refs.join("; ")
+        )));
+    }
+
+    // Compute reference removals before moving anything
+    let cascade = options.cascade && referenced;
+    let mut pending = Vec::new();
+    if cascade {
+        for dependent in &dependents {
+            let mut updates = serde_json::Map::new();
+            updates.insert("depends_on".to_string(), serde_json::json!({ "remove": [id] }));
+            pending.push(prepare_update(workspace_root, dependent, &updates)?);
+        }
+        for child in &children {
+            let mut updates = serde_json::Map::new();
+            updates.insert("parent".to_string(), serde_json::Value::Null);
+            pending.push(prepare_update(workspace_root, child, &updates)?);
+        }
+    }
+
+    let trash = trash_dir(workspace_root);
+    fs::create_dir_all(&trash)?;
+    let mut trash_path = trash.join(format!("{}.md", id));
+    if trash_path.exists() {
+        let stamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
+        trash_path = trash.join(format!("{}.{}.md", id, stamp));
+    }
+    fs::rename(&card_path, &trash_path)?;
+
+    if let Err(e) = write_all_or_nothing(&pending, crate::write_atomic) {
+        // Put the card back so the workspace is unchanged
+        if let Err(restore) = fs::rename(&trash_path, &card_path) {
+            tracing::error!("Failed to restore {} from trash: {}", id, restore);
+        }
+        return Err(e);
+    }
+
+    record_event_lossy(workspace_root, &id, "deleted", Vec::new());
+    for update in &pending {
+        record_event_lossy(workspace_root, &update.id, update.action(), update.changes.clone());
+    }
+
+    Ok(DeleteReport {
+        trash_path: trash_path
+            .strip_prefix(workspace_root)
+            .unwrap_or(&trash_path)
+            .to_path_buf(),
+        id,
+        dependents,
+        children,
+        cascaded: cascade,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    fn workspace() -> assert_fs::TempDir {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        cards.create_dir_all().unwrap();
+        cards.child("base11.md").write_str("---\ntitle: Base\n---\n").unwrap();
+        cards.child("other1.md").write_str("---\ntitle: Other\n---\n").unwrap();
+        cards
+            .child("next11.md")
+            .write_str("---\ntitle: Next\ndepends_on: [base11, other1]\n---\n")
+            .unwrap();
+        cards
+            .child("child1.md")
+            .write_str("---\ntitle: Child\nparent: base11\n---\n")
+            .unwrap();
+        temp
+    }
+
+    fn meta(temp: &assert_fs::TempDir, id: &str) -> cue_common::CardMetadata {
+        crate::parse_file(&temp.path().join(format!(".cuedeck/cards/{}.md", id)))
+            .unwrap()
+            .frontmatter
+            .unwrap()
+    }
+
+    #[test]
+    fn test_delete_refuses_referenced_card() {
+        let temp = workspace();
+        let err = delete_task(temp.path(), "base11", &DeleteOptions::default()).unwrap_err();
+
+        assert!(err.to_string().contains("next11"));
+        assert!(err.to_string().contains("child1"));
+        assert!(temp.path().join(".cuedeck/cards/base11.md").exists());
+    }
+
+    #[test]
+    fn test_delete_cascade_removes_references() {
+        let temp = workspace();
+        let options = DeleteOptions {
+            cascade: true,
+            ..Default::default()
+        };
+        let report = delete_task(temp.path(), "base11", &options).unwrap();
+
+        assert!(report.cascaded);
+        assert_eq!(report.trash_path, PathBuf::from(".cuedeck/trash/base11.md"));
+        assert!(temp.path().join(".cuedeck/trash/base11.md").exists());
+        assert!(!temp.path().join(".cuedeck/cards/base11.md").exists());
+        assert_eq!(meta(&temp, "next11").depends_on, Some(vec!["other1".to_string()]));
+        assert_eq!(meta(&temp, "child1").parent, None);
+    }
+
+    #[test]
+    fn test_delete_force_keeps_references_and_trash_names_are_unique() {
+        let temp = workspace();
+        let options = DeleteOptions {
+            force: true,
+            ..Default::default()
+        };
+        delete_task(temp.path(), "base11", &options).unwrap();
+        assert_eq!(meta(&temp, "child1").parent.as_deref(), Some("base11"));
+
+        // A second card with the same ID does not overwrite the first
+        temp.child(".cuedeck/cards/base11.md")
+            .write_str("---\ntitle: Again\n---\n")
+            .unwrap();
+        let report = delete_task(temp.path(), "base11", &options).unwrap();
+        assert_ne!(report.trash_path, PathBuf::from(".cuedeck/trash/base11.md"));
+        assert_eq!(list_trashed_ids(temp.path()).unwrap(), vec!["base11".to_string()]);
+    }
+}
@@ -0,0 +1,197 @@
+//! Open work per assignee, checked against `[capacity]` limits
+//!
+//! A card is open until it reaches a terminal status and blocked while any of
+//! its dependencies is open. Limits are advisory: `capacity_warnings` is
+//! called after a card is created or reassigned, and never stops the write.
+
+use crate::task_filters::TaskFilters;
+use crate::task_graph::TaskGraph;
+use crate::tasks::list_tasks_filtered;
+use cue_common::{CardMetadata, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Group for open cards without an assignee
+pub const UNASSIGNED: &str = "(unassigned)";
+
+/// The longest-running in-progress card of an assignee
+#[derive(Debug, Clone, Serialize)]
+pub struct OldestCard {
+    pub id: String,
+    pub title: String,
+    /// Created timestamp (RFC 3339)
+    pub created: String,
+}
+
+/// Open work held by one assignee
+#[derive(Debug, Clone, Serialize)]
+pub struct AssigneeLoad {
+    pub assignee: String,
+    pub open: usize,
+    pub by_status: BTreeMap<String, usize>,
+    pub by_priority: BTreeMap<String, usize>,
+    /// Open cards waiting on an open dependency
+    pub blocked: usize,
+    /// Sum of `estimate` over open cards
+    pub estimate: f64,
+    /// Open cards without an estimate
+    pub unestimated: usize,
+    pub oldest_active: Option<OldestCard>,
+    pub max_open: Option<usize>,
+    pub max_estimate: Option<f64>,
+    pub over_capacity: bool,
+}
+
+/// Open work per assignee, busiest first
+pub fn workload(workspace_root: &Path) -> Result<Vec<AssigneeLoad>> {
+    let config = cue_config::Config::load(workspace_root)?;
+    let workflow = &config.workflow;
+
+    let cards: HashMap<String, CardMetadata> = list_tasks_filtered(workspace_root, &TaskFilters::default())?
+        .into_iter()
+        .filter_map(|doc| Some((doc.path.file_stem()?.to_str()?.to_string(), doc.frontmatter?)))
+        .filter(|(_, meta)| !workflow.is_terminal(&meta.status))
+        .collect();
+    let graph = TaskGraph::from_workspace(workspace_root)?;
+
+    let mut loads: BTreeMap<String, AssigneeLoad> = BTreeMap::new();
+    for (id, meta) in &cards {
+        let assignee = meta
+            .assignee
+            .clone()
+            .filter(|a| !a.is_empty())
+            .unwrap_or_else(|| UNASSIGNED.to_string());
+        let load = loads.entry(assignee.clone()).or_insert_with(|| {
+            let limit = if assignee == UNASSIGNED {
+                Default::default()
+            } else {
+                config.capacity.limit_for(&assignee)
+            };
+            AssigneeLoad {
+                assignee,
+                open: 0,
+                by_status: BTreeMap::new(),
+                by_priority: BTreeMap::new(),
+                blocked: 0,
+                estimate: 0.0,
+                unestimated: 0,
+                oldest_active: None,
+                max_open: limit.max_open,
+                max_estimate: limit.max_estimate,
+                over_capacity: false,
+            }
+        });
+
+        load.open += 1;
+        *load.by_status.entry(meta.status.clone()).or_default() += 1;
+        *load.by_priority.entry(meta.priority.clone()).or_default() += 1;
+        // Open cards are exactly the ones in `cards`
+        if graph.get_dependencies(id).iter().any(|dep| cards.contains_key(dep)) {
+            load.blocked += 1;
+        }
+        match meta.estimate {
+            Some(estimate) => load.estimate += estimate,
+            None => load.unestimated += 1,
+        }
+
+        if let (true, Some(created)) = (workflow.is_in_progress(&meta.status), &meta.created) {
+            let older = load.oldest_active.as_ref().is_none_or(|o| {
+                (created.as_str(), id.as_str()) < (o.created.as_str(), o.id.as_str())
+            });
+            if older {
+                load.oldest_active = Some(OldestCard {
+                    id: id.clone(),
+                    title: meta.title.clone(),
+                    created: created.clone(),
+                });
+            }
+        }
+    }
+
+    let mut report: Vec<AssigneeLoad> = loads
+        .into_values()
+        .map(|mut load| {
+            load.over_capacity = !over_limits(&load).is_empty();
+            load
+        })
+        .collect();
+    report.sort_by(|a, b| b.open.cmp(&a.open).then_with(|| a.assignee.cmp(&b.assignee)));
+    Ok(report)
+}
+
+fn over_limits(load: &AssigneeLoad) -> Vec<String> {
+    let mut over = Vec::new();
+    if let Some(max) = load.max_open.filter(|max| load.open > *max) {
+        over.push(format!("{} open card(s), over the limit of {}", load.open, max));
+    }
+    if let Some(max) = load.max_estimate.filter(|max| load.estimate > *max) {
+        over.push(format!("an estimate of {}, over the limit of {}", load.estimate, max));
+    }
+    over
+}
+
+/// Warnings if `assignee` is now over their `[capacity]` limits
+pub fn capacity_warnings(workspace_root: &Path, assignee: &str) -> Result<Vec<String>> {
+    let Some(load) = workload(workspace_root)?
+        .into_iter()
+        .find(|l| l.assignee == assignee)
+    else {
+        return Ok(Vec::new());
+    };
+
+    Ok(over_limits(&load)
+        .into_iter()
+        .map(|over| format!("{} now has {}", assignee, over))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn test_workload_and_capacity() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child(".cuedeck/config.toml")
+            .write_str("[capacity]\nmax_open = 5\n\n[capacity.assignees.dana]\nmax_open = 2\n")
+            .unwrap();
+        let cards = temp.child(".cuedeck/cards");
+        let card = |id: &str, body: &str| {
+            cards.child(format!("{}.md", id)).write_str(&format!("---\n{}\n---\n", body)).unwrap();
+        };
+        card("aaa111", "title: A\nstatus: active\nassignee: dana\npriority: high\nestimate: 3\ncreated: 2024-06-02T09:00:00Z");
+        card("bbb222", "title: B\nstatus: active\nassignee: dana\nestimate: 1.5\ncreated: 2024-06-01T09:00:00Z");
+        card("ccc333", "title: C\nstatus: todo\nassignee: dana\ndepends_on: [aaa111]");
+        card("ddd444", "title: D\nstatus: done\nassignee: dana\nestimate: 8");
+        card("eee555", "title: E\nstatus: todo\nassignee: sam\ndepends_on: [ddd444]");
+        card("fff666", "title: F\nstatus: todo");
+
+        let report = workload(temp.path()).unwrap();
+        let names: Vec<&str> = report.iter().map(|l| l.assignee.as_str()).collect();
+        assert_eq!(names, vec!["dana", UNASSIGNED, "sam"]);
+
+        let dana = &report[0];
+        assert_eq!(dana.open, 3);
+        assert_eq!(dana.by_status["active"], 2);
+        assert_eq!(dana.by_priority["high"], 1);
+        assert_eq!(dana.blocked, 1);
+        assert_eq!((dana.estimate, dana.unestimated), (4.5, 1));
+        assert_eq!(dana.oldest_active.as_ref().unwrap().id, "bbb222");
+        assert_eq!(dana.max_open, Some(2));
+        assert!(dana.over_capacity);
+
+        // A done dependency does not block
+        assert_eq!(report[2].blocked, 0);
+        assert_eq!(report[2].max_open, Some(5));
+        assert_eq!(report[1].max_open, None);
+
+        assert_eq!(
+            capacity_warnings(temp.path(), "dana").unwrap(),
+            vec!["dana now has 3 open card(s), over the limit of 2"]
+        );
+        assert!(capacity_warnings(temp.path(), "sam").unwrap().is_empty());
+        assert!(capacity_warnings(temp.path(), "nobody").unwrap().is_empty());
+    }
+}
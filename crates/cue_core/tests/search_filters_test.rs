@@ -96,3 +96,43 @@ Backend API"#)
     let results = search_workspace_with_mode(root, "doc", SearchMode::Keyword, Some(filters)).unwrap();
     assert_eq!(results.len(), 1, "Should handle case insensitivity");
 }
+
+#[test]
+fn test_search_filters_query_expression() {
+    use cue_core::query::Query;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let root = temp.path();
+
+    temp.child("auth.md")
+        .write_str("---\ntitle: Auth\ntags: [auth]\nassignee: \"@alice\"\n---\n# Auth\nLogin flow")
+        .unwrap();
+    temp.child("api.md")
+        .write_str("---\ntitle: API\ntags: [api]\nassignee: \"@bob\"\n---\n# API\nLogin flow")
+        .unwrap();
+    temp.child("ui.md")
+        .write_str("---\ntitle: UI\ntags: [ui]\n---\n# UI\nButtons")
+        .unwrap();
+
+    // Text is ranked, field terms filter
+    let (text, rest) = Query::parse("login (tag:auth OR tag:api) -assignee:@bob")
+        .unwrap()
+        .split_text();
+    assert_eq!(text, "login");
+
+    let filters = SearchFilters {
+        query: Some(rest),
+        ..Default::default()
+    };
+    let results = search_workspace_with_mode(root, &text, SearchMode::Keyword, Some(filters)).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].frontmatter.as_ref().unwrap().title, "Auth");
+
+    // Filter-only query lists every match, even in hybrid mode
+    let filters = SearchFilters {
+        query: Some(Query::parse("tag:ui OR tag:api").unwrap()),
+        ..Default::default()
+    };
+    let results = search_workspace_with_mode(root, "", SearchMode::Hybrid, Some(filters)).unwrap();
+    assert_eq!(results.len(), 2);
+}
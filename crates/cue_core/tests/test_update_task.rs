@@ -0,0 +1,283 @@
+use assert_fs::prelude::*;
+use cue_common::CueError;
+use cue_core::tasks::{update_task, update_task_checked};
+use serde_json::json;
+
+fn workspace() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards = temp.child(".cuedeck/cards");
+    cards.child("aaa111.md").write_str("---\ntitle: A\ntags: [auth]\ndepends_on: [bbb222]\n---\n").unwrap();
+    cards.child("bbb222.md").write_str("---\ntitle: B\ndepends_on: [ccc333]\n---\n").unwrap();
+    cards.child("ccc333.md").write_str("---\ntitle: C\n---\n").unwrap();
+    temp
+}
+
+fn updates(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn test_update_task_list_values_and_operations() {
+    let temp = workspace();
+
+    let doc = update_task(temp.path(), "aaa111", updates(json!({ "tags": ["api", "backend"] }))).unwrap();
+    assert_eq!(doc.frontmatter.unwrap().tags, Some(vec!["api".to_string(), "backend".to_string()]));
+
+    let doc = update_task(
+        temp.path(),
+        "aaa111",
+        updates(json!({ "tags": { "add": ["urgent", "API"], "remove": ["backend"] } })),
+    )
+    .unwrap();
+    assert_eq!(doc.frontmatter.unwrap().tags, Some(vec!["api".to_string(), "urgent".to_string()]));
+
+    let doc = update_task(temp.path(), "aaa111", updates(json!({ "depends_on": { "add": ["ccc333"] } }))).unwrap();
+    assert_eq!(
+        doc.frontmatter.unwrap().depends_on,
+        Some(vec!["bbb222".to_string(), "ccc333".to_string()])
+    );
+
+    let doc = update_task(temp.path(), "aaa111", updates(json!({ "tags": null }))).unwrap();
+    assert_eq!(doc.frontmatter.unwrap().tags, None);
+
+    let result = update_task(temp.path(), "aaa111", updates(json!({ "tags": { "replace": ["x"] } })));
+    assert!(matches!(result, Err(CueError::ValidationError(_))));
+}
+
+#[test]
+fn test_update_task_rejects_bad_dependencies() {
+    let temp = workspace();
+    let before = std::fs::read_to_string(temp.path().join(".cuedeck/cards/ccc333.md")).unwrap();
+
+    // c -> a closes a -> b -> c -> a
+    let err = update_task(temp.path(), "ccc333", updates(json!({ "depends_on": ["aaa111"] }))).unwrap_err();
+    match err {
+        CueError::CircularDependency(chain) => assert_eq!(chain, "ccc333 -> aaa111 -> bbb222 -> ccc333"),
+        other => panic!("expected cycle, got {:?}", other),
+    }
+
+    let err = update_task(temp.path(), "ccc333", updates(json!({ "depends_on": { "add": ["zzz999"] } }))).unwrap_err();
+    assert!(matches!(err, CueError::DependencyNotFound(id) if id == "zzz999"));
+
+    // Nothing is written when validation fails
+    let after = std::fs::read_to_string(temp.path().join(".cuedeck/cards/ccc333.md")).unwrap();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn test_update_task_preserves_formatting() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let original = "---\n# Owner notes\ntitle: Login\nassignee: \"@alice\" # on call\nstatus: todo\ntags: [auth]\n---\n# Login\n\nBody text\n";
+    temp.child(".cuedeck/cards/aaa111.md").write_str(original).unwrap();
+
+    update_task(temp.path(), "aaa111", updates(json!({ "status": "done", "assignee": "@bob" }))).unwrap();
+
+    let content = std::fs::read_to_string(temp.path().join(".cuedeck/cards/aaa111.md")).unwrap();
+    let (head, body) = content.split_once("\n---\n").unwrap();
+    assert_eq!(body, "# Login\n\nBody text\n");
+
+    let lines: Vec<&str> = head.lines().collect();
+    assert_eq!(lines[..6], ["---", "# Owner notes", "title: Login", "assignee: \"@bob\" # on call", "status: done", "tags: [auth]"]);
+    assert!(lines[6].starts_with("updated: "));
+}
+
+#[test]
+fn test_update_task_enforces_workflow() {
+    let temp = workspace();
+    temp.child(".cuedeck/config.toml")
+        .write_str(
+            "[workflow]\nstatuses = [\"todo\", \"active\", \"review\", \"done\"]\nterminal = [\"done\"]\n\n\
+             [workflow.transitions]\ntodo = [\"active\"]\nactive = [\"review\", \"todo\"]\ndone = []\n",
+        )
+        .unwrap();
+
+    let err = update_task(temp.path(), "ccc333", updates(json!({ "status": "shipped" }))).unwrap_err();
+    assert!(err.to_string().contains("Unknown status 'shipped'"));
+
+    // Cards without a status start as todo, which may only move to active
+    let err = update_task(temp.path(), "ccc333", updates(json!({ "status": "done" }))).unwrap_err();
+    assert!(err.to_string().contains("Allowed: active"));
+
+    update_task(temp.path(), "ccc333", updates(json!({ "status": "active" }))).unwrap();
+    update_task(temp.path(), "ccc333", updates(json!({ "status": "review" }))).unwrap();
+
+    // review has no transitions entry, so anything goes
+    let doc = update_task(temp.path(), "ccc333", updates(json!({ "status": "done" }))).unwrap();
+    assert_eq!(doc.frontmatter.unwrap().status, "done");
+
+    let err = update_task(temp.path(), "ccc333", updates(json!({ "status": "todo" }))).unwrap_err();
+    assert!(err.to_string().contains("'done' has no next statuses"));
+}
+
+#[test]
+fn test_concurrent_updates_are_serialized() {
+    let temp = workspace();
+    let root = temp.path().to_path_buf();
+    let read = cue_core::parse_file(&root.join(".cuedeck/cards/ccc333.md")).unwrap();
+
+    // Two writers holding the same hash: exactly one may win
+    let results: Vec<_> = ["@dana", "@lee"]
+        .into_iter()
+        .map(|assignee| {
+            let (root, hash) = (root.clone(), read.hash.clone());
+            std::thread::spawn(move || {
+                update_task_checked(&root, "ccc333", updates(json!({ "assignee": assignee })), Some(&hash))
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+    assert!(results.iter().any(|r| matches!(r, Err(CueError::Conflict { .. }))));
+
+    // Unchecked read-modify-write updates do not lose each other's changes
+    let handles: Vec<_> = (0..2)
+        .map(|t| {
+            let root = root.clone();
+            std::thread::spawn(move || {
+                for i in 0..10 {
+                    let tag = format!("t{}-{}", t, i);
+                    update_task(&root, "ccc333", updates(json!({ "tags": { "add": [tag] } }))).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let meta = cue_core::parse_file(&root.join(".cuedeck/cards/ccc333.md")).unwrap().frontmatter.unwrap();
+    assert_eq!(meta.tags.unwrap().len(), 20);
+    assert!(!root.join(".cuedeck/cards/.ccc333.md.lock").exists());
+}
+
+#[test]
+fn test_invalid_priority_default_falls_back_to_default_scale() {
+    let temp = workspace();
+    temp.child(".cuedeck/config.toml")
+        .write_str("[priorities]\nlevels = [\"P0\", \"P1\"]\n")
+        .unwrap();
+
+    // The built-in default, medium, is not on this scale: commands keep
+    // working on the default scale and doctor reports the error
+    update_task(temp.path(), "ccc333", updates(json!({ "priority": "high" }))).unwrap();
+    let err = cue_config::Config::check_file(temp.path()).unwrap_err();
+    assert!(err.to_string().contains("Invalid [priorities] default 'medium'"), "got {}", err);
+
+    let report = cue_core::doctor::run_diagnostics(temp.path()).unwrap();
+    let check = report.checks.iter().find(|c| c.name == "Config File").unwrap();
+    assert_eq!(check.status, cue_core::doctor::CheckStatus::Fail);
+    assert!(check.details.as_ref().unwrap()[0].contains("Invalid [priorities] default"));
+}
+
+#[test]
+fn test_terminal_status_is_final_unless_listed() {
+    let temp = workspace();
+    let config = temp.child(".cuedeck/config.toml");
+    config
+        .write_str(
+            "[workflow]\nstatuses = [\"todo\", \"active\", \"shipped\", \"dropped\"]\nterminal = [\"shipped\", \"dropped\"]\n\n\
+             [workflow.transitions]\ntodo = [\"active\", \"dropped\"]\ndropped = [\"todo\"]\n",
+        )
+        .unwrap();
+
+    // active has no entry, so it may move anywhere
+    update_task(temp.path(), "ccc333", updates(json!({ "status": "active" }))).unwrap();
+    update_task(temp.path(), "ccc333", updates(json!({ "status": "shipped" }))).unwrap();
+
+    // shipped is terminal and not listed, so it is final
+    let err = update_task(temp.path(), "ccc333", updates(json!({ "status": "active" }))).unwrap_err();
+    assert!(err.to_string().contains("'shipped' is terminal"), "got {}", err);
+
+    // A listed terminal status follows its entry
+    let workflow = cue_config::Config::load(temp.path()).unwrap().workflow;
+    assert_eq!(workflow.next_statuses("dropped"), vec!["todo"]);
+    assert!(workflow.next_statuses("shipped").is_empty());
+
+    // Without any transitions, terminal statuses can be reopened
+    config
+        .write_str("[workflow]\nstatuses = [\"todo\", \"shipped\"]\nterminal = [\"shipped\"]\n")
+        .unwrap();
+    update_task(temp.path(), "ccc333", updates(json!({ "status": "todo" }))).unwrap();
+}
+
+#[test]
+fn test_update_task_checked_detects_conflicts() {
+    let temp = workspace();
+    let read = cue_core::parse_file(&temp.path().join(".cuedeck/cards/ccc333.md")).unwrap();
+
+    // Someone else writes first
+    update_task(temp.path(), "ccc333", updates(json!({ "assignee": "@dana" }))).unwrap();
+
+    let err = update_task_checked(
+        temp.path(),
+        "ccc333",
+        updates(json!({ "assignee": "@lee" })),
+        Some(&read.hash),
+    )
+    .unwrap_err();
+    let current = cue_core::parse_file(&temp.path().join(".cuedeck/cards/ccc333.md")).unwrap();
+    match err {
+        CueError::Conflict { current_hash, frontmatter, .. } => {
+            assert_eq!(current_hash, current.hash);
+            assert_eq!(frontmatter.unwrap().assignee.as_deref(), Some("@dana"));
+        }
+        other => panic!("expected conflict, got {:?}", other),
+    }
+
+    // Retrying with the fresh hash succeeds, and leaves no temp files behind
+    let doc = update_task_checked(
+        temp.path(),
+        "ccc333",
+        updates(json!({ "assignee": "@lee" })),
+        Some(&current.hash),
+    )
+    .unwrap();
+    assert_eq!(doc.frontmatter.unwrap().assignee.as_deref(), Some("@lee"));
+    let entries = std::fs::read_dir(temp.path().join(".cuedeck/cards")).unwrap().count();
+    assert_eq!(entries, 3);
+}
+
+#[test]
+fn test_update_task_coerces_estimate() {
+    let temp = workspace();
+
+    let doc = update_task(temp.path(), "ccc333", updates(json!({ "estimate": "2.5" }))).unwrap();
+    assert_eq!(doc.frontmatter.unwrap().estimate, Some(2.5));
+
+    let doc = update_task(temp.path(), "ccc333", updates(json!({ "estimate": 3 }))).unwrap();
+    assert_eq!(doc.frontmatter.unwrap().estimate, Some(3.0));
+
+    let err = update_task(temp.path(), "ccc333", updates(json!({ "estimate": "lots" }))).unwrap_err();
+    assert!(matches!(err, CueError::ValidationError(_)));
+
+    let doc = update_task(temp.path(), "ccc333", updates(json!({ "estimate": null }))).unwrap();
+    assert_eq!(doc.frontmatter.unwrap().estimate, None);
+}
+
+#[test]
+fn test_concurrent_atomic_writes_use_separate_temp_files() {
+    let temp = workspace();
+    let path = temp.path().join(".cuedeck/cards/ccc333.md");
+
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            let path = path.clone();
+            std::thread::spawn(move || {
+                let content = format!("writer {}\n", t).repeat(1000);
+                for _ in 0..20 {
+                    cue_core::write_atomic(&path, &content).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // The file holds one writer's content in full, never a mix
+    let content = std::fs::read_to_string(&path).unwrap();
+    let first = content.lines().next().unwrap();
+    assert!(content.lines().all(|line| line == first));
+    assert_eq!(content.lines().count(), 1000);
+}
@@ -46,12 +46,13 @@ impl JsonRpcError {
     pub fn from_cue_error(err: &CueError) -> Self {
         let code = match err {
             CueError::FileNotFound { .. } => 1001,
-            CueError::CycleDetected => 1002,
+            CueError::CycleDetected | CueError::CircularDependency(_) => 1002,
             CueError::TokenLimit { .. } => 1003,
             CueError::StaleCache => 1006,
             CueError::Locked { .. } => 1007,
+            CueError::Conflict { .. } => 1011,
             CueError::RateLimit { .. } => 429,
-            CueError::ValidationError(_) => -32602, // Invalid params
+            CueError::ValidationError(_) | CueError::DependencyNotFound(_) => -32602, // Invalid params
             CueError::ParseError(_) => -32700,      // Parse error
             _ => -32603,                            // Internal error
         };
@@ -73,6 +74,25 @@ impl JsonRpcError {
             CueError::FileNotFound { path } => Some(serde_json::json!({
                 "path": path
             })),
+            CueError::CircularDependency(chain) => Some(serde_json::json!({
+                "cycle": chain.split(" -> ").collect::<Vec<_>>()
+            })),
+            CueError::DependencyNotFound(id) => Some(serde_json::json!({
+                "dependency": id
+            })),
+            CueError::Locked { holder, until } => Some(serde_json::json!({
+                "holder": holder,
+                "until": until
+            })),
+            CueError::Conflict {
+                path,
+                current_hash,
+                frontmatter,
+            } => Some(serde_json::json!({
+                "path": path,
+                "current_hash": current_hash,
+                "frontmatter": frontmatter
+            })),
             _ => None,
         };
 
@@ -102,6 +122,20 @@ impl RateLimiter {
             "read_doc" => (30, 60),
             "list_tasks" => (20, 60),
             "update_task" => (10, 60),
+            "bulk_update_tasks" => (10, 60),
+            "move_document" => (10, 60),
+            "delete_task" => (10, 60),
+            "claim_task" => (10, 60),
+            "release_task" => (10, 60),
+            "start_work_session" => (10, 60),
+            "stop_work_session" => (10, 60),
+            "log_work_session" => (10, 60),
+            "get_time_report" => (10, 60),
+            "get_task_commits" => (10, 60),
+            "scan_commits" => (10, 60),
+            "get_velocity_report" => (10, 60),
+            "get_digest" => (10, 60),
+            "get_workload" => (10, 60),
             _ => return Ok(()),
         };
 
@@ -189,7 +223,18 @@ pub async fn handle_request(request: JsonRpcRequest) -> Option<JsonRpcResponse>
 }
 
 /// Initialize handler - MCP protocol handshake
-async fn handle_initialize(_params: Option<Value>) -> Result<Value> {
+async fn handle_initialize(params: Option<Value>) -> Result<Value> {
+    // Attribute card changes made during this session to the client
+    let client_name = params
+        .as_ref()
+        .and_then(|p| p.get("clientInfo"))
+        .and_then(|c| c.get("name"))
+        .and_then(|n| n.as_str())
+        .map(String::from);
+    if client_name.is_some() {
+        cue_core::activity::set_session_author(client_name);
+    }
+
     Ok(serde_json::json!({
         "protocolVersion": "2024-11-05",
         "capabilities": {
@@ -223,6 +268,21 @@ async fn handle_tools_call(params: Option<Value>) -> Result<Value> {
         "validate_task_graph" => handle_validate_task_graph(args).await?,
         "query_graph" => handle_query_graph(args).await?,
         "update_task" => handle_update_task(args).await?,
+        "get_task_history" => handle_get_task_history(args).await?,
+        "bulk_update_tasks" => handle_bulk_update_tasks(args).await?,
+        "move_document" => handle_move_document(args).await?,
+        "delete_task" => handle_delete_task(args).await?,
+        "claim_task" => handle_claim_task(args).await?,
+        "release_task" => handle_release_task(args).await?,
+        "start_work_session" => handle_start_work_session(args).await?,
+        "stop_work_session" => handle_stop_work_session(args).await?,
+        "log_work_session" => handle_log_work_session(args).await?,
+        "get_time_report" => handle_get_time_report(args).await?,
+        "get_task_commits" => handle_get_task_commits(args).await?,
+        "scan_commits" => handle_scan_commits().await?,
+        "get_velocity_report" => handle_get_velocity_report(args).await?,
+        "get_digest" => handle_get_digest(args).await?,
+        "get_workload" => handle_get_workload().await?,
         _ => {
             return Err(CueError::ValidationError(format!(
                 "Unknown tool: {}",
@@ -243,6 +303,14 @@ async fn handle_tools_call(params: Option<Value>) -> Result<Value> {
 
 /// Tools list handler - return available MCP tools
 async fn handle_tools_list() -> Result<Value> {
+    // Priority enums follow the workspace's [priorities] config
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+    let priorities = cue_config::Config::load(&workspace)
+        .map(|c| c.priorities)
+        .unwrap_or_default();
+
     Ok(serde_json::json!({
         "tools": [
             {
@@ -255,6 +323,10 @@ async fn handle_tools_list() -> Result<Value> {
                             "type": "string",
                             "description": "Keywords to match (e.g. 'auth flow')"
                         },
+                        "filter": {
+                            "type": "string",
+                            "description": "Query expression to filter results, e.g. 'tag:api -priority:low'. Bare words must also match, except under OR/NOT"
+                        },
                         "limit": {
                             "type": "integer",
                             "description": "Max results per page (default: 10, max: 50)",
@@ -288,6 +360,11 @@ async fn handle_tools_list() -> Result<Value> {
                                     "description": "Filter by assignee (exact match)"
                                 }
                             }
+                        },
+                        "include_archived": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Also search archived cards"
                         }
                     },
                     "required": ["query"]
@@ -328,8 +405,8 @@ async fn handle_tools_list() -> Result<Value> {
                         },
                         "priority": {
                             "type": "string",
-                            "enum": ["low", "medium", "high", "critical"],
-                            "default": "medium",
+                            "enum": priorities.levels,
+                            "default": priorities.default,
                             "description": "Task priority"
                         },
                         "assignee": {
@@ -340,6 +417,22 @@ async fn handle_tools_list() -> Result<Value> {
                             "type": "array",
                             "items": { "type": "string" },
                             "description": "Task IDs this task depends on"
+                        },
+                        "parent": {
+                            "type": "string",
+                            "description": "Parent (epic) task ID"
+                        },
+                        "due": {
+                            "type": "string",
+                            "description": "Due date (YYYY-MM-DD)"
+                        },
+                        "milestone": {
+                            "type": "string",
+                            "description": "Milestone name"
+                        },
+                        "template": {
+                            "type": "string",
+                            "description": "Template name from .cuedeck/templates/ (e.g. bug, spike)"
                         }
                     },
                     "required": ["title"]
@@ -367,9 +460,21 @@ async fn handle_tools_list() -> Result<Value> {
                         },
                         "priority": {
                             "type": "string",
-                            "enum": ["critical", "high", "medium", "low"],
+                            "enum": priorities.levels,
                             "description": "Filter by priority"
                         },
+                        "parent": {
+                            "type": "string",
+                            "description": "Only list children of this epic"
+                        },
+                        "milestone": {
+                            "type": "string",
+                            "description": "Filter by milestone"
+                        },
+                        "due": {
+                            "type": "string",
+                            "description": "Filter by due date (due<1w, due:overdue, <2024-06-01)"
+                        },
                         "created": {
                             "type": "string",
                             "description": "Filter by created date (YYYY, YYYY-MM, YYYY-MM-DD, >2w, <7d)"
@@ -377,6 +482,25 @@ async fn handle_tools_list() -> Result<Value> {
                         "updated": {
                             "type": "string",
                             "description": "Filter by updated date (same formats as created)"
+                        },
+                        "query": {
+                            "type": "string",
+                            "description": "Query expression, e.g. 'status:todo (tag:auth OR tag:api) -assignee:@bob updated>2w \"login flow\"'"
+                        },
+                        "nested": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Return tasks as a parent/child tree with epic progress"
+                        },
+                        "include_archived": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Also list archived cards (implied by status 'archived')"
+                        },
+                        "unclaimed": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Only list cards without a live claim (see claim_task)"
                         }
                     }
                 }
@@ -389,7 +513,7 @@ async fn handle_tools_list() -> Result<Value> {
                     "properties": {
                         "id": {
                             "type": "string",
-                            "description": "Task ID to query"
+                            "description": "Task ID (or unambiguous prefix) to query"
                         },
                         "reverse": {
                             "type": "boolean",
@@ -436,15 +560,303 @@ async fn handle_tools_list() -> Result<Value> {
                     "properties": {
                         "id": {
                             "type": "string",
-                            "description": "6-character task ID"
+                            "description": "Task ID or unambiguous ID prefix"
                         },
                         "updates": {
                             "type": "object",
-                            "description": "Fields to update (status, assignee, priority)"
+                            "description": "Fields to update (status, assignee, priority, parent, ...). tags and depends_on take a list, or {\"add\": [...], \"remove\": [...]}; new dependencies are checked for existence and cycles. status changes must follow the [workflow] transitions in config.toml"
+                        },
+                        "expected_hash": {
+                            "type": "string",
+                            "description": "The card's hash as last read; if it changed since, the update fails with a conflict (1011) carrying the current hash and frontmatter"
                         }
                     },
                     "required": ["id", "updates"]
                 }
+            },
+            {
+                "name": "get_task_history",
+                "description": "Get the activity history (who changed what, when) of a task card",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task ID or unambiguous ID prefix"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Only return the most recent N events"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "bulk_update_tasks",
+                "description": "Apply the same metadata update to every task card matching a query (all-or-nothing)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "where": {
+                            "type": "string",
+                            "description": "Query selecting the cards, e.g. 'status:todo tag:auth'"
+                        },
+                        "updates": {
+                            "type": "object",
+                            "description": "Fields to set, same as update_task (e.g. {\"priority\": \"high\"})"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Return the per-card changes without writing them"
+                        }
+                    },
+                    "required": ["where", "updates"]
+                }
+            },
+            {
+                "name": "move_document",
+                "description": "Rename a doc or card, rewriting [[links]], depends_on/parent references and cache entries that point to it",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "from": {
+                            "type": "string",
+                            "description": "Current workspace-relative path, or a card ID"
+                        },
+                        "to": {
+                            "type": "string",
+                            "description": "New workspace-relative path, or a card ID"
+                        },
+                        "dry_run": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Return the files that would change without writing them"
+                        }
+                    },
+                    "required": ["from", "to"]
+                }
+            },
+            {
+                "name": "delete_task",
+                "description": "Delete a task card by moving it to .cuedeck/trash. Refused while other cards depend on it or use it as parent, unless cascade or force is set",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task ID (or unambiguous prefix)"
+                        },
+                        "cascade": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Remove the card from other cards' depends_on and parent"
+                        },
+                        "force": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Delete even if other cards still reference it"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "claim_task",
+                "description": "Claim a task card for a time-limited lease so other agents leave it alone. Fails with a lock error (1007) while someone else holds a live claim; expired claims are taken over. Claiming a card you hold renews the lease",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task ID (or unambiguous prefix)"
+                        },
+                        "claimant": {
+                            "type": "string",
+                            "description": "Who is claiming (defaults to the client name)"
+                        },
+                        "ttl_seconds": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "default": cue_core::claims::DEFAULT_CLAIM_TTL_SECS,
+                            "description": "Lease length in seconds"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "release_task",
+                "description": "Release a claim on a task card. Releasing someone else's live claim needs force",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task ID (or unambiguous prefix)"
+                        },
+                        "claimant": {
+                            "type": "string",
+                            "description": "Who is releasing (defaults to the client name)"
+                        },
+                        "force": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Release even if another claimant holds the card"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "start_work_session",
+                "description": "Start timing work on a task card. Fails if the author already has a session running on it",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task ID (or unambiguous prefix)"
+                        },
+                        "author": {
+                            "type": "string",
+                            "description": "Who is working (defaults to the client name)"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "stop_work_session",
+                "description": "Stop the author's running work session on a task card",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task ID (or unambiguous prefix)"
+                        },
+                        "author": {
+                            "type": "string",
+                            "description": "Whose session to stop (defaults to the client name)"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "log_work_session",
+                "description": "Record a finished work session on a task card after the fact",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task ID (or unambiguous prefix)"
+                        },
+                        "start": {
+                            "type": "string",
+                            "description": "Session start (RFC 3339)"
+                        },
+                        "end": {
+                            "type": "string",
+                            "description": "Session end (RFC 3339, not in the future)"
+                        },
+                        "author": {
+                            "type": "string",
+                            "description": "Who did the work (defaults to the client name)"
+                        }
+                    },
+                    "required": ["id", "start", "end"]
+                }
+            },
+            {
+                "name": "get_time_report",
+                "description": "Summarize logged work time per assignee or tag",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "since": {
+                            "type": "string",
+                            "description": "Only count time in this window (e.g. 7d, 2w, 3m)"
+                        },
+                        "by": {
+                            "type": "string",
+                            "enum": ["assignee", "tag"],
+                            "default": "assignee",
+                            "description": "How to group the time"
+                        }
+                    }
+                }
+            },
+            {
+                "name": "get_task_commits",
+                "description": "List local git commits whose message mentions a task card, newest first. Indexes commits made since the last scan first, but never changes cards (use scan_commits to apply Closes trailers)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task ID (or unambiguous prefix)"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "scan_commits",
+                "description": "Index git commits made since the last scan and, with [git] close_on_trailer, move cards named in Closes: trailers to done",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {}
+                }
+            },
+            {
+                "name": "get_velocity_report",
+                "description": "Delivery trends over a date range: daily burndown (open/closed cards), cards closed per week, and cycle time (created to done) percentiles per assignee or tag",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "since": {
+                            "type": "string",
+                            "default": "30d",
+                            "description": "Start of the range: YYYY-MM-DD or a duration ago (e.g. 30d, 8w)"
+                        },
+                        "until": {
+                            "type": "string",
+                            "description": "End of the range (default: today)"
+                        },
+                        "by": {
+                            "type": "string",
+                            "enum": ["assignee", "tag"],
+                            "default": "assignee",
+                            "description": "How to group cycle times"
+                        }
+                    }
+                }
+            },
+            {
+                "name": "get_digest",
+                "description": "Summarize what changed since a point in time: cards created, status changes, newly blocked and unblocked cards, other card updates and edited docs. Useful at the start of a session",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "since": {
+                            "type": "string",
+                            "default": "1d",
+                            "description": "Start of the window: a duration ago (e.g. 1d, 1w), YYYY-MM-DD or an RFC 3339 timestamp"
+                        }
+                    }
+                }
+            },
+            {
+                "name": "get_workload",
+                "description": "Open work per assignee: open cards by status and priority, blocked count, summed estimates, oldest active card, and whether the assignee is over their [capacity] limits",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {}
+                }
             }
         ]
     }))
@@ -460,6 +872,7 @@ async fn handle_read_context(params: Option<Value>) -> Result<Value> {
     #[derive(Deserialize)]
     struct SearchParams {
         query: String,
+        filter: Option<String>,
         limit: Option<usize>,
         cursor: Option<String>,
         #[serde(default)]
@@ -468,6 +881,8 @@ async fn handle_read_context(params: Option<Value>) -> Result<Value> {
         mode: Option<String>,
         #[serde(default)]
         filters: Option<FilterParams>,
+        #[serde(default)]
+        include_archived: bool,
     }
 
     #[derive(Deserialize)]
@@ -509,20 +924,43 @@ async fn handle_read_context(params: Option<Value>) -> Result<Value> {
         .map(std::path::PathBuf::from)
         .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
 
+    // Free text in the filter is ranked with the keywords; field terms filter
+    let mut search_text = params.query.clone();
+    let query_filter = match params.filter.as_deref() {
+        Some(filter) => {
+            let (text, rest) = cue_core::query::Query::parse(filter)?.split_text();
+            if !text.is_empty() {
+                search_text = format!("{} {}", search_text, text).trim().to_string();
+            }
+            (!rest.is_all()).then_some(rest)
+        }
+        None => None,
+    };
+
     // Convert MCP filters to core SearchFilters
-    let search_filters = params.filters.map(|f| {
-        cue_core::context::SearchFilters {
-            tags: f.tags,
-            priority: f.priority,
-            assignee: f.assignee,
+    let search_filters = match (params.filters, query_filter) {
+        (None, None) if !params.include_archived => None,
+        (f, query) => {
+            let f = f.unwrap_or(FilterParams {
+                tags: None,
+                priority: None,
+                assignee: None,
+            });
+            Some(cue_core::context::SearchFilters {
+                tags: f.tags,
+                priority: f.priority,
+                assignee: f.assignee,
+                query,
+                include_archived: params.include_archived,
+            })
         }
-    });
+    };
 
     // Use paginated search
     let search_mode = cue_core::context::SearchMode::parse(&mode_str);
     let search_result = cue_core::context::search_workspace_paginated(
         &workspace,
-        &params.query,
+        &search_text,
         search_mode,
         search_filters,
         limit,
@@ -593,8 +1031,18 @@ async fn handle_list_tasks(params: Option<Value>) -> Result<Value> {
         assignee: Option<String>,
         tags: Option<Vec<String>>,
         priority: Option<String>,
+        parent: Option<String>,
+        milestone: Option<String>,
+        due: Option<String>,
         created: Option<String>,
         updated: Option<String>,
+        query: Option<String>,
+        #[serde(default)]
+        nested: bool,
+        #[serde(default)]
+        include_archived: bool,
+        #[serde(default)]
+        unclaimed: bool,
     }
 
     let params: ListTasksParams = if let Some(p) = params {
@@ -606,8 +1054,15 @@ async fn handle_list_tasks(params: Option<Value>) -> Result<Value> {
             assignee: None,
             tags: None,
             priority: None,
+            parent: None,
+            milestone: None,
+            due: None,
             created: None,
             updated: None,
+            query: None,
+            nested: false,
+            include_archived: false,
+            unclaimed: false,
         }
     };
 
@@ -622,6 +1077,13 @@ async fn handle_list_tasks(params: Option<Value>) -> Result<Value> {
         assignee: params.assignee,
         tags: params.tags,
         priority: params.priority,
+        parent: params.parent,
+        milestone: params.milestone,
+        due: params
+            .due
+            .as_deref()
+            .map(cue_core::task_filters::parse_due_filter)
+            .transpose()?,
         created: params
             .created
             .as_deref()
@@ -632,10 +1094,22 @@ async fn handle_list_tasks(params: Option<Value>) -> Result<Value> {
             .as_deref()
             .map(cue_core::task_filters::parse_date_filter)
             .transpose()?,
+        query: params
+            .query
+            .as_deref()
+            .map(cue_core::query::Query::parse)
+            .transpose()?,
+        include_archived: params.include_archived,
+        unclaimed: params.unclaimed,
     };
     
     let tasks = cue_core::tasks::list_tasks_filtered(&workspace, &filters)?;
 
+    if params.nested {
+        let tree = cue_core::task_hierarchy::build_task_tree(&workspace, tasks)?;
+        return serde_json::to_value(tree).map_err(CueError::JsonError);
+    }
+
     serde_json::to_value(tasks).map_err(CueError::JsonError)
 }
 
@@ -648,6 +1122,10 @@ async fn handle_create_task(params: Option<Value>) -> Result<Value> {
         priority: Option<String>,
         assignee: Option<String>,
         depends_on: Option<Vec<String>>,
+        parent: Option<String>,
+        due: Option<String>,
+        milestone: Option<String>,
+        template: Option<String>,
     }
 
     let params: CreateTaskParams = serde_json::from_value(params.unwrap_or_default())?;
@@ -656,28 +1134,30 @@ async fn handle_create_task(params: Option<Value>) -> Result<Value> {
         .map(std::path::PathBuf::from)
         .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
 
-    // Validate depends_on if provided
-    if let Some(deps) = &params.depends_on {
-        for dep_id in deps {
-            let dep_path = workspace.join(".cuedeck/cards").join(format!("{}.md", dep_id));
-            if !dep_path.exists() {
-                return Err(CueError::DependencyNotFound(dep_id.clone()));
-            }
-        }
-    }
+    let options = cue_core::tasks::NewTaskOptions {
+        tags: params.tags,
+        priority: params.priority,
+        assignee: params.assignee,
+        depends_on: params.depends_on,
+        parent: params.parent,
+        due: params.due,
+        milestone: params.milestone,
+        template: params.template,
+        ..Default::default()
+    };
+    let path = cue_core::tasks::create_task_with_options(&workspace, &params.title, &options)?;
 
-    let path = cue_core::tasks::create_task_with_metadata(
-        &workspace,
-        &params.title,
-        params.tags,
-        params.priority.as_deref(),
-        params.assignee.as_deref(),
-        params.depends_on,
-    )?;
-
-    // Return the created task doc
+    // Return the created task doc, warning if its assignee is over [capacity]
     let doc = cue_core::parse_file(&path)?;
-    serde_json::to_value(doc).map_err(CueError::JsonError)
+    let warnings = match doc.frontmatter.as_ref().and_then(|m| m.assignee.as_deref()) {
+        Some(assignee) => cue_core::workload::capacity_warnings(&workspace, assignee)?,
+        None => Vec::new(),
+    };
+    let mut result = serde_json::to_value(doc).map_err(CueError::JsonError)?;
+    if !warnings.is_empty() {
+        result["warnings"] = serde_json::json!(warnings);
+    }
+    Ok(result)
 }
 
 /// Get task dependencies handler
@@ -689,17 +1169,18 @@ async fn handle_get_task_dependencies(params: Option<Value>) -> Result<Value> {
         reverse: bool,
     }
 
-    let params: GetDepsParams = serde_json::from_value(params.unwrap_or_default())?;
+    let mut params: GetDepsParams = serde_json::from_value(params.unwrap_or_default())?;
     let workspace = std::env::var("CUE_WORKSPACE")
         .map(std::path::PathBuf::from)
         .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+    params.id = cue_core::card_ids::resolve_task_id(&workspace, &params.id)?;
 
     if params.reverse {
         // Get dependents
         let dependents = cue_core::tasks::get_task_dependents(&workspace, &params.id)?;
         let mut dep_details = Vec::new();
         for dep in dependents {
-            let task_path = workspace.join(format!(".cuedeck/cards/{}.md", dep.from_id));
+            let task_path = cue_core::archive::card_path(&workspace, &dep.from_id);
             if let Ok(doc) = cue_core::parse_file(&task_path) {
                 dep_details.push(serde_json::json!({
                     "id": dep.from_id,
@@ -719,7 +1200,7 @@ async fn handle_get_task_dependencies(params: Option<Value>) -> Result<Value> {
         let dependencies = cue_core::tasks::get_task_dependencies(&workspace, &params.id)?;
         let mut dep_details = Vec::new();
         for dep in dependencies {
-            let task_path = workspace.join(format!(".cuedeck/cards/{}.md", dep.to_id));
+            let task_path = cue_core::archive::card_path(&workspace, &dep.to_id);
             if let Ok(doc) = cue_core::parse_file(&task_path) {
                 dep_details.push(serde_json::json!({
                     "id": dep.to_id,
@@ -750,6 +1231,7 @@ async fn handle_validate_task_graph(params: Option<Value>) -> Result<Value> {
         .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
 
     if let Some(task_id) = params.id {
+        let task_id = cue_core::card_ids::resolve_task_id(&workspace, &task_id)?;
         let deps = cue_core::tasks::get_task_dependencies(&workspace, &task_id)?;
         let dep_ids: Vec<String> = deps.into_iter().map(|d| d.to_id).collect();
 
@@ -823,30 +1305,522 @@ async fn handle_update_task(params: Option<Value>) -> Result<Value> {
     struct UpdateTaskParams {
         id: String,
         updates: serde_json::Map<String, Value>,
+        #[serde(default)]
+        expected_hash: Option<String>,
     }
 
-    let params: UpdateTaskParams = params
+    let mut params: UpdateTaskParams = params
         .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
         .and_then(|v| {
             serde_json::from_value(v)
                 .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
         })?;
 
-    // Validate ID pattern (6-char alphanumeric)
-    if !params.id.chars().all(|c| c.is_ascii_alphanumeric()) || params.id.len() != 6 {
-        return Err(CueError::ValidationError(
-            "Task ID must be 6 alphanumeric characters".to_string(),
-        ));
-    }
-
     // Use CUE_WORKSPACE env var if set, otherwise use current directory
     let workspace = std::env::var("CUE_WORKSPACE")
         .map(std::path::PathBuf::from)
         .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    // Accept full IDs or unambiguous prefixes (also rejects path-like IDs)
+    params.id = cue_core::card_ids::resolve_task_id(&workspace, &params.id)?;
     
-    let doc = cue_core::tasks::update_task(&workspace, &params.id, params.updates)?;
+    let workflow = cue_config::Config::load(&workspace)?.workflow;
+    let closing = params
+        .updates
+        .get("status")
+        .and_then(|v| v.as_str())
+        .is_some_and(|status| workflow.is_terminal(status));
+    let reassigned = params.updates.contains_key("assignee") || params.updates.contains_key("estimate");
+
+    let doc = cue_core::tasks::update_task_checked(
+        &workspace,
+        &params.id,
+        params.updates,
+        params.expected_hash.as_deref(),
+    )?;
+    let assignee = doc.frontmatter.as_ref().and_then(|m| m.assignee.clone());
+    let mut result = serde_json::to_value(doc).map_err(CueError::JsonError)?;
+    let mut warnings = Vec::new();
+
+    // Closing an epic is allowed, but surface children that are still open
+    if closing {
+        let open = cue_core::task_hierarchy::open_children(&workspace, &params.id)?;
+        if !open.is_empty() {
+            warnings.push(format!(
+                "Epic {} still has {} open child card(s): {}",
+                params.id,
+                open.len(),
+                open.join(", ")
+            ));
+        }
+    }
 
-    serde_json::to_value(doc).map_err(CueError::JsonError)
+    // Assigning work past [capacity] is allowed too, with a warning
+    if let Some(assignee) = assignee.filter(|_| reassigned) {
+        warnings.extend(cue_core::workload::capacity_warnings(&workspace, &assignee)?);
+    }
+
+    if !warnings.is_empty() {
+        result["warnings"] = serde_json::json!(warnings);
+    }
+    Ok(result)
+}
+
+/// Bulk update handler - apply one update map to every matching card
+async fn handle_bulk_update_tasks(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct BulkUpdateParams {
+        #[serde(rename = "where")]
+        filter: String,
+        updates: serde_json::Map<String, Value>,
+        #[serde(default)]
+        dry_run: bool,
+    }
+
+    let params: BulkUpdateParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let filters = cue_core::TaskFilters {
+        query: Some(cue_core::query::Query::parse(&params.filter)?),
+        ..Default::default()
+    };
+    let result =
+        cue_core::bulk::bulk_update_tasks(&workspace, &filters, &params.updates, params.dry_run)?;
+
+    serde_json::to_value(result).map_err(CueError::JsonError)
+}
+
+/// Move document handler - rename a doc or card and rewrite references to it
+async fn handle_move_document(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct MoveParams {
+        from: String,
+        to: String,
+        #[serde(default)]
+        dry_run: bool,
+    }
+
+    let params: MoveParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let report =
+        cue_core::rename::move_document(&workspace, &params.from, &params.to, params.dry_run)?;
+
+    serde_json::to_value(report).map_err(CueError::JsonError)
+}
+
+/// Delete task handler - move a card to the trash
+async fn handle_delete_task(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct DeleteParams {
+        id: String,
+        #[serde(default)]
+        cascade: bool,
+        #[serde(default)]
+        force: bool,
+    }
+
+    let params: DeleteParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let options = cue_core::trash::DeleteOptions {
+        cascade: params.cascade,
+        force: params.force,
+    };
+    let report = cue_core::trash::delete_task(&workspace, &params.id, &options)?;
+
+    serde_json::to_value(report).map_err(CueError::JsonError)
+}
+
+/// Claim task handler - take a lease on a card
+async fn handle_claim_task(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct ClaimParams {
+        id: String,
+        claimant: Option<String>,
+        ttl_seconds: Option<i64>,
+    }
+
+    let params: ClaimParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let claimant = claimant_or_author(&workspace, params.claimant)?;
+    let ttl = chrono::Duration::seconds(
+        params
+            .ttl_seconds
+            .unwrap_or(cue_core::claims::DEFAULT_CLAIM_TTL_SECS),
+    );
+    let claim = cue_core::claims::claim_task(&workspace, &params.id, &claimant, ttl)?;
+
+    serde_json::to_value(claim).map_err(CueError::JsonError)
+}
+
+/// Release task handler - drop a lease on a card
+async fn handle_release_task(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct ReleaseParams {
+        id: String,
+        claimant: Option<String>,
+        #[serde(default)]
+        force: bool,
+    }
+
+    let params: ReleaseParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let claimant = claimant_or_author(&workspace, params.claimant)?;
+    let id = cue_core::card_ids::resolve_task_id(&workspace, &params.id)?;
+    cue_core::claims::release_task(&workspace, &id, &claimant, params.force)?;
+
+    Ok(serde_json::json!({ "id": id, "released": true }))
+}
+
+/// Start work session handler - begin timing work on a card
+async fn handle_start_work_session(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct StartSessionParams {
+        id: String,
+        author: Option<String>,
+    }
+
+    let params: StartSessionParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let session =
+        cue_core::timelog::start_session(&workspace, &params.id, params.author.as_deref())?;
+
+    serde_json::to_value(session).map_err(CueError::JsonError)
+}
+
+/// Stop work session handler - close the running session on a card
+async fn handle_stop_work_session(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct StopSessionParams {
+        id: String,
+        author: Option<String>,
+    }
+
+    let params: StopSessionParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let session =
+        cue_core::timelog::stop_session(&workspace, &params.id, params.author.as_deref())?;
+    let seconds = session.duration(chrono::Utc::now(), None).num_seconds();
+
+    let mut result = serde_json::to_value(session).map_err(CueError::JsonError)?;
+    result["seconds"] = seconds.into();
+    Ok(result)
+}
+
+/// Log work session handler - record a finished session after the fact
+async fn handle_log_work_session(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct LogSessionParams {
+        id: String,
+        start: String,
+        end: String,
+        author: Option<String>,
+    }
+
+    let params: LogSessionParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let parse = |field: &str, value: &str| {
+        chrono::DateTime::parse_from_rfc3339(value)
+            .map(|t| t.with_timezone(&chrono::Utc))
+            .map_err(|_| {
+                CueError::ValidationError(format!(
+                    "Invalid {} '{}'. Expected an RFC 3339 timestamp",
+                    field, value
+                ))
+            })
+    };
+    let start = parse("start", &params.start)?;
+    let end = parse("end", &params.end)?;
+
+    let session = cue_core::timelog::log_session(
+        &workspace,
+        &params.id,
+        params.author.as_deref(),
+        start,
+        end,
+    )?;
+
+    serde_json::to_value(session).map_err(CueError::JsonError)
+}
+
+/// Get time report handler - logged time per assignee or tag
+async fn handle_get_time_report(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize, Default)]
+    struct TimeReportParams {
+        since: Option<String>,
+        by: Option<String>,
+    }
+
+    let params: TimeReportParams = if let Some(p) = params {
+        serde_json::from_value(p)
+            .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))?
+    } else {
+        TimeReportParams::default()
+    };
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let by_name = params.by.as_deref().unwrap_or("assignee");
+    let by = cue_core::timelog::TimeGrouping::parse(by_name)?;
+    let since = match params.since.as_deref() {
+        Some(s) => {
+            let age = cue_core::task_filters::parse_relative_duration(s).ok_or_else(|| {
+                CueError::ValidationError(format!(
+                    "Invalid since '{}'. Expected e.g. 7d, 2w, 3m",
+                    s
+                ))
+            })?;
+            Some(chrono::Utc::now() - age)
+        }
+        None => None,
+    };
+    let report = cue_core::timelog::time_report(&workspace, since, by)?;
+
+    Ok(serde_json::json!({ "by": by_name, "buckets": report }))
+}
+
+/// Get task commits handler - git commits that mention a card
+async fn handle_get_task_commits(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct GetCommitsParams {
+        id: String,
+    }
+
+    let params: GetCommitsParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let id = cue_core::card_ids::resolve_task_id(&workspace, &params.id)?;
+    let (commits, scan) = cue_core::commits::task_commits(&workspace, &id)?;
+
+    Ok(serde_json::json!({ "id": id, "commits": commits, "scan": scan }))
+}
+
+/// Scan commits handler - index new commits and apply Closes trailers
+async fn handle_scan_commits() -> Result<Value> {
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let report = cue_core::commits::scan_commits(&workspace, true)?;
+    serde_json::to_value(report).map_err(CueError::JsonError)
+}
+
+/// Get velocity report handler - burndown, throughput and cycle time
+async fn handle_get_velocity_report(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize, Default)]
+    struct VelocityParams {
+        since: Option<String>,
+        until: Option<String>,
+        by: Option<String>,
+    }
+
+    let params: VelocityParams = if let Some(p) = params {
+        serde_json::from_value(p)
+            .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))?
+    } else {
+        VelocityParams::default()
+    };
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let today = chrono::Utc::now().date_naive();
+    let parse = |value: &str| {
+        cue_core::metrics::parse_day(value, today).ok_or_else(|| {
+            CueError::ValidationError(format!(
+                "Invalid date '{}'. Expected YYYY-MM-DD or e.g. 30d, 8w",
+                value
+            ))
+        })
+    };
+    let from = parse(params.since.as_deref().unwrap_or("30d"))?;
+    let to = match params.until.as_deref() {
+        Some(until) => parse(until)?,
+        None => today,
+    };
+    if from > to {
+        return Err(CueError::ValidationError(format!(
+            "since ({}) is after until ({})",
+            from, to
+        )));
+    }
+    let by_name = params.by.as_deref().unwrap_or("assignee");
+    let by = cue_core::timelog::TimeGrouping::parse(by_name)?;
+    let report = cue_core::metrics::velocity_report(&workspace, from, to, by)?;
+
+    let mut value = serde_json::to_value(report)?;
+    value["by"] = Value::from(by_name);
+    Ok(value)
+}
+
+/// Get digest handler - changes since a point in time, with a Markdown rendering
+async fn handle_get_digest(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize, Default)]
+    struct DigestParams {
+        since: Option<String>,
+    }
+
+    let params: DigestParams = if let Some(p) = params {
+        serde_json::from_value(p)
+            .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))?
+    } else {
+        DigestParams::default()
+    };
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let since = params.since.as_deref().unwrap_or("1d");
+    let cutoff = cue_core::digest::parse_since(since, chrono::Utc::now()).ok_or_else(|| {
+        CueError::ValidationError(format!(
+            "Invalid since '{}'. Expected e.g. 1d, 1w, 2024-06-01",
+            since
+        ))
+    })?;
+    let digest = cue_core::digest::digest(&workspace, cutoff)?;
+
+    let mut value = serde_json::to_value(&digest)?;
+    value["markdown"] = Value::from(digest.to_markdown());
+    Ok(value)
+}
+
+/// Get workload handler - open work per assignee against capacity limits
+async fn handle_get_workload() -> Result<Value> {
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    let report = cue_core::workload::workload(&workspace)?;
+    Ok(serde_json::json!({ "assignees": report }))
+}
+
+/// Explicit claimant, else the session author (MCP client name or config)
+fn claimant_or_author(workspace: &std::path::Path, claimant: Option<String>) -> Result<String> {
+    claimant
+        .filter(|c| !c.is_empty())
+        .or_else(|| cue_core::activity::current_author(workspace))
+        .ok_or_else(|| {
+            CueError::ValidationError(
+                "No claimant given and no client name or [author] name to default to".to_string(),
+            )
+        })
+}
+
+/// Get task history handler - read a card's activity log entries
+async fn handle_get_task_history(params: Option<Value>) -> Result<Value> {
+    #[derive(Deserialize)]
+    struct GetHistoryParams {
+        id: String,
+        limit: Option<usize>,
+    }
+
+    let params: GetHistoryParams = params
+        .ok_or_else(|| CueError::ValidationError("Missing params".to_string()))
+        .and_then(|v| {
+            serde_json::from_value(v)
+                .map_err(|e| CueError::ValidationError(format!("Invalid params: {}", e)))
+        })?;
+
+    let workspace = std::env::var("CUE_WORKSPACE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default());
+
+    // History outlives the card, so fall back to the literal ID if no card matches
+    let id = match cue_core::card_ids::resolve_task_id(&workspace, &params.id) {
+        Ok(id) => id,
+        Err(CueError::FileNotFound { .. }) => params.id,
+        Err(e) => return Err(e),
+    };
+
+    let mut history = cue_core::activity::task_history(&workspace, &id)?;
+    if let Some(limit) = params.limit {
+        let skip = history.len().saturating_sub(limit);
+        history.drain(..skip);
+    }
+
+    serde_json::to_value(history).map_err(CueError::JsonError)
 }
 
 #[cfg(test)]
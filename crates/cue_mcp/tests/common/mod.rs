@@ -0,0 +1,27 @@
+//! JSON-RPC helpers shared by the MCP integration tests
+
+#![allow(dead_code)]
+
+use cue_mcp::{JsonRpcRequest, JsonRpcResponse};
+use serde_json::{json, Value};
+
+/// Build a JSON-RPC request
+pub fn request(id: i64, method: &str, params: Value) -> JsonRpcRequest {
+    JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: Some(json!(id)),
+        method: method.to_string(),
+        params: Some(params),
+    }
+}
+
+/// Build a `tools/call` request for `name`
+pub fn tool_call(id: i64, name: &str, arguments: Value) -> JsonRpcRequest {
+    request(id, "tools/call", json!({ "name": name, "arguments": arguments }))
+}
+
+/// Parse the JSON text content of a successful tool call
+pub fn tool_text(resp: JsonRpcResponse) -> Value {
+    let result = resp.result.expect("tool call should succeed");
+    serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap()
+}
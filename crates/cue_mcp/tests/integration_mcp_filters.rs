@@ -1,4 +1,7 @@
+mod common;
+
 use assert_fs::prelude::*;
+use common::{tool_call, tool_text};
 use cue_mcp::{handle_request, JsonRpcRequest};
 use serde_json::{json, Value};
 use std::env;
@@ -67,3 +70,40 @@ async fn test_integration_create_and_filter() {
     let tasks_arr = tasks_both.as_array().unwrap();
     assert_eq!(tasks_arr.len(), 2);
 }
+
+#[tokio::test]
+async fn test_read_context_keywords_and_filter() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards = temp.child(".cuedeck/cards");
+    cards.child("aaa111.md").write_str("---\ntitle: Entry\ntags: [cli]\n---\nTODO: fix main() on error: foo\n").unwrap();
+    cards.child("bbb222.md").write_str("---\ntitle: API\ntags: [api]\n---\nendpoints\n").unwrap();
+    cards.child("ccc333.md").write_str("---\ntitle: Notes\n---\nnothing here\n").unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let search = |id: i64, arguments: Value| tool_call(id, "read_context", arguments);
+    let names = |result: &Value| -> Vec<String> {
+        let mut paths: Vec<String> = result["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["path"].as_str().unwrap().rsplit('/').next().unwrap().to_string())
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    // Query syntax characters in plain keywords are not parsed
+    for (i, query) in ["main()", "TODO: fix", "error: foo"].iter().enumerate() {
+        let result = tool_text(handle_request(search(i as i64, json!({ "query": query, "mode": "keyword" }))).await.unwrap());
+        assert_eq!(names(&result), vec!["aaa111.md"], "query {:?}", query);
+    }
+
+    // Bare words under OR are matched in place, not required
+    let result = tool_text(handle_request(search(10, json!({
+        "query": "", "filter": "tag:api OR main", "mode": "keyword"
+    }))).await.unwrap());
+    assert_eq!(names(&result), vec!["aaa111.md", "bbb222.md"]);
+
+    let resp = handle_request(search(11, json!({ "query": "x", "filter": "colour:red" }))).await.unwrap();
+    assert!(resp.error.unwrap().message.contains("Unknown field 'colour'"));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{tool_call, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_archived_cards_as_dependencies() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cuedeck/cards").create_dir_all().unwrap();
+    temp.child(".cuedeck/archive/old111.md")
+        .write_str("---\ntitle: Old Work\nstatus: archived\n---\n")
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let created = tool_text(handle_request(tool_call(1, "create_task", json!({
+        "title": "Follow-up", "depends_on": ["old111"]
+    }))).await.unwrap());
+    let id = created["path"].as_str().unwrap().rsplit('/').next().unwrap().trim_end_matches(".md").to_string();
+
+    let deps = tool_text(handle_request(tool_call(2, "get_task_dependencies", json!({ "id": id }))).await.unwrap());
+    assert_eq!(deps["count"], 1);
+    assert_eq!(deps["tasks"][0]["title"], "Old Work");
+    assert_eq!(deps["tasks"][0]["status"], "archived");
+
+    let dependents = tool_text(handle_request(tool_call(3, "get_task_dependencies", json!({
+        "id": "old111", "reverse": true
+    }))).await.unwrap());
+    assert_eq!(dependents["tasks"][0]["title"], "Follow-up");
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_bulk_update_tasks_dry_run_then_apply() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cuedeck/cards").create_dir_all().unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    for (i, tag) in ["auth", "auth", "ui"].iter().enumerate() {
+        handle_request(request(i as i64, "tools/call", json!({
+            "name": "create_task",
+            "arguments": { "title": format!("Task {}", i), "tags": [tag] }
+        }))).await.unwrap();
+    }
+
+    let preview = tool_text(handle_request(request(10, "tools/call", json!({
+        "name": "bulk_update_tasks",
+        "arguments": { "where": "tag:auth", "updates": { "priority": "high" }, "dry_run": true }
+    }))).await.unwrap());
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["cards"].as_array().unwrap().len(), 2);
+    assert_eq!(preview["cards"][0]["changes"][0]["field"], "priority");
+    assert_eq!(preview["cards"][0]["changes"][0]["new"], "high");
+
+    let high = tool_text(handle_request(request(11, "tools/call", json!({
+        "name": "list_tasks",
+        "arguments": { "priority": "high" }
+    }))).await.unwrap());
+    assert_eq!(high.as_array().unwrap().len(), 0, "dry run must not write");
+
+    handle_request(request(12, "tools/call", json!({
+        "name": "bulk_update_tasks",
+        "arguments": { "where": "tag:auth", "updates": { "priority": "high" } }
+    }))).await.unwrap();
+
+    let high = tool_text(handle_request(request(13, "tools/call", json!({
+        "name": "list_tasks",
+        "arguments": { "priority": "high" }
+    }))).await.unwrap());
+    assert_eq!(high.as_array().unwrap().len(), 2);
+
+    // An empty selector is refused rather than touching every card
+    let resp = handle_request(request(14, "tools/call", json!({
+        "name": "bulk_update_tasks",
+        "arguments": { "where": "", "updates": { "priority": "low" } }
+    }))).await.unwrap();
+    assert!(resp.error.is_some());
+}
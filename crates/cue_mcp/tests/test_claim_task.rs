@@ -0,0 +1,47 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{tool_call, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_claim_and_release_task() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards = temp.child(".cuedeck/cards");
+    cards.child("abc123.md").write_str("---\ntitle: Shared\nstatus: todo\n---\n").unwrap();
+    cards.child("def456.md").write_str("---\ntitle: Free\nstatus: todo\n---\n").unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let claim = tool_text(handle_request(tool_call(1, "claim_task", json!({
+        "id": "abc123", "claimant": "agent-a", "ttl_seconds": 600
+    }))).await.unwrap());
+    assert_eq!(claim["claimed_by"], "agent-a");
+    assert_eq!(claim["renewed"], false);
+
+    // A second agent is locked out and told who holds the card
+    let resp = handle_request(tool_call(2, "claim_task", json!({ "id": "abc123", "claimant": "agent-b" }))).await.unwrap();
+    let error = resp.error.expect("claimed card should be locked");
+    assert_eq!(error.code, 1007);
+    assert_eq!(error.data.unwrap()["holder"], "agent-a");
+
+    let unclaimed = tool_text(handle_request(tool_call(3, "list_tasks", json!({ "unclaimed": true }))).await.unwrap());
+    let titles: Vec<&str> = unclaimed
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["frontmatter"]["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, vec!["Free"]);
+
+    let released = tool_text(handle_request(tool_call(4, "release_task", json!({
+        "id": "abc123", "claimant": "agent-a"
+    }))).await.unwrap());
+    assert_eq!(released["released"], true);
+
+    let claim = tool_text(handle_request(tool_call(5, "claim_task", json!({
+        "id": "abc123", "claimant": "agent-b"
+    }))).await.unwrap());
+    assert_eq!(claim["claimed_by"], "agent-b");
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_delete_task_checks_dependents() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards = temp.child(".cuedeck/cards");
+    cards.create_dir_all().unwrap();
+    cards.child("base11.md").write_str("---\ntitle: Base\n---\n").unwrap();
+    cards
+        .child("next11.md")
+        .write_str("---\ntitle: Next\ndepends_on: [base11]\n---\n")
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let resp = handle_request(request(1, "tools/call", json!({
+        "name": "delete_task",
+        "arguments": { "id": "base11" }
+    }))).await.unwrap();
+    let error = resp.error.expect("referenced card must not be deleted");
+    assert_eq!(error.code, -32602);
+    assert!(error.message.contains("next11"));
+
+    let report = tool_text(handle_request(request(2, "tools/call", json!({
+        "name": "delete_task",
+        "arguments": { "id": "base11", "force": true }
+    }))).await.unwrap());
+    assert_eq!(report["trash_path"], ".cuedeck/trash/base11.md");
+    assert_eq!(report["dependents"], json!(["next11"]));
+    assert_eq!(report["cascaded"], false);
+    assert!(!cards.child("base11.md").path().exists());
+
+    let history = tool_text(handle_request(request(3, "tools/call", json!({
+        "name": "get_task_history",
+        "arguments": { "id": "base11" }
+    }))).await.unwrap());
+    assert!(history.to_string().contains("deleted"), "got {}", history);
+}
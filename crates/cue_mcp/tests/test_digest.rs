@@ -0,0 +1,44 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_get_digest() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards = temp.child(".cuedeck/cards");
+    cards
+        .child("api001.md")
+        .write_str("---\ntitle: API\nstatus: done\ncreated: 2024-06-01T09:00:00Z\n---\n")
+        .unwrap();
+    cards
+        .child("ui0001.md")
+        .write_str("---\ntitle: UI\nstatus: todo\ndepends_on: [api001]\ncreated: 2024-06-01T09:00:00Z\n---\n")
+        .unwrap();
+    temp.child(".cuedeck/activity.jsonl")
+        .write_str(concat!(
+            r#"{"timestamp":"2024-06-10T09:00:00Z","task_id":"api001","action":"updated","changes":[{"field":"status","old":"active","new":"done"}]}"#,
+            "\n"
+        ))
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let digest = tool_text(handle_request(request(1, "tools/call", json!({
+        "name": "get_digest",
+        "arguments": { "since": "2024-06-10" }
+    }))).await.unwrap());
+
+    assert_eq!(digest["status_changes"][0]["to"], "done");
+    assert_eq!(digest["unblocked"][0]["id"], "ui0001");
+    assert!(digest["created"].as_array().unwrap().is_empty());
+    assert!(digest["markdown"].as_str().unwrap().contains("## Unblocked (1)"));
+
+    let resp = handle_request(request(2, "tools/call", json!({
+        "name": "get_digest",
+        "arguments": { "since": "later" }
+    }))).await.unwrap();
+    assert!(resp.error.unwrap().message.contains("Invalid since"));
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_update_task_expected_hash_conflict() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cuedeck/cards/abc123.md")
+        .write_str("---\ntitle: Shared\nstatus: todo\n---\n")
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let update = |id: i64, assignee: &str, hash: &str| {
+        request(id, "tools/call", json!({
+            "name": "update_task",
+            "arguments": { "id": "abc123", "updates": { "assignee": assignee }, "expected_hash": hash }
+        }))
+    };
+
+    let read = cue_core::parse_file(&temp.path().join(".cuedeck/cards/abc123.md")).unwrap();
+
+    // The first agent wins; the second read the same version and conflicts
+    let first = tool_text(handle_request(update(1, "@dana", &read.hash)).await.unwrap());
+    assert_eq!(first["frontmatter"]["assignee"], "@dana");
+
+    let resp = handle_request(update(2, "@lee", &read.hash)).await.unwrap();
+    let error = resp.error.expect("stale hash should conflict");
+    assert_eq!(error.code, 1011);
+    let data = error.data.unwrap();
+    assert_eq!(data["current_hash"], first["hash"]);
+    assert_eq!(data["frontmatter"]["assignee"], "@dana");
+
+    let second = tool_text(handle_request(update(3, "@lee", first["hash"].as_str().unwrap())).await.unwrap());
+    assert_eq!(second["frontmatter"]["assignee"], "@lee");
+}
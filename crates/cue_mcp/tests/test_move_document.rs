@@ -0,0 +1,48 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_move_document_renames_card_and_references() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards = temp.child(".cuedeck/cards");
+    cards.create_dir_all().unwrap();
+    cards.child("aaa111.md").write_str("---\ntitle: Base\n---\n").unwrap();
+    cards
+        .child("bbb222.md")
+        .write_str("---\ntitle: Next\ndepends_on:\n  - aaa111\n---\nAfter [[aaa111]].\n")
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let preview = tool_text(handle_request(request(1, "tools/call", json!({
+        "name": "move_document",
+        "arguments": { "from": "aaa111", "to": "base-1", "dry_run": true }
+    }))).await.unwrap());
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["files"][0]["path"], ".cuedeck/cards/bbb222.md");
+    assert_eq!(preview["files"][0]["changes"].as_array().unwrap().len(), 2);
+    assert!(cards.child("aaa111.md").path().exists());
+
+    let report = tool_text(handle_request(request(2, "tools/call", json!({
+        "name": "move_document",
+        "arguments": { "from": "aaa111", "to": "base-1" }
+    }))).await.unwrap());
+    assert_eq!(report["to"], ".cuedeck/cards/base-1.md");
+
+    let deps = tool_text(handle_request(request(3, "tools/call", json!({
+        "name": "get_task_dependencies",
+        "arguments": { "id": "bbb222" }
+    }))).await.unwrap());
+    assert!(deps.to_string().contains("base-1"), "got {}", deps);
+
+    // Moving onto an existing card is refused
+    let resp = handle_request(request(4, "tools/call", json!({
+        "name": "move_document",
+        "arguments": { "from": "bbb222", "to": "base-1" }
+    }))).await.unwrap();
+    assert!(resp.error.is_some());
+}
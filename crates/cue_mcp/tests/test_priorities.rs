@@ -0,0 +1,52 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_custom_priority_scale() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cuedeck/cards").create_dir_all().unwrap();
+    temp.child(".cuedeck/config.toml")
+        .write_str("[priorities]\nlevels = [\"P0\", \"P1\", \"P2\", \"P3\"]\ndefault = \"P2\"\n\n[priorities.aliases]\nurgent = \"P0\"\n")
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let tools = handle_request(request(1, "tools/list", json!({}))).await.unwrap();
+    let tools = tools.result.unwrap()["tools"].as_array().unwrap().clone();
+    let create = tools.iter().find(|t| t["name"] == "create_task").unwrap();
+    assert_eq!(create["inputSchema"]["properties"]["priority"]["enum"], json!(["P0", "P1", "P2", "P3"]));
+    assert_eq!(create["inputSchema"]["properties"]["priority"]["default"], "P2");
+
+    for (i, priority) in [Some("P3"), None, Some("urgent")].iter().enumerate() {
+        let mut args = json!({ "title": format!("Task {}", i) });
+        if let Some(p) = priority {
+            args["priority"] = json!(p);
+        }
+        handle_request(request(10 + i as i64, "tools/call", json!({
+            "name": "create_task",
+            "arguments": args
+        }))).await.unwrap();
+    }
+
+    let listed = tool_text(handle_request(request(20, "tools/call", json!({
+        "name": "list_tasks",
+        "arguments": {}
+    }))).await.unwrap());
+    let priorities: Vec<&str> = listed
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["frontmatter"]["priority"].as_str().unwrap())
+        .collect();
+    assert_eq!(priorities, vec!["P0", "P2", "P3"]);
+
+    let resp = handle_request(request(21, "tools/call", json!({
+        "name": "create_task",
+        "arguments": { "title": "Bad", "priority": "high" }
+    }))).await.unwrap();
+    assert!(resp.error.unwrap().message.contains("Invalid priority 'high'"));
+}
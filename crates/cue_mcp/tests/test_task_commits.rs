@@ -0,0 +1,40 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git").arg("-C").arg(dir).args(args).output().unwrap().status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[tokio::test]
+async fn test_get_task_commits() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cuedeck/cards/abc123.md")
+        .write_str("---\ntitle: Login\nstatus: todo\n---\n")
+        .unwrap();
+    git(temp.path(), &["init", "-q"]);
+    git(temp.path(), &["config", "user.name", "Dana"]);
+    git(temp.path(), &["config", "user.email", "dana@example.com"]);
+    git(temp.path(), &["config", "commit.gpgsign", "false"]);
+    git(temp.path(), &["commit", "--allow-empty", "-q", "-m", "fix login (abc123)"]);
+    git(temp.path(), &["commit", "--allow-empty", "-q", "-m", "docs only"]);
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let result = tool_text(handle_request(request(1, "tools/call", json!({
+        "name": "get_task_commits",
+        "arguments": { "id": "abc123" }
+    }))).await.unwrap());
+
+    assert_eq!(result["scan"]["scanned"], 2);
+    let commits = result["commits"].as_array().unwrap();
+    assert_eq!(commits.len(), 1);
+    assert_eq!(commits[0]["summary"], "fix login (abc123)");
+    assert_eq!(commits[0]["author"], "Dana");
+}
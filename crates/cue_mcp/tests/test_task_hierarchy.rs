@@ -0,0 +1,93 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::tool_call;
+use cue_mcp::handle_request;
+use serde_json::{json, Value};
+use std::env;
+
+#[tokio::test]
+async fn test_epic_hierarchy_tools() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards_dir = temp.child(".cuedeck/cards");
+    cards_dir.create_dir_all().unwrap();
+
+    cards_dir.child("epic01.md").write_str(r#"---
+title: Auth Epic
+status: active
+priority: high
+---
+# Auth Epic"#).unwrap();
+
+    cards_dir.child("kid001.md").write_str(r#"---
+title: Login Form
+status: done
+priority: medium
+parent: epic01
+---
+# Login Form"#).unwrap();
+
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    // create_task with a parent
+    {
+        let resp = handle_request(tool_call(1, "create_task", json!({
+            "title": "Logout Button",
+            "parent": "epic01"
+        }))).await.unwrap();
+        let result = resp.result.expect("create_task should succeed");
+        let doc: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(doc["frontmatter"]["parent"], "epic01");
+    }
+
+    // create_task with an unknown parent fails
+    {
+        let resp = handle_request(tool_call(2, "create_task", json!({
+            "title": "Orphan",
+            "parent": "nope00"
+        }))).await.unwrap();
+        assert!(resp.error.is_some());
+    }
+
+    // list_tasks nested returns the epic with children and progress
+    {
+        let resp = handle_request(tool_call(3, "list_tasks", json!({ "nested": true }))).await.unwrap();
+        let result = resp.result.unwrap();
+        let tree: Vec<Value> = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0]["id"], "epic01");
+        assert_eq!(tree[0]["progress"]["done"], 1);
+        assert_eq!(tree[0]["progress"]["total"], 2);
+        assert_eq!(tree[0]["children"].as_array().unwrap().len(), 2);
+    }
+
+    // list_tasks parent filter
+    {
+        let resp = handle_request(tool_call(4, "list_tasks", json!({ "parent": "epic01" }))).await.unwrap();
+        let result = resp.result.unwrap();
+        let tasks: Vec<Value> = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(tasks.len(), 2);
+    }
+
+    // Closing the epic while a child is open returns a warning
+    {
+        let resp = handle_request(tool_call(5, "update_task", json!({
+            "id": "epic01",
+            "updates": { "status": "done" }
+        }))).await.unwrap();
+        let result = resp.result.unwrap();
+        let doc: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        let warnings = doc["warnings"].as_array().expect("warnings expected");
+        assert!(warnings[0].as_str().unwrap().contains("1 open child card(s)"));
+    }
+
+    // Re-parenting the epic under its own child is a cycle
+    {
+        let resp = handle_request(tool_call(6, "update_task", json!({
+            "id": "epic01",
+            "updates": { "parent": "kid001" }
+        }))).await.unwrap();
+        assert!(resp.error.is_some());
+    }
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_task_history_records_client_author() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cuedeck/cards").create_dir_all().unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    // Client name from the handshake becomes the event author
+    handle_request(request(1, "initialize", json!({
+        "protocolVersion": "2024-11-05",
+        "clientInfo": { "name": "test-agent", "version": "1.0" }
+    }))).await.unwrap();
+
+    let created = tool_text(handle_request(request(2, "tools/call", json!({
+        "name": "create_task",
+        "arguments": { "title": "Tracked Task" }
+    }))).await.unwrap());
+    let id = created["path"]
+        .as_str()
+        .and_then(|p| std::path::Path::new(p).file_stem())
+        .and_then(|s| s.to_str())
+        .unwrap()
+        .to_string();
+
+    handle_request(request(3, "tools/call", json!({
+        "name": "update_task",
+        "arguments": { "id": id, "updates": { "status": "active" } }
+    }))).await.unwrap();
+
+    let history = tool_text(handle_request(request(4, "tools/call", json!({
+        "name": "get_task_history",
+        "arguments": { "id": id }
+    }))).await.unwrap());
+    let events = history.as_array().unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["action"], "created");
+    assert_eq!(events[1]["action"], "updated");
+    assert_eq!(events[1]["author"], "test-agent");
+    assert_eq!(events[1]["changes"][0]["field"], "status");
+    assert_eq!(events[1]["changes"][0]["old"], "todo");
+    assert_eq!(events[1]["changes"][0]["new"], "active");
+
+    // limit keeps the most recent events
+    let history = tool_text(handle_request(request(5, "tools/call", json!({
+        "name": "get_task_history",
+        "arguments": { "id": id, "limit": 1 }
+    }))).await.unwrap());
+    assert_eq!(history.as_array().unwrap().len(), 1);
+    assert_eq!(history[0]["action"], "updated");
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::request;
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_update_task_reports_cycle_as_structured_error() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards = temp.child(".cuedeck/cards");
+    cards.child("aaa111.md").write_str("---\ntitle: A\ndepends_on: [bbb222]\n---\n").unwrap();
+    cards.child("bbb222.md").write_str("---\ntitle: B\n---\n").unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let resp = handle_request(request(1, "tools/call", json!({
+        "name": "update_task",
+        "arguments": { "id": "bbb222", "updates": { "depends_on": { "add": ["aaa111"] }, "tags": ["core"] } }
+    }))).await.unwrap();
+
+    let error = resp.error.expect("cycle should be rejected");
+    assert_eq!(error.code, 1002);
+    assert_eq!(error.data.unwrap()["cycle"], json!(["bbb222", "aaa111", "bbb222"]));
+
+    // Tags alone are accepted as a list
+    let resp = handle_request(request(2, "tools/call", json!({
+        "name": "update_task",
+        "arguments": { "id": "bbb222", "updates": { "tags": { "add": ["core"] } } }
+    }))).await.unwrap();
+    assert!(resp.error.is_none());
+    let content = std::fs::read_to_string(temp.path().join(".cuedeck/cards/bbb222.md")).unwrap();
+    assert!(content.contains("- core"));
+}
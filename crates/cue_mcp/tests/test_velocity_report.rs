@@ -0,0 +1,40 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_get_velocity_report() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cards = temp.child(".cuedeck/cards");
+    cards
+        .child("aaa111.md")
+        .write_str("---\ntitle: A\nstatus: done\ntags: [api]\ncreated: 2024-06-03T09:00:00Z\nupdated: 2024-06-04T09:00:00Z\n---\n")
+        .unwrap();
+    cards
+        .child("bbb222.md")
+        .write_str("---\ntitle: B\nstatus: todo\ncreated: 2024-06-03T09:00:00Z\n---\n")
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let report = tool_text(handle_request(request(1, "tools/call", json!({
+        "name": "get_velocity_report",
+        "arguments": { "since": "2024-06-03", "until": "2024-06-09", "by": "tag" }
+    }))).await.unwrap());
+
+    assert_eq!(report["by"], "tag");
+    assert_eq!(report["burndown"].as_array().unwrap().len(), 7);
+    assert_eq!(report["burndown"][6]["open"], 1);
+    assert_eq!(report["throughput"], json!([{ "week": "2024-06-03", "closed": 1 }]));
+    assert_eq!(report["cycle_time"][0]["key"], "api");
+    assert_eq!(report["overall"]["p50_seconds"], 86_400);
+
+    let resp = handle_request(request(2, "tools/call", json!({
+        "name": "get_velocity_report",
+        "arguments": { "since": "2024-06-09", "until": "2024-06-03" }
+    }))).await.unwrap();
+    assert!(resp.error.unwrap().message.contains("after until"));
+}
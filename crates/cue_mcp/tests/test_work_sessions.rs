@@ -0,0 +1,69 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::json;
+use std::env;
+
+#[tokio::test]
+async fn test_work_sessions_and_time_report() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cuedeck/cards/abc123.md")
+        .write_str("---\ntitle: API work\nassignee: dana\ntags: [api]\n---\n")
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let started = tool_text(handle_request(request(1, "tools/call", json!({
+        "name": "start_work_session",
+        "arguments": { "id": "abc123", "author": "agent-a" }
+    }))).await.unwrap());
+    assert_eq!(started["author"], "agent-a");
+    assert!(started.get("end").is_none());
+
+    let resp = handle_request(request(2, "tools/call", json!({
+        "name": "start_work_session",
+        "arguments": { "id": "abc123", "author": "agent-a" }
+    }))).await.unwrap();
+    assert!(resp.error.unwrap().message.contains("already running"));
+
+    let stopped = tool_text(handle_request(request(3, "tools/call", json!({
+        "name": "stop_work_session",
+        "arguments": { "id": "abc123", "author": "agent-a" }
+    }))).await.unwrap());
+    assert!(stopped["end"].is_string());
+
+    handle_request(request(4, "tools/call", json!({
+        "name": "log_work_session",
+        "arguments": {
+            "id": "abc123",
+            "author": "agent-b",
+            "start": "2024-01-01T09:00:00Z",
+            "end": "2024-01-01T10:30:00Z"
+        }
+    }))).await.unwrap().result.expect("log should succeed");
+
+    let resp = handle_request(request(5, "tools/call", json!({
+        "name": "log_work_session",
+        "arguments": { "id": "abc123", "start": "2024-01-01T10:00:00Z", "end": "2024-01-01T09:00:00Z" }
+    }))).await.unwrap();
+    assert!(resp.error.unwrap().message.contains("end must be after"));
+
+    let report = tool_text(handle_request(request(6, "tools/call", json!({
+        "name": "get_time_report",
+        "arguments": { "by": "tag" }
+    }))).await.unwrap());
+    let buckets = report["buckets"].as_array().unwrap();
+    assert_eq!(buckets.len(), 1);
+    assert_eq!(buckets[0]["key"], "api");
+    assert_eq!(buckets[0]["sessions"], 2);
+    assert!(buckets[0]["seconds"].as_i64().unwrap() >= 90 * 60);
+
+    // The 2024 session falls outside a one-week window
+    let recent = tool_text(handle_request(request(7, "tools/call", json!({
+        "name": "get_time_report",
+        "arguments": { "since": "1w" }
+    }))).await.unwrap());
+    assert_eq!(recent["buckets"][0]["key"], "dana");
+    assert_eq!(recent["buckets"][0]["sessions"], 1);
+}
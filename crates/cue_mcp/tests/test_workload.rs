@@ -0,0 +1,54 @@
+mod common;
+
+use assert_fs::prelude::*;
+use common::{request, tool_text};
+use cue_mcp::handle_request;
+use serde_json::{json, Value};
+use std::env;
+
+#[tokio::test]
+async fn test_workload_and_capacity_warnings() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cuedeck/config.toml")
+        .write_str("[capacity]\nmax_open = 1\nmax_estimate = 5\n")
+        .unwrap();
+    temp.child(".cuedeck/cards/aaa111.md")
+        .write_str("---\ntitle: A\nstatus: active\nassignee: dana\nestimate: 2\n---\n")
+        .unwrap();
+    temp.child(".cuedeck/cards/bbb222.md")
+        .write_str("---\ntitle: B\nstatus: todo\n---\n")
+        .unwrap();
+    env::set_var("CUE_WORKSPACE", temp.path());
+
+    let created = tool_text(handle_request(request(1, "tools/call", json!({
+        "name": "create_task",
+        "arguments": { "title": "C", "assignee": "dana" }
+    }))).await.unwrap());
+    assert_eq!(created["warnings"], json!(["dana now has 2 open card(s), over the limit of 1"]));
+
+    let updated = tool_text(handle_request(request(2, "tools/call", json!({
+        "name": "update_task",
+        "arguments": { "id": "aaa111", "updates": { "estimate": 6 } }
+    }))).await.unwrap());
+    let warnings = updated["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[1].as_str().unwrap().contains("an estimate of 6"));
+
+    // Unassigned cards have no limit
+    let updated = tool_text(handle_request(request(3, "tools/call", json!({
+        "name": "update_task",
+        "arguments": { "id": "bbb222", "updates": { "priority": "high" } }
+    }))).await.unwrap());
+    assert!(updated.get("warnings").is_none());
+
+    let workload = tool_text(handle_request(request(4, "tools/call", json!({
+        "name": "get_workload",
+        "arguments": {}
+    }))).await.unwrap());
+    let dana = &workload["assignees"][0];
+    assert_eq!(dana["assignee"], "dana");
+    assert_eq!(dana["open"], 2);
+    assert_eq!(dana["estimate"], 6.0);
+    assert_eq!(dana["oldest_active"], Value::Null);
+    assert_eq!(dana["over_capacity"], true);
+}